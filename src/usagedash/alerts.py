@@ -0,0 +1,199 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.atomic import write_atomic
+from usagedash.config import AlertRule, Config
+from usagedash.killswitch import take_action
+from usagedash.models import UsageSnapshot
+from usagedash.oplog import log_event
+from usagedash.sinks import SinkError, deliver
+from usagedash.store import Store
+
+LEVELS = {"warn": 1, "critical": 2}
+
+
+@dataclass
+class AlertEvent:
+    rule: str
+    level: str
+    provider: str
+    window: str
+    value: float
+    sink: str
+    message: str
+
+
+def alert_state_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).parent / "alerts.json"
+
+
+def load_alert_state(cfg: Config) -> dict[str, dict]:
+    path = alert_state_path(cfg)
+    if not path.exists():
+        return {}
+    try:
+        raw = json.loads(path.read_text())
+    except (OSError, json.JSONDecodeError):
+        return {}
+    return raw if isinstance(raw, dict) else {}
+
+
+def save_alert_state(cfg: Config, state: dict[str, dict]) -> None:
+    path = alert_state_path(cfg)
+    write_atomic(path, json.dumps(state, indent=2), cfg.store.fsync)
+
+
+def _level_for(rule: AlertRule, value: float) -> str | None:
+    if rule.critical_pct is not None and value >= rule.critical_pct:
+        return "critical"
+    if value >= rule.warn_pct:
+        return "warn"
+    return None
+
+
+def evaluate_alerts(
+    cfg: Config,
+    snapshot: UsageSnapshot,
+    state: dict[str, dict],
+    now: datetime | None = None,
+) -> list[AlertEvent]:
+    """Decide which rules fire this cycle, updating `state` in place.
+
+    A rule fires when it first crosses a level, again immediately when it
+    escalates, and otherwise at most once per cooldown while it stays
+    active. `repeat_until_ack = false` turns it into a single-shot alert
+    per level. Dropping back below `warn_pct` clears the rule, ack included.
+    """
+    now = now or datetime.now()
+    by_provider = {p.key: p for p in snapshot.providers}
+    events: list[AlertEvent] = []
+
+    for rule in cfg.alerts:
+        snap = by_provider.get(rule.provider)
+        value = None
+        if rule.only_while_running and not (snap and (snap.details or {}).get("running")):
+            # Not running (or not detected): treat like dropping below warn.
+            state.pop(rule.name, None)
+            continue
+        if snap is not None and not (snap.details or {}).get("maintenance"):
+            value = snap.session_used_pct if rule.window == "session" else snap.weekly_used_pct
+        if value is None:
+            continue
+
+        level = _level_for(rule, value)
+        entry = state.get(rule.name)
+        if level is None:
+            state.pop(rule.name, None)
+            continue
+
+        if entry is None or LEVELS[level] > LEVELS.get(entry.get("level", ""), 0):
+            entry = {"level": level, "acked": False, "last_fired": None}
+            state[rule.name] = entry
+        elif LEVELS[level] < LEVELS[entry["level"]]:
+            # De-escalated: keep the ack but track the current level.
+            entry["level"] = level
+
+        if entry.get("acked"):
+            continue
+        last_fired = datetime.fromisoformat(entry["last_fired"]) if entry.get("last_fired") else None
+        if last_fired is not None:
+            if not rule.repeat_until_ack:
+                continue
+            if now - last_fired < timedelta(minutes=rule.cooldown_minutes):
+                continue
+
+        sink = rule.critical_sink if level == "critical" and rule.critical_sink else rule.sink
+        message = f"[{level.upper()}] {rule.provider} {rule.window} usage at {value:.1f}% ({rule.name})"
+        if rule.only_while_running:
+            message += " while agent is running"
+        events.append(AlertEvent(rule.name, level, rule.provider, rule.window, value, sink, message))
+        entry["last_fired"] = now.isoformat()
+
+    return events
+
+
+def dispatch(events: list[AlertEvent]) -> list[tuple[AlertEvent, str | None]]:
+    """Deliver events, returning each with its delivery error (None on success)."""
+    results: list[tuple[AlertEvent, str | None]] = []
+    for event in events:
+        payload = {
+            "rule": event.rule,
+            "level": event.level,
+            "provider": event.provider,
+            "window": event.window,
+            "value": event.value,
+        }
+        try:
+            deliver(event.sink, event.message, payload)
+            results.append((event, None))
+        except SinkError as exc:
+            results.append((event, str(exc)))
+    return results
+
+
+def record_history(
+    store: Store,
+    results: list[tuple[AlertEvent, str | None]],
+    now: datetime | None = None,
+) -> None:
+    fired_at = (now or datetime.now()).isoformat()
+    for event, error in results:
+        store.append_alert({
+            "fired_at": fired_at,
+            "rule": event.rule,
+            "level": event.level,
+            "provider": event.provider,
+            "window": event.window,
+            "value": event.value,
+            "sink": event.sink,
+            "delivered": error is None,
+            "error": error,
+        })
+
+
+def process_alerts(cfg: Config, store: Store, snapshot: UsageSnapshot) -> None:
+    if not cfg.alerts:
+        return
+    state = load_alert_state(cfg)
+    events = evaluate_alerts(cfg, snapshot, state)
+    for event in events:
+        log_event(cfg, "alert", event.message, "error" if event.level == "critical" else "warning")
+    record_history(store, dispatch(events))
+    rules = {rule.name: rule for rule in cfg.alerts}
+    for event in events:
+        rule = rules[event.rule]
+        if event.level != "critical" or not rule.action:
+            continue
+        done, error = take_action(cfg, rule)
+        log_event(cfg, "kill_switch", f"{rule.name}: {error or done}", "error" if error else "warning")
+        # The alert history doubles as the audit trail for actions taken.
+        store.append_alert({
+            "fired_at": datetime.now().isoformat(),
+            "rule": event.rule,
+            "level": event.level,
+            "provider": event.provider,
+            "window": event.window,
+            "value": event.value,
+            "sink": f"action:{done or rule.action}",
+            "delivered": error is None,
+            "error": error,
+        })
+    save_alert_state(cfg, state)
+
+
+def acknowledge(cfg: Config, name: str | None = None) -> list[str]:
+    """Silence active alerts until they clear or escalate; returns acked rule names."""
+    state = load_alert_state(cfg)
+    acked: list[str] = []
+    for rule_name, entry in state.items():
+        if name is not None and rule_name != name:
+            continue
+        if not entry.get("acked"):
+            entry["acked"] = True
+            acked.append(rule_name)
+    save_alert_state(cfg, state)
+    return acked
@@ -0,0 +1,140 @@
+from __future__ import annotations
+
+from dataclasses import replace
+from datetime import datetime
+
+from usagedash.config import Config, active_config_path, apply_env_overrides, load_config
+from usagedash.events import iter_claude_events
+from usagedash.models import ProviderSnapshot
+from usagedash.providers import ADAPTERS
+from usagedash.projects import tag_events
+from usagedash.providers.pricing import price_table
+from usagedash.report import group_events
+from usagedash.snapshot import build_snapshot, read_snapshot, snapshot_to_json
+from usagedash.store import open_store
+
+# Entry points for status lines and bar modules that want one provider,
+# e.g. `collect_one("claude").session_used_pct`. Nothing is persisted: no
+# state file, history, alerts, flags, or process scan. Non-Python tools can
+# run `usagedash query <provider>` or `usagedash snapshot` instead; there is
+# no C ABI since the core is Python. Notebooks can import this module as is:
+# `history_records` and `cost_records` return lists of flat dicts, ready for
+# `pandas.DataFrame(...)`.
+__all__ = [
+    "collect_one",
+    "collect_rows",
+    "collect_snapshot_json",
+    "cost_records",
+    "history_records",
+    "load_effective_config",
+    "provider_ids",
+    "read_latest_json",
+]
+
+
+def provider_ids() -> list[str]:
+    return list(ADAPTERS)
+
+
+def load_effective_config() -> Config:
+    """The user's config (or `USAGEDASH_CONFIG`) plus `USAGEDASH_*` overrides, never writing a default file."""
+    cfg = load_config(active_config_path(), create=False)
+    apply_env_overrides(cfg)
+    return cfg
+
+
+def collect_rows(provider: str, cfg: Config | None = None) -> list[ProviderSnapshot]:
+    """Every row of one provider (several for multi-account and custom providers).
+
+    Collects even when the provider is disabled in config, since the caller
+    asked for it by name.
+    """
+    if provider not in ADAPTERS:
+        raise KeyError(f"unknown provider {provider!r}; expected one of {', '.join(ADAPTERS)}")
+    cfg = cfg or load_effective_config()
+    provider_cfg = cfg.providers[provider]
+    rows = ADAPTERS[provider]().collect_all(provider_cfg)
+    for row in rows:
+        row.display_name = provider_cfg.display_name or row.display_name
+    return rows
+
+
+def collect_one(provider: str, cfg: Config | None = None, account: str = "") -> ProviderSnapshot:
+    """One provider row; `account` picks a row of a multi-row provider, else the first."""
+    rows = collect_rows(provider, cfg)
+    if account:
+        for row in rows:
+            if row.account == account:
+                return row
+        raise KeyError(f"{provider} has no account {account!r}")
+    if not rows:
+        raise KeyError(f"{provider} reported no rows")
+    return rows[0]
+
+
+def collect_snapshot_json(cfg: Config | None = None) -> str:
+    """Every enabled provider as snapshot JSON, collected now and not persisted."""
+    return snapshot_to_json(build_snapshot(cfg or load_effective_config()), pretty=False)
+
+
+def read_latest_json(cfg: Config | None = None) -> str:
+    """The last snapshot the collector wrote, as JSON, whatever its on-disk encoding."""
+    cfg = cfg or load_effective_config()
+    return snapshot_to_json(read_snapshot(cfg.general.state_file), pretty=False)
+
+
+def history_records(
+    cfg: Config | None = None,
+    since: datetime | None = None,
+    until: datetime | None = None,
+    provider: str = "",
+) -> list[dict[str, object]]:
+    """One flat record per provider row per stored snapshot, oldest first.
+
+    `since` and `until` are naive UTC, as history is stored. The store is
+    opened read-only, so this never creates a database.
+    """
+    cfg = cfg or load_effective_config()
+    cfg = replace(cfg, general=replace(cfg.general, read_only=True))
+    records: list[dict[str, object]] = []
+    with open_store(cfg) as store:
+        for snap in store.snapshots(since=since, until=until):
+            for row in snap.providers:
+                if provider and row.provider.value != provider:
+                    continue
+                records.append({
+                    "at": snap.generated_at,
+                    "provider": row.provider.value,
+                    "account": row.account,
+                    "status": row.status.value,
+                    "session_used_pct": row.session_used_pct,
+                    "session_reset_at": row.session_reset_at,
+                    "weekly_used_pct": row.weekly_used_pct,
+                    "weekly_reset_at": row.weekly_reset_at,
+                })
+    return records
+
+
+def cost_records(
+    since: datetime, group_by: str = "model", cfg: Config | None = None, tag: str | None = None
+) -> list[dict[str, object]]:
+    """Estimated Claude spend since local time `since`, grouped like `usagedash report`.
+
+    Prices come from `providers.claude.pricing` over the built-in table;
+    turns of models with no known price count tokens but no cost. Tags come
+    from `project_tags` and each project's `.usagedash.toml`.
+    """
+    cfg = cfg or load_effective_config()
+    prices = price_table(cfg.providers["claude"].options.get("pricing"))
+    events = tag_events(cfg, list(iter_claude_events(since)))
+    rows = [
+        {
+            "key": r.key,
+            "events": r.events,
+            "input_tokens": r.input_tokens,
+            "output_tokens": r.output_tokens,
+            "cost_usd": round(r.cost_usd, 4),
+        }
+        for r in group_events(events, group_by, tag, prices)
+    ]
+    return sorted(rows, key=lambda r: r["cost_usd"], reverse=True)
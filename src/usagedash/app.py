@@ -1,7 +1,9 @@
 from __future__ import annotations
 
+import os
 import time
 from datetime import datetime
+from pathlib import Path
 
 from rich.console import Console, Group
 from rich.live import Live
@@ -10,35 +12,78 @@ from rich.text import Text
 
 from usagedash.cli import _render_panel
 from usagedash.config import Config
-from usagedash.snapshot import build_snapshot, write_snapshot_files
+from usagedash.formats import paused_line, render_html, render_plain
+from usagedash.durations import format_seconds
+from usagedash.oplog import log_event
+from usagedash.pipeline import MIN_REFRESH_SECONDS, refresh_interval, run_cycle
+from usagedash.reload import ConfigWatcher
+from usagedash.snapshot import snapshot_to_json
+from usagedash.timefmt import format_clock
 
 
 def _build_display(cfg: Config) -> Group:
-    snapshot = build_snapshot(cfg)
-    write_snapshot_files(cfg, snapshot)
+    snapshot = run_cycle(cfg)
 
     panels = [_render_panel(p) for p in snapshot.providers]
 
-    now = datetime.now().strftime("%H:%M:%S")
+    now = format_clock(datetime.now())
     footer = Text()
     footer.append(f"  {now}", style="bold bright_white")
-    footer.append(f"  |  refreshing every {cfg.general.refresh_seconds}s", style="dim")
+    paused = paused_line(snapshot)
+    if paused:
+        footer.append(f"  |  {paused}", style="bold yellow")
+    footer.append(f"  |  refreshing every {format_seconds(cfg.general.refresh_seconds)}", style="dim")
     footer.append("  |  Ctrl+C to exit", style="dim")
 
     return Group(*panels, footer)
 
 
-def run_dashboard(cfg: Config) -> None:
+def render_watch_output(cfg: Config, fmt: str) -> str:
+    snapshot = run_cycle(cfg)
+    if fmt == "json":
+        return snapshot_to_json(snapshot) + "\n"
+    if fmt == "html":
+        return render_html(snapshot, refresh_seconds=max(1, round(cfg.general.refresh_seconds)))
+    body = render_plain(snapshot.providers) + "\n"
+    paused = paused_line(snapshot)
+    return f"{paused}\n{body}" if paused else body
+
+
+def run_watch(watcher: ConfigWatcher, output: Path, fmt: str) -> None:
+    """Headless loop: rewrite the rendered summary to `output` every cycle."""
+    output.parent.mkdir(parents=True, exist_ok=True)
+    tmp = output.with_name(output.name + ".tmp")
+    cfg = watcher.current
+    log_event(cfg, "start", f"watch writing {output}")
+    try:
+        while True:
+            cfg = watcher.poll()
+            tmp.write_text(render_watch_output(cfg, fmt), encoding="utf-8")
+            # Replace atomically so web servers never serve a half-written file.
+            os.replace(tmp, output)
+            time.sleep(refresh_interval(cfg))
+    except KeyboardInterrupt:
+        pass
+    except Exception as exc:
+        log_event(cfg, "collect_failed", f"{type(exc).__name__}: {exc}", "error")
+        raise
+    finally:
+        log_event(cfg, "stop", "watch stopped")
+
+
+def run_dashboard(watcher: ConfigWatcher) -> None:
     console = Console()
+    cfg = watcher.current
     try:
         with Live(
             _build_display(cfg),
             console=console,
-            refresh_per_second=1,
+            refresh_per_second=max(1.0, 1.0 / max(cfg.general.refresh_seconds, MIN_REFRESH_SECONDS)),
             screen=True,
         ) as live:
             while True:
+                cfg = watcher.poll()
                 live.update(_build_display(cfg))
-                time.sleep(max(1, cfg.general.refresh_seconds))
+                time.sleep(refresh_interval(cfg))
     except KeyboardInterrupt:
         pass
@@ -0,0 +1,45 @@
+from __future__ import annotations
+
+import os
+import stat
+import tempfile
+from pathlib import Path
+
+FSYNC_POLICIES = ("always", "normal", "off")
+# Mode of a newly created file, as `open()` would give it under the usual 022 umask.
+DEFAULT_MODE = 0o644
+
+
+def write_atomic(path: Path, data: bytes | str, fsync: str = "normal") -> None:
+    """Replace `path` so readers and power loss see the old or new file, never a torn one.
+
+    `fsync = "always"` also syncs the file before the rename and the directory
+    after it, which is what survives a hard power-off; `normal` relies on the
+    rename alone and `off` writes in place.
+    """
+    path.parent.mkdir(parents=True, exist_ok=True)
+    raw = data.encode("utf-8") if isinstance(data, str) else data
+    if fsync == "off":
+        path.write_bytes(raw)
+        return
+    # A unique name per writer: two processes replacing the same file must
+    # not write into, or rename away, each other's temp file.
+    fd, tmp = tempfile.mkstemp(prefix=f".{path.name}.", suffix=".tmp", dir=path.parent)
+    try:
+        with os.fdopen(fd, "wb") as fh:
+            fh.write(raw)
+            if fsync == "always":
+                fh.flush()
+                os.fsync(fh.fileno())
+        # mkstemp creates 0600; keep the mode readers of the old file relied on.
+        os.chmod(tmp, stat.S_IMODE(path.stat().st_mode) if path.exists() else DEFAULT_MODE)
+        os.replace(tmp, path)
+    except BaseException:
+        Path(tmp).unlink(missing_ok=True)
+        raise
+    if fsync == "always" and os.name != "nt":
+        dir_fd = os.open(path.parent, os.O_RDONLY)
+        try:
+            os.fsync(dir_fd)
+        finally:
+            os.close(dir_fd)
@@ -0,0 +1,160 @@
+from __future__ import annotations
+
+import threading
+import time
+from collections import deque
+from collections.abc import Callable
+from dataclasses import dataclass, field
+
+from usagedash.config import Config
+from usagedash.models import UsageSnapshot
+from usagedash.oplog import log_event
+
+Handler = Callable[[Config, UsageSnapshot], None]
+
+
+@dataclass(eq=False)
+class Subscriber:
+    name: str
+    handler: Handler
+    # A failing required subscriber fails the cycle (after the others ran);
+    # any other failure is only logged.
+    required: bool = False
+    delivered: int = 0
+    failed: int = 0
+    dropped: int = 0
+    pending: deque[tuple[Config, UsageSnapshot]] = field(default_factory=deque)
+    worker: threading.Thread | None = None
+    busy: bool = False
+
+
+class Bus:
+    """Fan a collected snapshot out to independent consumers.
+
+    Each subscriber gets its own error handling, so a broken sink or flags
+    directory does not stop history, alerts or the state file. Failures are
+    logged, to stderr when `[log] sinks` is empty. Subscribers
+    named in `bus.queued` are fed from a bounded queue on their own thread.
+    """
+
+    def __init__(self) -> None:
+        self._lock = threading.Lock()
+        self._wake = threading.Condition(self._lock)
+        self._subscribers: list[Subscriber] = []
+
+    def subscribe(self, name: str, handler: Handler, required: bool = False) -> Callable[[], None]:
+        """Add a consumer, called in subscription order; returns its unsubscribe."""
+        sub = Subscriber(name, handler, required)
+        with self._lock:
+            self._subscribers.append(sub)
+
+        def unsubscribe() -> None:
+            with self._lock:
+                if sub in self._subscribers:
+                    self._subscribers.remove(sub)
+                    sub.pending.clear()
+                    self._wake.notify_all()
+
+        return unsubscribe
+
+    @property
+    def names(self) -> list[str]:
+        with self._lock:
+            return [s.name for s in self._subscribers]
+
+    def publish(self, cfg: Config, snapshot: UsageSnapshot) -> list[str]:
+        """Deliver to every subscriber; returns the inline ones that failed."""
+        with self._lock:
+            subscribers = list(self._subscribers)
+        failed: list[str] = []
+        first_required: Exception | None = None
+        for sub in subscribers:
+            if not sub.required and sub.name in cfg.bus.queued:
+                self._enqueue(cfg, sub, snapshot)
+                continue
+            error = self._deliver(cfg, sub, snapshot)
+            if error is not None:
+                failed.append(sub.name)
+                if sub.required and first_required is None:
+                    first_required = error
+        if first_required is not None:
+            raise first_required
+        return failed
+
+    def _deliver(self, cfg: Config, sub: Subscriber, snapshot: UsageSnapshot) -> Exception | None:
+        try:
+            sub.handler(cfg, snapshot)
+        except Exception as exc:
+            with self._lock:
+                sub.failed += 1
+            # Unlogged, a failing history or alert subscriber would go unnoticed.
+            log_event(cfg, "sink_failed", f"{sub.name}: {type(exc).__name__}: {exc}", "error", stderr_fallback=True)
+            return exc
+        with self._lock:
+            sub.delivered += 1
+        return None
+
+    def _enqueue(self, cfg: Config, sub: Subscriber, snapshot: UsageSnapshot) -> None:
+        limit = 1 if cfg.bus.policy == "coalesce" else cfg.bus.max_queue
+        with self._lock:
+            if len(sub.pending) >= limit:
+                sub.dropped += 1
+                if cfg.bus.policy == "drop_newest":
+                    return
+                sub.pending.popleft()
+            sub.pending.append((cfg, snapshot))
+            if sub.worker is None:
+                sub.worker = threading.Thread(target=self._run, args=(sub,), name=f"bus-{sub.name}", daemon=True)
+                sub.worker.start()
+            self._wake.notify_all()
+
+    def _run(self, sub: Subscriber) -> None:
+        while True:
+            with self._lock:
+                while not sub.pending:
+                    if sub not in self._subscribers:
+                        sub.worker = None
+                        return
+                    self._wake.wait()
+                cfg, snapshot = sub.pending.popleft()
+                sub.busy = True
+            self._deliver(cfg, sub, snapshot)
+            with self._lock:
+                sub.busy = False
+                self._wake.notify_all()
+
+    def drain(self, timeout: float) -> bool:
+        """Wait up to `timeout` seconds for queued deliveries; True when all caught up."""
+        deadline = time.monotonic() + timeout
+        with self._lock:
+            while any(s.pending or s.busy for s in self._subscribers):
+                remaining = deadline - time.monotonic()
+                if remaining <= 0:
+                    return False
+                self._wake.wait(remaining)
+        return True
+
+    def stats(self) -> dict[str, dict[str, int]]:
+        with self._lock:
+            return {
+                s.name: {"queue_depth": len(s.pending), "delivered": s.delivered, "failed": s.failed, "dropped": s.dropped}
+                for s in self._subscribers
+            }
+
+
+_METRICS = (
+    ("queue_depth", "usagedash_bus_queue_depth", "gauge", "Snapshots waiting in a subscriber's queue."),
+    ("delivered", "usagedash_bus_delivered_total", "counter", "Snapshots a subscriber handled."),
+    ("failed", "usagedash_bus_failed_total", "counter", "Snapshots a subscriber raised on."),
+    ("dropped", "usagedash_bus_dropped_total", "counter", "Snapshots dropped or coalesced away from a full queue."),
+)
+
+
+def render_bus_metrics(bus: Bus) -> str:
+    stats = bus.stats()
+    lines: list[str] = []
+    for key, metric, kind, help_text in _METRICS:
+        lines.append(f"# HELP {metric} {help_text}")
+        lines.append(f"# TYPE {metric} {kind}")
+        lines.extend(f'{metric}{{subscriber="{name}"}} {values[key]}' for name, values in stats.items())
+    return "\n".join(lines) + "\n"
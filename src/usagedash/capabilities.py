@@ -0,0 +1,53 @@
+from __future__ import annotations
+
+import importlib.util
+
+from usagedash import __version__
+from usagedash.config import STATE_FORMATS, Config
+from usagedash.models import SNAPSHOT_SCHEMA_VERSION
+from usagedash.plugins import list_provider_plugins, list_subcommands
+from usagedash.providers import ADAPTERS
+
+# Bump when a field is removed or changes meaning; additions keep the version.
+CONFIG_SCHEMA_VERSION = 1
+
+# Optional extras and the module that signals each is installed.
+_FEATURES = {
+    "postgres": "psycopg",
+    "parquet": "pyarrow",
+    "cbor": "cbor2",
+    "zstd": "zstandard",
+    "tray": "pystray",
+    "dns_srv": "dns.resolver",
+}
+
+
+def _installed(module: str) -> bool:
+    try:
+        return importlib.util.find_spec(module) is not None
+    except (ImportError, ValueError):
+        return False
+
+
+def capabilities(cfg: Config) -> dict:
+    """What this install can do, so frontends can adapt instead of guessing."""
+    return {
+        "version": __version__,
+        "schemas": {"snapshot": SNAPSHOT_SCHEMA_VERSION, "config": CONFIG_SCHEMA_VERSION},
+        "providers": {
+            name: {"enabled": cfg.providers[name].enabled, "experimental": adapter.experimental}
+            for name, adapter in ADAPTERS.items()
+        },
+        "plugins": sorted(list_subcommands()),
+        "provider_plugins": sorted(list_provider_plugins()),
+        "features": {name: _installed(module) for name, module in _FEATURES.items()},
+        "formats": {
+            "panel": ["panel", "plain", "template"],
+            "watch": ["text", "html"],
+            "snapshot": ["json"],
+            "state": list(STATE_FORMATS),
+            "events": ["csv", "parquet"],
+            "metrics": ["prometheus"],
+        },
+        "read_only": cfg.general.read_only,
+    }
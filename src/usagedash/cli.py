@@ -1,20 +1,60 @@
 from __future__ import annotations
 
 import argparse
+import copy
 import json
+import os
 import platform
-from dataclasses import asdict
+import sys
+import tomllib
+from dataclasses import asdict, replace
 from pathlib import Path
 
-from datetime import datetime
+from datetime import datetime, timezone
 
 from rich.console import Console
 from rich.panel import Panel
 from rich.table import Table
 from rich.text import Text
 
-from usagedash.config import CONFIG_PATH, load_config, save_config, set_config_value
-from usagedash.snapshot import build_snapshot, snapshot_to_json, write_snapshot_files
+from usagedash.alerts import acknowledge
+from usagedash.atomic import write_atomic
+from usagedash.api import collect_one, history_records
+from usagedash.capabilities import capabilities
+from usagedash.config import (
+    CONFIG_PATH,
+    PROVIDER_DEFAULTS,
+    Config,
+    ConfigError,
+    active_config_path,
+    apply_env_overrides,
+    load_config,
+    save_config,
+    set_config_value,
+)
+from usagedash.digest import build_digest, send_digest
+from usagedash.dryrun import config_diff, failed_providers, validation_pass
+from usagedash.durations import parse_duration
+from usagedash.heartbeat import check_heartbeat
+from usagedash.killswitch import resume_agents
+from usagedash.messages import configure as configure_messages
+from usagedash.models import Percent, UsageSnapshot
+from usagedash.history import snapshot_at
+from usagedash.events import EVENT_COLUMNS, iter_claude_events, select_columns, write_csv, write_parquet
+from usagedash.formats import paused_line, render_plain, render_prometheus, render_project_plain, render_summary, render_template, resolve_template
+from usagedash.plugins import find_subcommand, run_subcommand
+from usagedash.pause import mark_snapshot, pause, resume
+from usagedash.paths import home_dir, home_warning
+from usagedash.pipeline import collect_or_reuse
+from usagedash.protocol import SUPPORTED_VERSIONS, Message, ProtocolError, decode, encode
+from usagedash.projects import PROJECT_FILE, ProjectUsage, find_project_budget, project_usage, tag_events
+from usagedash.providers.pricing import price_table
+from usagedash.report import ReportRow, group_events
+from usagedash.schedule import render_cron, render_systemd, schedule_times, upcoming_resets
+from usagedash.sinks import SinkError
+from usagedash.snapshot import snapshot_to_json
+from usagedash.store import open_store
+from usagedash.timefmt import configure as configure_timefmt, format_clock, format_duration, format_reset
 from usagedash.tray import run_tray
 
 
@@ -30,7 +70,7 @@ def _cli_bar(value: float | None, width: int = 30) -> Text:
     if value is None:
         return Text("── no data ──", style="dim")
     shown = max(0.0, value)
-    bar_pct = min(100.0, shown)
+    bar_pct = Percent(shown).bar
     filled = int(round((bar_pct / 100.0) * width))
     empty = width - filled
     color = _bar_color(shown)
@@ -59,17 +99,6 @@ def _fmt_rate(value) -> str:
     return f"{value:.1f} tok/min"
 
 
-def _fmt_reset(dt) -> str:
-    if dt is None:
-        return "-"
-    if isinstance(dt, str):
-        try:
-            dt = datetime.fromisoformat(dt)
-        except ValueError:
-            return dt
-    return dt.strftime("%b %d  %H:%M")
-
-
 def _fmt_runout(runout_iso: str | None) -> Text:
     if not runout_iso:
         return Text("-", style="dim")
@@ -81,12 +110,7 @@ def _fmt_runout(runout_iso: str | None) -> Text:
     total_seconds = remaining.total_seconds()
     if total_seconds <= 0:
         return Text("EXHAUSTED", style="bold red")
-    hours = int(total_seconds // 3600)
-    minutes = int((total_seconds % 3600) // 60)
-    if hours > 0:
-        time_str = f"{hours}h {minutes}m remaining"
-    else:
-        time_str = f"{minutes}m remaining"
+    time_str = f"{format_duration(total_seconds)} remaining"
     color = "red" if total_seconds < 1800 else "yellow" if total_seconds < 7200 else "green"
     return Text(time_str, style=color)
 
@@ -106,7 +130,12 @@ def _model_name(raw: str) -> str:
     return f"{name} {ver}".strip() if ver else name
 
 
-def _render_panel(provider) -> Panel:
+def _panel_title(provider) -> str:
+    title = provider.label if provider.display_name or provider.account else provider.provider.value.upper()
+    return f"{title} *" if (provider.details or {}).get("active") else title
+
+
+def _render_panel(provider, detail: bool = False) -> Panel:
     table = Table.grid(padding=(0, 1), expand=True)
     table.add_column("label", no_wrap=True, style="bold bright_white", ratio=1)
     table.add_column("value", ratio=4)
@@ -116,17 +145,35 @@ def _render_panel(provider) -> Panel:
     status_text = Text()
     status_text.append(f"● {provider.status.value.upper()}", style=f"bold {status_color}")
     status_text.append(f"    source: {provider.source.value}", style="dim")
+    running = (provider.details or {}).get("running")
+    if running:
+        status_text.append(f"    ▶ running ({running})", style="bold green")
     table.add_row("Status", status_text)
+    if provider.activity:
+        active = provider.activity == "active"
+        table.add_row("Activity", Text(provider.activity, style="bold green" if active else "dim"))
 
     # ── Session ──
     table.add_row("", Text())
-    table.add_row(Text("Session", style="bold cyan"), _cli_bar(provider.session_used_pct))
-    table.add_row(Text("  resets", style="dim"), Text(_fmt_reset(provider.session_reset_at), style="bright_white"))
+    session_window = str((provider.details or {}).get("session_window", "session")).capitalize()
+    table.add_row(Text(session_window, style="bold cyan"), _cli_bar(provider.session_used_pct))
+    table.add_row(Text("  resets", style="dim"), Text(format_reset(provider.session_reset_at), style="bright_white"))
 
     # ── Weekly ──
     table.add_row("", Text())
-    table.add_row(Text("Weekly", style="bold magenta"), _cli_bar(provider.weekly_used_pct))
-    table.add_row(Text("  resets", style="dim"), Text(_fmt_reset(provider.weekly_reset_at), style="bright_white"))
+    long_window = str((provider.details or {}).get("long_window", "weekly")).capitalize()
+    table.add_row(Text(long_window, style="bold magenta"), _cli_bar(provider.weekly_used_pct))
+    table.add_row(Text("  resets", style="dim"), Text(format_reset(provider.weekly_reset_at), style="bright_white"))
+
+    # ── Context window ──
+    context = (provider.details or {}).get("context")
+    if isinstance(context, dict) and context.get("window_tokens"):
+        table.add_row("", Text())
+        table.add_row(Text("Context", style="bold yellow"), _cli_bar(context.get("used_pct")))
+        table.add_row(
+            Text("  tokens", style="dim"),
+            Text(f"{_fmt_num(context.get('used_tokens'))} / {_fmt_num(context.get('window_tokens'))}", style="bright_white"),
+        )
 
     # ── Codex details ──
     codex_dyn = (provider.details or {}).get("codex_limits", {})
@@ -173,6 +220,12 @@ def _render_panel(provider) -> Panel:
             Text(_fmt_rate(dyn.get("burn_rate_tokens_per_min")), style="bright_white"),
         )
         table.add_row(Text("Runout", style="bold blue"), _fmt_runout(dyn.get("predicted_tokens_runout_at")))
+        costs = (provider.details or {}).get("costs")
+        if isinstance(costs, dict):
+            table.add_row(
+                Text("Cost", style="bold blue"),
+                Text(f"${costs.get('today_usd', 0):,.2f} today  ${costs.get('week_usd', 0):,.2f} 7d  (est.)", style="bright_white"),
+            )
 
         model_dist = dyn.get("model_distribution", {})
         if isinstance(model_dist, dict) and model_dist:
@@ -184,35 +237,236 @@ def _render_panel(provider) -> Panel:
                 models_text.append(f" {v}%", style="bright_white")
             table.add_row(Text("Models", style="bold blue"), models_text)
 
+        split = dyn.get("model_split", {})
+        if detail and isinstance(split, dict) and split:
+            split_text = Text()
+            for i, (family, bucket) in enumerate(split.items()):
+                if i > 0:
+                    split_text.append("  ", style="dim")
+                split_text.append(family, style="bold cyan")
+                split_text.append(f" {_fmt_num(bucket.get('tokens'))} ({bucket.get('pct')}%)", style="bright_white")
+                if bucket.get("subagent_tokens"):
+                    split_text.append(f" {_fmt_num(bucket['subagent_tokens'])} via subagents", style="dim")
+            table.add_row(Text("Split", style="bold blue"), split_text)
+
+    if provider.provider.value == "claude":
+        for family in ("opus", "sonnet"):
+            family_weekly = (provider.details or {}).get(f"{family}_weekly_used_pct")
+            if isinstance(family_weekly, (int, float)):
+                table.add_row(Text(f"{family.capitalize()} weekly", style="bold magenta"), _cli_bar(float(family_weekly)))
+
     # ── Notes ──
     if provider.messages:
         table.add_row("", Text())
-        notes = " | ".join(provider.messages)
+        notes = " | ".join(provider.rendered_messages(detail))
         table.add_row(Text("Notes", style="dim"), Text(notes, style="dim italic"))
 
     border = {"ok": "#2be38f", "partial": "#f2c94c", "error": "#ff5e6c"}.get(provider.status.value, "#7184d6")
     return Panel(
         table,
-        title=f"[bold bright_white] {provider.provider.value.upper()} [/]",
-        subtitle=f"[dim]updated {provider.updated_at.strftime('%H:%M:%S')}[/]" if provider.updated_at else None,
+        title=f"[bold bright_white] {_panel_title(provider)} [/]",
+        subtitle=f"[dim]updated {format_clock(provider.updated_at)}[/]" if provider.updated_at else None,
+        border_style=border,
+        padding=(1, 2),
+    )
+
+
+def _render_project_panel(usage: ProjectUsage) -> Panel:
+    table = Table.grid(padding=(0, 1), expand=True)
+    table.add_column("label", no_wrap=True, style="bold bright_white", ratio=1)
+    table.add_column("value", ratio=4)
+    for label, used, limit, pct in (
+        ("Today", usage.daily_tokens, usage.budget.daily_tokens, usage.daily_pct),
+        ("7 days", usage.weekly_tokens, usage.budget.weekly_tokens, usage.weekly_pct),
+    ):
+        if limit is None:
+            continue
+        table.add_row(Text(label, style="bold cyan"), _cli_bar(pct))
+        table.add_row(
+            Text("  tokens", style="dim"),
+            Text(f"{_fmt_num(used)} / {_fmt_num(limit)}", style="bright_white"),
+        )
+    border = "#ff5e6c" if usage.exceeded else "#7184d6"
+    return Panel(
+        table,
+        title=f"[bold bright_white] PROJECT {usage.budget.name} [/]",
+        subtitle=f"[dim]{usage.budget.root}[/]",
         border_style=border,
         padding=(1, 2),
     )
 
 
-def main() -> None:
+def _render_report(rows: list[ReportRow], group_by: str) -> Table:
+    table = Table(title=f"Usage by {group_by}", expand=True)
+    table.add_column(group_by.capitalize())
+    for col in ("Turns", "Input", "Output", "Total", "Cost"):
+        table.add_column(col, justify="right")
+    for r in rows:
+        table.add_row(
+            r.key, _fmt_num(r.events), _fmt_num(r.input_tokens), _fmt_num(r.output_tokens), _fmt_num(r.tokens), f"${r.cost_usd:,.2f}"
+        )
+    return table
+
+
+def _fmt_pct(value: object) -> Text:
+    if not isinstance(value, (int, float)):
+        return Text("-", style="dim")
+    return Text(f"{value:.1f}%", style=_bar_color(float(value)))
+
+
+def _render_history(records: list[dict]) -> Table:
+    table = Table(title="Usage history", expand=True)
+    table.add_column("Recorded")
+    table.add_column("Provider")
+    table.add_column("Session", justify="right")
+    table.add_column("Weekly", justify="right")
+    table.add_column("Status")
+    for r in records:
+        # History is stored in UTC; show the local time it was recorded.
+        recorded = r["at"].replace(tzinfo=timezone.utc).astimezone().replace(tzinfo=None)
+        name = f"{r['provider']} ({r['account']})" if r["account"] else str(r["provider"])
+        table.add_row(format_reset(recorded), name, _fmt_pct(r["session_used_pct"]), _fmt_pct(r["weekly_used_pct"]), str(r["status"]))
+    return table
+
+
+def _render_alert_history(records: list[dict]) -> Table:
+    table = Table(title="Alert history", expand=True)
+    for col in ("Fired", "Rule", "Level", "Value", "Sink", "Delivery"):
+        table.add_column(col)
+    for r in records:
+        level_color = "red" if r.get("level") == "critical" else "yellow"
+        if r.get("delivered"):
+            delivery = Text("ok", style="green")
+        else:
+            delivery = Text(f"failed: {r.get('error')}", style="red")
+        table.add_row(
+            format_reset(r.get("fired_at")),
+            str(r.get("rule")),
+            Text(str(r.get("level")), style=level_color),
+            f"{r.get('value', 0.0):.1f}%",
+            str(r.get("sink")),
+            delivery,
+        )
+    return table
+
+
+def _dry_run(active: Config, candidate: Config, console: Console) -> int:
+    """Print what `candidate` changes, then collect once with it; 1 when a provider errors."""
+    changes = config_diff(active, candidate)
+    print("\n".join(changes) if changes else "no effective changes")
+    snapshot = validation_pass(candidate)
+    print()
+    print(render_summary(snapshot.providers))
+    failed = failed_providers(snapshot)
+    if failed:
+        console.print(f"[red]collection failed for {', '.join(failed)}[/red]")
+        return 1
+    return 0
+
+
+def _post_ipc(url: str, message: Message, headers: dict[str, str] | None = None) -> Message:
+    import urllib.error
+    import urllib.request
+
+    data = encode(message).encode("utf-8")
+    req = urllib.request.Request(f"{url.rstrip('/')}/ipc", data=data, headers=headers or {}, method="POST")
+    try:
+        with urllib.request.urlopen(req, timeout=10) as resp:
+            return decode(resp.read())
+    except urllib.error.HTTPError as exc:
+        return decode(exc.read())
+
+
+def _request_reload(cfg: Config, url: str) -> int:
+    """POST a `reload` message to a running server; dashboard and watch pick up edits on their own."""
+    from usagedash.server import SESSION_HEADER, ipc_token_path, read_ipc_token
+
+    token = read_ipc_token(cfg)
+    if token is None:
+        print(f"no {ipc_token_path(cfg)}; is `usagedash serve` running with this config?", file=sys.stderr)
+        return 1
+    try:
+        reply = _post_ipc(url, Message(type="hello", payload={"versions": list(SUPPORTED_VERSIONS)}))
+        if reply.type == "welcome":
+            headers = {SESSION_HEADER: str(reply.payload.get("session", "")), "Authorization": f"Bearer {token}"}
+            reply = _post_ipc(url, Message(type="reload", version=reply.version), headers)
+    except (OSError, ProtocolError) as exc:
+        print(f"cannot reach {url}: {exc}", file=sys.stderr)
+        return 1
+    if reply.type == "error":
+        print(f"reload failed: {reply.payload.get('error', '')}", file=sys.stderr)
+        return 1
+    print("config reloaded")
+    return 0
+
+
+def main(argv: list[str] | None = None) -> None:
+    argv = sys.argv[1:] if argv is None else argv
     parser = argparse.ArgumentParser(prog="usagedash")
+    parser.add_argument(
+        "--read-only",
+        action="store_true",
+        help="collect and render without writing any state, history, or config",
+    )
+    parser.add_argument(
+        "--in-memory",
+        action="store_true",
+        help="write no files at all: config, state, and history stay in memory (also USAGEDASH_IN_MEMORY=1)",
+    )
+    parser.add_argument("--config", type=Path, help=f"config file to use instead of {CONFIG_PATH}")
     sub = parser.add_subparsers(dest="cmd")
 
     sub.add_parser("dashboard")
+    sub.add_parser("tui", help="full-screen dashboard with sparklines and keybindings (q, r, p)")
+
+    watch = sub.add_parser("watch")
+    watch.add_argument("--output", required=True, type=Path)
+    watch.add_argument("--format", choices=["text", "html", "json"], default="text")
 
     panel = sub.add_parser("panel")
-    panel.add_argument("--provider", choices=["all", "codex", "claude", "gemini"], default="all")
+    panel.add_argument("--provider", choices=["all", *PROVIDER_DEFAULTS], default="all")
+    panel.add_argument(
+        "--format",
+        choices=["panel", "plain", "summary", "template", "json"],
+        help="default: panel on a terminal, summary when stdout is piped or redirected",
+    )
+    panel.add_argument("--template", help="inline template, @file, or a name from [templates]")
+    panel.add_argument("--detail", action="store_true", help="show every provider message, not just the most severe")
+    panel.add_argument("--at", help="show status as of a past local time from history, e.g. '2025-03-01 14:00'")
+
+    serve_cmd = sub.add_parser("serve", help="collect continuously and serve /healthz, /metrics, /snapshot")
+    serve_cmd.add_argument("--bind", default="127.0.0.1:9184", help="host:port to listen on")
+
+    pause_cmd = sub.add_parser("pause", help="stop collecting without stopping running loops")
+    pause_cmd.add_argument("--for", dest="duration", help="resume automatically after e.g. 30m or 1h")
+    sub.add_parser("resume", help="undo `usagedash pause`")
+    reload_cmd = sub.add_parser("reload", help="ask a running `serve` to re-read config.toml now")
+    reload_cmd.add_argument("--url", default="http://127.0.0.1:9184", help="address of the running server")
+
+    snap_cmd = sub.add_parser(
+        "snapshot", aliases=["export"], help="collect once and print or export the snapshot (JSON or Prometheus)"
+    )
+    snap_cmd.add_argument("--format", choices=["json", "prometheus"], default="json")
+    snap_cmd.add_argument("--compact", action="store_true", help="single-line JSON instead of indented")
+    snap_cmd.add_argument(
+        "--output",
+        type=Path,
+        help="replace this file atomically instead of printing, e.g. a node_exporter textfile-collector .prom",
+    )
 
-    snap_cmd = sub.add_parser("snapshot")
-    snap_cmd.add_argument("--format", choices=["json"], default="json")
+    health = sub.add_parser("health")
+    health.add_argument("--format", choices=["json", "table", "plain"], default="json")
 
-    sub.add_parser("health")
+    query = sub.add_parser("query", help="collect one provider without the pipeline, for status lines")
+    query.add_argument("provider", choices=list(PROVIDER_DEFAULTS))
+    query.add_argument("--account", default="", help="row of a multi-account provider")
+    query.add_argument("--field", help="print one field, e.g. session_used_pct or details.context")
+
+    selftest = sub.add_parser("selftest", help="run the pipeline against a mock provider and report each stage")
+    selftest.add_argument("--no-sinks", action="store_true", help="don't send test messages to alert sinks")
+
+    caps_cmd = sub.add_parser("capabilities", help="describe providers, plugins, and formats")
+    caps_cmd.add_argument("--format", choices=["json", "text"], default="json")
 
     config = sub.add_parser("config")
     config_sub = config.add_subparsers(dest="config_cmd")
@@ -220,61 +474,474 @@ def main() -> None:
     config_set = config_sub.add_parser("set")
     config_set.add_argument("key")
     config_set.add_argument("value")
+    config_set.add_argument("--dry-run", action="store_true", help="show the change and test-collect without saving")
+
+    sub.add_parser("check", help="diff --config against the active config and test-collect without writing")
+
+    alerts = sub.add_parser("alerts")
+    alerts_sub = alerts.add_subparsers(dest="alerts_cmd")
+    alerts_ack = alerts_sub.add_parser("ack")
+    alerts_ack.add_argument("name", nargs="?", help="rule to acknowledge (default: all active)")
+    alerts_history = alerts_sub.add_parser("history")
+    alerts_history.add_argument("--since", default="7d", help="look-back window, e.g. 12h or 7d")
+    alerts_sub.add_parser("heartbeat", help="fire heartbeat.sink if collection has gone stale")
+    alerts_resume = alerts_sub.add_parser("resume", help="SIGCONT agents a `stop` kill-switch action suspended")
+    alerts_resume.add_argument("provider", nargs="?", help="only this provider's agents (default: all)")
+
+    greet = sub.add_parser("greet", help="one-line summary for shell rc files and session hooks")
+    greet.add_argument("--max-age", default="10m", help="reuse snapshots younger than this")
+    greet.add_argument("--budget", default="300ms", help="max time to wait for a refresh")
+
+    guard = sub.add_parser("guard", help="exit 2 when the project's .usagedash.toml budget is exceeded")
+    guard.add_argument("--cwd", type=Path, default=None)
+
+    schedule = sub.add_parser("schedule", help="generate systemd timers or cron entries aligned to resets")
+    schedule.add_argument("--around-resets", required=True, metavar="COMMAND")
+    schedule.add_argument("--provider", action="append", choices=list(PROVIDER_DEFAULTS))
+    schedule.add_argument("--window", choices=["session", "weekly", "both"], default="both")
+    schedule.add_argument("--delay", default="2m", help="how long after the reset to start")
+    schedule.add_argument("--format", choices=["systemd", "cron"], default="systemd")
+    schedule.add_argument("--name", default="usagedash-job")
+    schedule.add_argument("--output-dir", type=Path, help="write unit files here instead of printing")
+
+    report = sub.add_parser("report", help="token usage and estimated cost grouped by project, model, or tag")
+    report.add_argument("--since", default="7d")
+    report.add_argument("--group-by", choices=["project", "model", "provider", "tag"], default="project")
+    report.add_argument("--tag", help="with --group-by tag, split on this tag key only")
+
+    events_cmd = sub.add_parser("events")
+    events_sub = events_cmd.add_subparsers(dest="events_cmd")
+    events_export = events_sub.add_parser("export", help="bulk export raw token events")
+    events_export.add_argument("--format", choices=["csv", "parquet"], default="csv")
+    events_export.add_argument("--since", default="30d")
+    events_export.add_argument("--columns", help=f"comma-separated subset of: {', '.join(EVENT_COLUMNS)}")
+    events_export.add_argument("--output", type=Path, help="output file (required for parquet)")
+
+    history = sub.add_parser("history", help="recorded usage over time from the history store")
+    history.add_argument("--provider", choices=list(PROVIDER_DEFAULTS), help="only this provider")
+    history.add_argument("--since", default="7d", help="look-back window, e.g. 12h or 7d")
+    history.add_argument("--limit", type=int, default=50, help="show at most this many of the latest rows (0 for all)")
+
+    digest = sub.add_parser("digest")
+    digest.add_argument("--days", type=int, default=7)
+    digest.add_argument("--send", action="store_true", help="deliver via digest.sink instead of printing")
 
     tray = sub.add_parser("tray")
     tray_sub = tray.add_subparsers(dest="tray_cmd")
     tray_sub.add_parser("run")
 
-    args = parser.parse_args()
-    cfg = load_config()
+    if argv and not argv[0].startswith("-") and argv[0] not in sub.choices:
+        plugin = find_subcommand(argv[0])
+        if plugin:
+            raise SystemExit(run_subcommand(plugin, argv[1:]))
+
+    args = parser.parse_args(argv)
+    in_memory = args.in_memory or os.environ.get("USAGEDASH_IN_MEMORY", "") not in ("", "0")
+    warning = home_warning()
+    if warning and not in_memory:
+        print(f"usagedash: {warning}", file=sys.stderr)
+    config_path: Path = args.config or active_config_path()
+    if args.config:
+        # Plugins and subprocesses read the same file.
+        os.environ["USAGEDASH_CONFIG"] = str(config_path)
+    try:
+        cfg = load_config(config_path, create=not (args.read_only or in_memory or config_path != CONFIG_PATH))
+    except (OSError, ValueError) as exc:  # TOMLDecodeError is a ValueError
+        parser.error(f"cannot read {config_path}: {exc}")
+
+    def prepare(cfg: Config) -> None:
+        # Also applied to every hot reload, so flags and env keep winning.
+        apply_env_overrides(cfg)
+        if in_memory:
+            # Nowhere writable to persist to: no state file, mirror, or flags either.
+            cfg.store.backend = "memory"
+            cfg.general.read_only = True
+        if args.read_only:
+            cfg.general.read_only = True
+        configure_timefmt(cfg.general.locale, cfg.general.clock)
+        try:
+            configure_messages(cfg.general.messages_file)
+        except (OSError, ValueError) as exc:
+            raise ValueError(f"general.messages_file: {exc}") from exc
+
+    try:
+        prepare(cfg)
+    except ValueError as exc:
+        parser.error(str(exc))
 
     cmd = args.cmd or "dashboard"
     console = Console()
 
+    if cmd in ("dashboard", "tui", "watch", "serve"):
+        from usagedash.reload import ConfigWatcher
+
+        watcher = ConfigWatcher(cfg, config_path, prepare)
+        watcher.install_sighup()
+
     if cmd == "dashboard":
         from usagedash.app import run_dashboard
-        run_dashboard(cfg)
+        run_dashboard(watcher)
+        return
+
+    if cmd == "tui":
+        from usagedash.tui import run_tui
+        run_tui(watcher)
+        return
+
+    if cmd == "watch":
+        from usagedash.app import run_watch
+        run_watch(watcher, args.output, args.format)
+        return
+
+    if cmd == "serve":
+        from usagedash.server import serve
+        serve(watcher, args.bind)
         return
 
+    if cmd == "check":
+        # The running loops use the default file; compare what --config would change.
+        try:
+            active = load_config(CONFIG_PATH, create=False) if args.config else copy.deepcopy(cfg)
+            apply_env_overrides(active)
+        except (OSError, ValueError) as exc:
+            active = Config()
+            print(f"cannot read {CONFIG_PATH} ({exc}); comparing against defaults")
+        raise SystemExit(_dry_run(active, cfg, console))
+
+    if cmd == "reload":
+        raise SystemExit(_request_reload(cfg, args.url))
+
     if cmd == "panel":
-        snapshot = build_snapshot(cfg)
-        write_snapshot_files(cfg, snapshot)
+        as_of = None
+        if args.at:
+            try:
+                at = datetime.fromisoformat(args.at)
+            except ValueError:
+                parser.error(f"--at: invalid time {args.at!r} (expected e.g. '2025-03-01 14:00')")
+            with open_store(cfg) as store:
+                past = snapshot_at(store, at)
+            if past is None:
+                print(f"no history recorded in the day before {args.at}")
+                raise SystemExit(1)
+            snapshot = past
+            generated = snapshot.generated_at.replace(tzinfo=timezone.utc).astimezone().replace(tzinfo=None)
+            as_of = f"recorded {format_reset(generated)}"
+        else:
+            snapshot = collect_or_reuse(cfg)
         providers = snapshot.providers
         if args.provider != "all":
             providers = [p for p in providers if p.provider.value == args.provider]
+        # Project budgets are only tracked live, not in history.
+        budget_warning = None
+        try:
+            budget = None if args.at else find_project_budget()
+        except tomllib.TOMLDecodeError as exc:
+            budget, budget_warning = None, f"ignoring malformed {PROJECT_FILE}: {exc}"
+        usage = project_usage(budget) if budget is not None else None
+        paused = paused_line(snapshot)
+        fmt = args.format or ("panel" if sys.stdout.isatty() else "summary")
+        if budget_warning and fmt in ("json", "template"):
+            # Keep stdout parseable; the warning row goes to stderr instead.
+            print(f"usagedash: {budget_warning}", file=sys.stderr)
+        if fmt == "json":
+            # The snapshot as stored, reduced to --provider; pipe into jq.
+            print(snapshot_to_json(replace(snapshot, providers=providers)))
+            return
+        if fmt == "summary":
+            if as_of:
+                print(as_of)
+            if paused:
+                print(paused)
+            print(render_summary(providers))
+            if budget_warning:
+                print(budget_warning)
+            return
+        if fmt == "plain":
+            if as_of:
+                print(as_of)
+            if paused:
+                print(paused)
+            print(render_plain(providers, detail=args.detail))
+            if usage is not None:
+                print(render_project_plain(usage))
+            if budget_warning:
+                print(budget_warning)
+            return
+        if fmt == "template":
+            if not args.template:
+                parser.error("--format template requires --template")
+            snapshot.providers = providers
+            try:
+                print(render_template(resolve_template(cfg, args.template), snapshot))
+            except (OSError, ValueError) as exc:
+                parser.error(str(exc))
+            return
+        if as_of:
+            console.print(as_of, style="dim")
+        if paused:
+            console.print(paused, style="bold yellow")
         for p in providers:
-            console.print(_render_panel(p))
+            console.print(_render_panel(p, detail=args.detail))
+        if usage is not None:
+            console.print(_render_project_panel(usage))
+        if budget_warning:
+            console.print(budget_warning, style="bold yellow")
         return
 
-    if cmd == "snapshot":
-        snapshot = build_snapshot(cfg)
-        write_snapshot_files(cfg, snapshot)
-        print(snapshot_to_json(snapshot))
+    if cmd in ("pause", "resume"):
+        if cfg.general.read_only:
+            parser.error(f"{cmd} is disabled in read-only mode")
+        if cmd == "pause":
+            until = None
+            if args.duration:
+                try:
+                    until = datetime.now() + parse_duration(args.duration)
+                except ValueError as exc:
+                    parser.error(str(exc))
+            state = pause(cfg, until)
+            mark_snapshot(cfg, True, state.until)
+            print(f"paused until {format_reset(until)}" if until else "paused until `usagedash resume`")
+            return
+        if resume(cfg):
+            mark_snapshot(cfg, False, None)
+            print("resumed")
+        else:
+            print("not paused")
+        return
+
+    if cmd == "capabilities":
+        caps = capabilities(cfg)
+        if args.format == "json":
+            print(json.dumps(caps, indent=2))
+            return
+        print(f"usagedash {caps['version']}")
+        for name, info in caps["providers"].items():
+            print(f"provider {name}: {'enabled' if info['enabled'] else 'disabled'}")
+        for name, present in caps["features"].items():
+            print(f"feature {name}: {'available' if present else 'not installed'}")
+        for name in caps["plugins"]:
+            print(f"plugin {name}")
+        for name in caps["provider_plugins"]:
+            print(f"provider plugin {name}")
+        return
+
+    if cmd in ("snapshot", "export"):
+        snapshot = collect_or_reuse(cfg)
+        if args.format == "prometheus":
+            body = render_prometheus(snapshot)
+        else:
+            body = snapshot_to_json(snapshot, pretty=not args.compact) + "\n"
+        if args.output is None:
+            print(body, end="")
+            return
+        # The textfile collector may read at any moment: never expose a partial
+        # file, so `[store] fsync = "off"` still gets the rename.
+        write_atomic(args.output, body, "always" if cfg.store.fsync == "always" else "normal")
+        return
+
+    if cmd == "query":
+        try:
+            row = collect_one(args.provider, cfg, args.account)
+        except KeyError as exc:
+            print(exc.args[0], file=sys.stderr)
+            raise SystemExit(1)
+        data = json.loads(snapshot_to_json(UsageSnapshot(generated_at=row.updated_at, providers=[row]), pretty=False))
+        item = data["providers"][0]
+        if not args.field:
+            print(json.dumps(item, indent=2))
+            return
+        value = item.get("details", {}).get(args.field.removeprefix("details.")) if args.field.startswith("details.") else item.get(args.field)
+        print("" if value is None else value if isinstance(value, (str, int, float)) else json.dumps(value))
         return
 
     if cmd == "health":
         checks = {
             "config": str(CONFIG_PATH),
-            "codex_history": str(Path.home() / ".codex/history.jsonl"),
-            "claude_stats": str(Path.home() / ".claude/stats-cache.json"),
+            "codex_history": str(home_dir() / ".codex/history.jsonl"),
+            "claude_stats": str(home_dir() / ".claude/stats-cache.json"),
             "state_file": cfg.general.state_file,
             "windows_mirror": cfg.general.windows_state_path,
             "platform": platform.platform(),
         }
-        print(json.dumps(checks, indent=2))
+        if args.format == "table":
+            table = Table(title="Health", show_header=False)
+            for key, value in checks.items():
+                table.add_row(Text(key, style="bold cyan"), value)
+            console.print(table)
+        elif args.format == "plain":
+            print("\n".join(f"{key}: {value}" for key, value in checks.items()))
+        else:
+            print(json.dumps(checks, indent=2))
         return
 
+    if cmd == "selftest":
+        from usagedash.selftest import run_selftest
+        results = run_selftest(cfg, sinks=not args.no_sinks)
+        for r in results:
+            print(f"{'PASS' if r.ok else 'FAIL'}  {r.stage}: {r.detail}")
+        raise SystemExit(0 if all(r.ok for r in results) else 1)
+
     if cmd == "config":
         if args.config_cmd == "show":
             print(json.dumps(asdict(cfg), indent=2, default=str))
             return
+        if args.config_cmd == "set" and args.dry_run:
+            changed = copy.deepcopy(cfg)
+            try:
+                set_config_value(changed, args.key, args.value)
+            except ValueError as exc:
+                parser.error(str(exc))
+            raise SystemExit(_dry_run(cfg, changed, console))
         if args.config_cmd == "set":
+            if in_memory:
+                parser.error("config set has nowhere to save in --in-memory mode")
+            if cfg.general.read_only:
+                parser.error("config set is disabled in read-only mode")
             set_config_value(cfg, args.key, args.value)
-            save_config(cfg)
+            try:
+                save_config(cfg, config_path)
+            except ConfigError as exc:
+                parser.error(str(exc))
             print(f"updated {args.key}")
             return
         parser.error("config requires show or set")
 
+    if cmd == "alerts":
+        if args.alerts_cmd in ("ack", "heartbeat", "resume") and cfg.general.read_only:
+            parser.error(f"alerts {args.alerts_cmd} is disabled in read-only mode")
+        if args.alerts_cmd == "ack":
+            acked = acknowledge(cfg, args.name)
+            print(f"acknowledged: {', '.join(acked)}" if acked else "no active alerts to acknowledge")
+            return
+        if args.alerts_cmd == "resume":
+            resumed = resume_agents(cfg, args.provider)
+            print(f"resumed pid {', '.join(map(str, resumed))}" if resumed else "no stopped agents to resume")
+            return
+        if args.alerts_cmd == "history":
+            try:
+                since = datetime.now() - parse_duration(args.since)
+            except ValueError as exc:
+                parser.error(str(exc))
+            with open_store(cfg) as store:
+                console.print(_render_alert_history(store.alerts(since)))
+            return
+        if args.alerts_cmd == "heartbeat":
+            with open_store(cfg) as store:
+                event = check_heartbeat(cfg, store)
+            if event is not None:
+                print(event.message)
+                raise SystemExit(1)
+            print("heartbeat ok")
+            return
+        parser.error("alerts requires ack, history, or heartbeat")
+
+    if cmd == "greet":
+        from usagedash.greet import greet as run_greet
+        try:
+            max_age = parse_duration(args.max_age).total_seconds()
+            budget = parse_duration(args.budget).total_seconds()
+        except ValueError as exc:
+            parser.error(str(exc))
+        line = run_greet(cfg, config_path, max_age, budget)
+        if line is not None:
+            console.print(line)
+        return
+
+    if cmd == "guard":
+        budget = find_project_budget(args.cwd)
+        if budget is None:
+            return
+        usage = project_usage(budget)
+        if usage.exceeded:
+            windows = " and ".join(usage.exceeded)
+            print(f"usagedash: project {budget.name} is over its {windows} token budget", file=sys.stderr)
+            raise SystemExit(2)
+        return
+
+    if cmd == "schedule":
+        try:
+            delay = parse_duration(args.delay)
+        except ValueError as exc:
+            parser.error(str(exc))
+        windows = ("session", "weekly") if args.window == "both" else (args.window,)
+        resets = upcoming_resets(collect_or_reuse(cfg), args.provider, windows)
+        if not resets:
+            parser.exit(1, "usagedash: no upcoming reset times known for the selected providers\n")
+        times = schedule_times(resets, delay)
+        if args.format == "cron":
+            print(render_cron(args.around_resets, times), end="")
+            return
+        units = render_systemd(args.name, args.around_resets, times)
+        if args.output_dir is None:
+            for filename, body in units.items():
+                print(f"# {filename}\n{body}")
+            return
+        args.output_dir.mkdir(parents=True, exist_ok=True)
+        for filename, body in units.items():
+            (args.output_dir / filename).write_text(body)
+            print(f"wrote {args.output_dir / filename}")
+        return
+
+    if cmd == "report":
+        try:
+            since = datetime.now() - parse_duration(args.since)
+        except ValueError as exc:
+            parser.error(str(exc))
+        events = tag_events(cfg, list(iter_claude_events(since)))
+        prices = price_table(cfg.providers["claude"].options.get("pricing"))
+        console.print(_render_report(group_events(events, args.group_by, args.tag, prices), args.group_by))
+        return
+
+    if cmd == "events":
+        if args.events_cmd != "export":
+            parser.error("events requires export")
+        try:
+            since = datetime.now() - parse_duration(args.since)
+            columns = select_columns(args.columns)
+        except ValueError as exc:
+            parser.error(str(exc))
+        events = tag_events(cfg, sorted(iter_claude_events(since), key=lambda e: e.timestamp))
+        if args.format == "parquet":
+            if args.output is None:
+                parser.error("--format parquet requires --output")
+            try:
+                write_parquet(events, columns, args.output)
+            except RuntimeError as exc:
+                parser.exit(1, f"usagedash: {exc}\n")
+            return
+        if args.output is None:
+            write_csv(events, columns, sys.stdout)
+            return
+        with args.output.open("w", newline="", encoding="utf-8") as fh:
+            write_csv(events, columns, fh)
+        return
+
+    if cmd == "history":
+        try:
+            since = datetime.now(timezone.utc).replace(tzinfo=None) - parse_duration(args.since)
+        except ValueError as exc:
+            parser.error(str(exc))
+        records = history_records(cfg, since=since, provider=args.provider or "")
+        if not records:
+            print(f"no history recorded in the last {args.since}")
+            return
+        console.print(_render_history(records[-args.limit:] if args.limit > 0 else records))
+        return
+
+    if cmd == "digest":
+        prices = price_table(cfg.providers["claude"].options.get("pricing"))
+        with open_store(cfg) as store:
+            body = build_digest(store, days=args.days, prices=prices)
+        if not args.send:
+            print(body, end="")
+            return
+        try:
+            send_digest(cfg, body)
+        except SinkError as exc:
+            parser.exit(1, f"usagedash: digest not sent: {exc}\n")
+        print("digest sent")
+        return
+
     if cmd == "tray":
         if args.tray_cmd != "run":
             parser.error("tray requires run")
@@ -0,0 +1,64 @@
+from __future__ import annotations
+
+import gzip
+
+ENCODINGS = ("none", "gzip", "zstd")
+
+# Smaller bodies gain nothing worth the CPU and header overhead.
+MIN_COMPRESS_BYTES = 1024
+
+_GZIP_MAGIC = b"\x1f\x8b"
+_ZSTD_MAGIC = b"\x28\xb5\x2f\xfd"
+
+
+def zstd_available() -> bool:
+    try:
+        import zstandard  # type: ignore[import-not-found]  # noqa: F401
+    except ImportError:
+        return False
+    return True
+
+
+def compress(data: bytes, encoding: str) -> bytes:
+    if encoding == "gzip":
+        # mtime=0 keeps output stable, so unchanged snapshots compare equal.
+        return gzip.compress(data, mtime=0)
+    if encoding == "zstd":
+        try:
+            import zstandard  # type: ignore[import-not-found]
+        except ImportError as exc:
+            raise ValueError("zstd compression needs usagedash[zstd]") from exc
+        return zstandard.ZstdCompressor().compress(data)
+    return data
+
+
+def decompress(data: bytes) -> bytes:
+    """Undo `compress`, recognizing the encoding from its magic bytes."""
+    if data[:2] == _GZIP_MAGIC:
+        return gzip.decompress(data)
+    if data[:4] == _ZSTD_MAGIC:
+        try:
+            import zstandard  # type: ignore[import-not-found]
+        except ImportError as exc:
+            raise ValueError("data is zstd compressed; install usagedash[zstd] to read it") from exc
+        return zstandard.ZstdDecompressor().decompressobj().decompress(data)
+    return data
+
+
+def accept_encoding(preferred: str) -> str:
+    """`Accept-Encoding` for a client configured with `preferred`."""
+    if preferred == "zstd" and zstd_available():
+        return "zstd, gzip"
+    return "gzip" if preferred in ("gzip", "zstd") else "identity"
+
+
+def negotiate_encoding(header: str | None, size: int) -> str | None:
+    """The encoding to answer a request's `Accept-Encoding` with, if any."""
+    if not header or size < MIN_COMPRESS_BYTES:
+        return None
+    offered = {part.split(";", 1)[0].strip().lower() for part in header.split(",")}
+    if "zstd" in offered and zstd_available():
+        return "zstd"
+    if "gzip" in offered:
+        return "gzip"
+    return None
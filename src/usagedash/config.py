@@ -1,14 +1,37 @@
 from __future__ import annotations
 
-from dataclasses import dataclass, field
+from dataclasses import dataclass, field, fields, is_dataclass
 from datetime import datetime
 from pathlib import Path
+import importlib.util
+import os
 import tomllib
 import tomli_w
 
+from usagedash.atomic import FSYNC_POLICIES
+from usagedash.compression import ENCODINGS
+from usagedash.cron import parse_cron
+from usagedash.durations import seconds_value
+from usagedash.models import ProviderSnapshot
+from usagedash.paths import config_dir, home_dir, state_dir
+from usagedash.timefmt import CLOCK_CHOICES
 
-HOME = Path.home()
-CONFIG_PATH = HOME / ".config/usagedash/config.toml"
+
+class ConfigError(RuntimeError):
+    pass
+
+
+STATE_FORMATS = ("json", "cbor")
+LOG_SINKS = ("syslog", "eventlog", "stderr")
+
+HOME = home_dir()
+CONFIG_PATH = config_dir() / "config.toml"
+
+
+def active_config_path() -> Path:
+    """`USAGEDASH_CONFIG` when set (`--config` exports it to plugins and children), else CONFIG_PATH."""
+    override = os.environ.get("USAGEDASH_CONFIG")
+    return Path(override).expanduser() if override else CONFIG_PATH
 
 
 @dataclass
@@ -24,32 +47,226 @@ class ProviderConfig:
     enabled: bool = True
     parser_mode: str = "hybrid"
     manual: ManualFields = field(default_factory=ManualFields)
+    display_name: str = ""
+    # Lower sorts first; ties sort by provider id.
+    order: int = 0
+    # Adapter-specific keys from the provider's table, e.g. `token_env` or `api_base`.
+    options: dict[str, object] = field(default_factory=dict)
+
+
+# Every known provider and its defaults. Providers that need credentials or
+# reach the network start disabled.
+PROVIDER_DEFAULTS: dict[str, dict] = {
+    "codex": {},
+    "claude": {},
+    "gemini": {"enabled": False, "parser_mode": "manual"},
+    "copilot": {"enabled": False},
+    "cursor": {"enabled": False},
+    "openai": {"enabled": False},
+    "groq": {"enabled": False},
+    "deepseek": {"enabled": False},
+    "xai": {"enabled": False},
+    "vertex": {"enabled": False},
+    "windsurf": {"enabled": False},
+    "aider": {"enabled": False},
+    "ollama": {"enabled": False},
+    "cohere": {"enabled": False},
+    "amazon_q": {"enabled": False},
+    "jetbrains": {"enabled": False},
+    "github_models": {"enabled": False},
+    "cline": {"enabled": False},
+    "fireworks": {"enabled": False},
+    "litellm": {"enabled": False},
+    # One row per `[providers.custom.<name>]` table; nothing runs until one is added.
+    "custom": {},
+    # One row per `usagedash-provider-<name>` executable on PATH. Off until
+    # enabled: otherwise anything on PATH would run every cycle unasked.
+    "plugin": {"enabled": False},
+}
+_PROVIDER_KEYS = ("enabled", "parser_mode", "manual", "display_name", "order")
 
 
 @dataclass
 class AppConfig:
-    refresh_seconds: int = 2
+    refresh_seconds: float = 2.0
+    refresh_jitter_seconds: float = 0.0
+    reuse_snapshot_seconds: int = 5
+    read_only: bool = False
+    pretty_state: bool = False
+    state_format: str = "json"
     timezone: str = "local"
-    state_file: str = str(HOME / ".local/state/usagedash/latest.json")
+    locale: str = "auto"
+    clock: str = "auto"
+    # TOML of `code = "template"` overriding built-in message text, e.g. a translation.
+    messages_file: str = ""
+    state_file: str = str(state_dir() / "latest.json")
     windows_state_path: str = "/mnt/c/Users/Public/AppData/Local/UsageDash/latest.json"
+    # Scan for running claude/codex/gemini CLIs each collection.
+    detect_processes: bool = True
+    # Under WSL, also list Windows-side processes through tasklist.exe (slower).
+    detect_windows_processes: bool = False
 
 
 @dataclass
 class TrayConfig:
     enabled: bool = True
-    poll_seconds: int = 15
+    poll_seconds: float = 15.0
     autostart: bool = True
 
 
+@dataclass
+class MaintenanceWindow:
+    name: str
+    # Cron expression; every minute it matches is inside the window.
+    cron: str
+    # Empty means every provider.
+    providers: list[str] = field(default_factory=list)
+
+    def applies_to(self, provider: str, now: datetime) -> bool:
+        if self.providers and provider not in self.providers:
+            return False
+        return parse_cron(self.cron).matches(now)
+
+
+@dataclass
+class FederationConfig:
+    targets: list[str] = field(default_factory=list)
+    dns_name: str = ""
+    srv_name: str = ""
+    timeout_seconds: float = 3.0
+    # Asked of every target via Accept-Encoding: "none", "gzip" or "zstd".
+    compression: str = "gzip"
+    # Per-target overrides, keyed by target URL as written in `targets`.
+    target_compression: dict[str, str] = field(default_factory=dict)
+    # Ask targets for per-provider changes since the last seen sequence and
+    # take a full snapshot every `full_sync_every` deltas (0: only on mismatch).
+    # Still pull: targets are polled when /metrics is scraped, never pushed to.
+    delta: bool = False
+    full_sync_every: int = 10
+
+
+@dataclass
+class StoreConfig:
+    backend: str = "sqlite"
+    path: str = ""
+    url: str = ""
+    # always: fsync every history row and state file; normal: safe against
+    # process crashes; off: fastest, may lose recent writes on power loss.
+    fsync: str = "normal"
+
+
+@dataclass
+class HistoryConfig:
+    enabled: bool = True
+    min_interval_seconds: int = 60
+    retention_days: int = 90
+
+
+@dataclass
+class DigestConfig:
+    sink: str = ""
+    smtp_host: str = "localhost"
+    smtp_port: int = 25
+    smtp_from: str = "usagedash@localhost"
+    smtp_username: str = ""
+    smtp_password_env: str = "USAGEDASH_SMTP_PASSWORD"
+
+
+@dataclass
+class HeartbeatConfig:
+    ping_url: str = ""
+    stale_minutes: int = 15
+    sink: str = ""
+    # Only count a collection where some provider came back ok; otherwise a
+    # collector failing on every provider would keep the switch quiet.
+    require_ok: bool = True
+
+
+@dataclass
+class FlagsConfig:
+    enabled: bool = True
+    # Empty means a `flags` directory next to `general.state_file`.
+    dir: str = ""
+    warn_pct: float = 80.0
+    exceeded_pct: float = 100.0
+
+
+@dataclass
+class MirrorConfig:
+    # Provider fields written to `general.windows_state_path`; empty writes all.
+    # `details.<key>` keeps one details entry. `provider` and `status` are always kept.
+    fields: list[str] = field(default_factory=list)
+    # "none", "gzip" or "zstd"; readers detect the encoding from the file itself.
+    compression: str = "none"
+
+
+QUEUE_POLICIES = ("drop_oldest", "drop_newest", "coalesce")
+# Optional subscribers registered on pipeline.BUS, in delivery order.
+BUS_SUBSCRIBERS = ("flags", "store", "alerts", "sources")
+
+
+@dataclass
+class BusConfig:
+    # Subscribers ("flags", "store", "alerts", "sources") delivered from their
+    # own bounded queue on a worker thread; queue "alerts" so a hanging webhook
+    # cannot stall collection or history. Only
+    # long-running modes (serve, watch, dashboard) benefit; required
+    # subscribers such as "state" always run inline.
+    queued: list[str] = field(default_factory=list)
+    max_queue: int = 16
+    # When a queue is full: drop the oldest or the newest snapshot, or
+    # "coalesce" to keep only the latest pending one.
+    policy: str = "drop_oldest"
+
+
+@dataclass
+class LogConfig:
+    # Any of "syslog", "eventlog" (Windows Application log), "stderr".
+    sinks: list[str] = field(default_factory=list)
+    ident: str = "usagedash"
+    min_level: str = "info"
+
+
+@dataclass
+class AlertRule:
+    name: str
+    # A provider id, or `provider:account` for one row of a multi-account provider.
+    provider: str
+    window: str = "weekly"
+    warn_pct: float = 80.0
+    critical_pct: float | None = 95.0
+    cooldown_minutes: int = 60
+    repeat_until_ack: bool = True
+    sink: str = "stderr"
+    critical_sink: str = ""
+    # Fire only while the provider's CLI process is running.
+    only_while_running: bool = False
+    # On critical: "stop" (SIGSTOP), "terminate" (SIGTERM) or "command:<cmd>".
+    # Refused unless the provider's table sets `kill_switch = true`.
+    action: str = ""
+
+
 @dataclass
 class Config:
     general: AppConfig = field(default_factory=AppConfig)
     tray: TrayConfig = field(default_factory=TrayConfig)
+    templates: dict[str, str] = field(default_factory=dict)
+    rate_limits: dict[str, str] = field(default_factory=dict)
+    project_tags: dict[str, dict[str, str]] = field(default_factory=dict)
+    alerts: list[AlertRule] = field(default_factory=list)
+    maintenance: list[MaintenanceWindow] = field(default_factory=list)
+    heartbeat: HeartbeatConfig = field(default_factory=HeartbeatConfig)
+    store: StoreConfig = field(default_factory=StoreConfig)
+    federation: FederationConfig = field(default_factory=FederationConfig)
+    history: HistoryConfig = field(default_factory=HistoryConfig)
+    digest: DigestConfig = field(default_factory=DigestConfig)
+    log: LogConfig = field(default_factory=LogConfig)
+    flags: FlagsConfig = field(default_factory=FlagsConfig)
+    mirror: MirrorConfig = field(default_factory=MirrorConfig)
+    bus: BusConfig = field(default_factory=BusConfig)
     providers: dict[str, ProviderConfig] = field(
         default_factory=lambda: {
-            "codex": ProviderConfig(enabled=True),
-            "claude": ProviderConfig(enabled=True),
-            "gemini": ProviderConfig(enabled=False, parser_mode="manual"),
+            name: provider_from_dict(defaults) for name, defaults in PROVIDER_DEFAULTS.items()
         }
     )
 
@@ -60,7 +277,7 @@ def _parse_dt(value: str | None) -> datetime | None:
     return datetime.fromisoformat(value)
 
 
-def _provider_from_dict(raw: dict) -> ProviderConfig:
+def provider_from_dict(raw: dict) -> ProviderConfig:
     manual_raw = raw.get("manual", {})
     manual = ManualFields(
         session_used_pct=manual_raw.get("session_used_pct"),
@@ -68,7 +285,14 @@ def _provider_from_dict(raw: dict) -> ProviderConfig:
         weekly_used_pct=manual_raw.get("weekly_used_pct"),
         weekly_reset_at=_parse_dt(manual_raw.get("weekly_reset_at")),
     )
-    return ProviderConfig(enabled=raw.get("enabled", True), parser_mode=raw.get("parser_mode", "hybrid"), manual=manual)
+    return ProviderConfig(
+        enabled=raw.get("enabled", True),
+        parser_mode=raw.get("parser_mode", "hybrid"),
+        manual=manual,
+        display_name=str(raw.get("display_name", "")),
+        order=int(raw.get("order", 0)),
+        options={k: v for k, v in raw.items() if k not in _PROVIDER_KEYS},
+    )
 
 
 def _provider_to_dict(cfg: ProviderConfig) -> dict:
@@ -86,67 +310,377 @@ def _provider_to_dict(cfg: ProviderConfig) -> dict:
         "enabled": cfg.enabled,
         "parser_mode": cfg.parser_mode,
         "manual": manual,
+        **({"display_name": cfg.display_name} if cfg.display_name else {}),
+        **({"order": cfg.order} if cfg.order else {}),
+        **cfg.options,
+    }
+
+
+def _maintenance_from_dict(raw: dict) -> MaintenanceWindow:
+    window = MaintenanceWindow(
+        name=str(raw.get("name", raw.get("cron", ""))),
+        cron=str(raw.get("cron", "")),
+        providers=[str(p) for p in raw.get("providers", [])],
+    )
+    parse_cron(window.cron)  # fail at load time rather than mid-collection
+    return window
+
+
+def active_maintenance(cfg: Config, provider: str, now: datetime | None = None) -> MaintenanceWindow | None:
+    now = now or datetime.now()
+    for window in cfg.maintenance:
+        if window.applies_to(provider, now):
+            return window
+    return None
+
+
+def _state_format(value: object) -> str:
+    if value not in STATE_FORMATS:
+        raise ValueError(f"general.state_format must be one of {', '.join(STATE_FORMATS)}")
+    if value == "cbor" and importlib.util.find_spec("cbor2") is None:
+        # Otherwise the state file would quietly stay JSON.
+        raise ValueError('general.state_format = "cbor" needs the cbor2 package (pip install cbor2)')
+    return str(value)
+
+
+def _log_sinks(values: object) -> list[str]:
+    sinks = [str(s) for s in values] if isinstance(values, list) else [str(values)]
+    unknown = [s for s in sinks if s not in LOG_SINKS]
+    if unknown:
+        # A misspelt sink would otherwise drop every daemon event unnoticed.
+        raise ValueError(f"log.sinks: unknown sink {unknown[0]!r} (expected any of {', '.join(LOG_SINKS)})")
+    return sinks
+
+
+def _fsync_policy(value: object) -> str:
+    if value not in FSYNC_POLICIES:
+        # Anything else would quietly behave like "normal".
+        raise ValueError(f"store.fsync must be one of {', '.join(FSYNC_POLICIES)}")
+    return str(value)
+
+
+def _mirror_fields(values: object) -> list[str]:
+    names = [str(f) for f in values] if isinstance(values, list) else [str(values)]
+    known = {f.name for f in fields(ProviderSnapshot)}
+    for name in names:
+        if name not in known and not (name.startswith("details.") and name != "details."):
+            # A misspelt field would silently vanish from the mirror.
+            raise ValueError(f"mirror.fields: unknown field {name!r} (expected a provider field or details.<key>)")
+    return names
+
+
+def _bus_queued(values: object) -> list[str]:
+    names = [str(s) for s in values] if isinstance(values, list) else [str(values)]
+    unknown = [s for s in names if s not in BUS_SUBSCRIBERS]
+    if unknown:
+        # A misspelt name would leave that subscriber inline without a word.
+        raise ValueError(f"bus.queued: unknown subscriber {unknown[0]!r} (expected any of {', '.join(BUS_SUBSCRIBERS)})")
+    return names
+
+
+def _compression(value: object, key: str) -> str:
+    if value not in ENCODINGS:
+        raise ValueError(f"{key} must be one of {', '.join(ENCODINGS)}")
+    return str(value)
+
+
+def _alert_from_dict(raw: dict) -> AlertRule:
+    missing = [key for key in ("name", "provider") if key not in raw]
+    if missing:
+        raise ValueError(f"alert {raw.get('name')!r}: missing {', '.join(missing)}")
+    window = raw.get("window", "weekly")
+    if window not in {"session", "weekly"}:
+        raise ValueError(f"alert {raw.get('name')!r}: window must be session or weekly")
+    action = str(raw.get("action", ""))
+    if action and action not in {"stop", "terminate"} and not action.startswith("command:"):
+        raise ValueError(f"alert {raw.get('name')!r}: action must be stop, terminate or command:<cmd>")
+    critical = raw.get("critical_pct", 95.0)
+    return AlertRule(
+        name=str(raw["name"]),
+        provider=str(raw["provider"]),
+        window=window,
+        warn_pct=float(raw.get("warn_pct", 80.0)),
+        critical_pct=float(critical) if critical is not None else None,
+        cooldown_minutes=int(raw.get("cooldown_minutes", 60)),
+        repeat_until_ack=bool(raw.get("repeat_until_ack", True)),
+        sink=str(raw.get("sink", "stderr")),
+        critical_sink=str(raw.get("critical_sink", "")),
+        only_while_running=bool(raw.get("only_while_running", False)),
+        action=action,
+    )
+
+
+def _alert_to_dict(rule: AlertRule) -> dict:
+    out: dict[str, object] = {
+        "name": rule.name,
+        "provider": rule.provider,
+        "window": rule.window,
+        "warn_pct": rule.warn_pct,
+        "cooldown_minutes": rule.cooldown_minutes,
+        "repeat_until_ack": rule.repeat_until_ack,
+        "sink": rule.sink,
     }
+    if rule.critical_pct is not None:
+        out["critical_pct"] = rule.critical_pct
+    if rule.critical_sink:
+        out["critical_sink"] = rule.critical_sink
+    if rule.only_while_running:
+        out["only_while_running"] = True
+    if rule.action:
+        out["action"] = rule.action
+    return out
+
 
+def load_config(path: Path = CONFIG_PATH, create: bool = True) -> Config:
+    """Load `path`, writing defaults there first when `create` is set.
 
-def load_config(path: Path = CONFIG_PATH) -> Config:
+    A read-only config dir (NixOS, locked-down containers) is not an error
+    here: the defaults are used in memory and only `save_config` complains.
+    """
     if not path.exists():
         cfg = Config()
-        save_config(cfg, path)
+        if create:
+            try:
+                save_config(cfg, path)
+            except ConfigError:
+                pass
         return cfg
 
     raw = tomllib.loads(path.read_text())
     general_raw = raw.get("general", {})
     tray_raw = raw.get("tray", {})
     providers_raw = raw.get("providers", {})
+    templates_raw = raw.get("templates", {})
+    rate_limits_raw = raw.get("rate_limits", {})
+    project_tags_raw = raw.get("project_tags", {})
+    alerts_raw = raw.get("alerts", [])
+    heartbeat_raw = raw.get("heartbeat", {})
+    store_raw = raw.get("store", {})
+    federation_raw = raw.get("federation", {})
+    history_raw = raw.get("history", {})
+    digest_raw = raw.get("digest", {})
+    log_raw = raw.get("log", {})
+    flags_raw = raw.get("flags", {})
+    mirror_raw = raw.get("mirror", {})
+    bus_raw = raw.get("bus", {})
+    if bus_raw.get("policy", "drop_oldest") not in QUEUE_POLICIES:
+        raise ValueError(f"bus.policy must be one of {', '.join(QUEUE_POLICIES)}")
 
     cfg = Config(
         general=AppConfig(
-            refresh_seconds=int(general_raw.get("refresh_seconds", 2)),
+            refresh_seconds=seconds_value(general_raw.get("refresh_seconds", 2)),
+            refresh_jitter_seconds=seconds_value(general_raw.get("refresh_jitter_seconds", 0)),
+            reuse_snapshot_seconds=int(general_raw.get("reuse_snapshot_seconds", 5)),
+            read_only=bool(general_raw.get("read_only", False)),
+            pretty_state=bool(general_raw.get("pretty_state", False)),
+            state_format=_state_format(general_raw.get("state_format", "json")),
             timezone=general_raw.get("timezone", "local"),
-            state_file=general_raw.get("state_file", str(HOME / ".local/state/usagedash/latest.json")),
+            locale=general_raw.get("locale", "auto"),
+            clock=general_raw.get("clock", "auto"),
+            messages_file=general_raw.get("messages_file", ""),
+            state_file=general_raw.get("state_file", str(state_dir() / "latest.json")),
             windows_state_path=general_raw.get("windows_state_path", "/mnt/c/Users/Public/AppData/Local/UsageDash/latest.json"),
+            detect_processes=bool(general_raw.get("detect_processes", True)),
+            detect_windows_processes=bool(general_raw.get("detect_windows_processes", False)),
         ),
         tray=TrayConfig(
             enabled=bool(tray_raw.get("enabled", True)),
-            poll_seconds=int(tray_raw.get("poll_seconds", 15)),
+            poll_seconds=seconds_value(tray_raw.get("poll_seconds", 15)),
             autostart=bool(tray_raw.get("autostart", True)),
         ),
+        templates={str(k): str(v) for k, v in templates_raw.items()},
+        rate_limits={str(k): str(v) for k, v in rate_limits_raw.items()},
+        project_tags={
+            str(root): {str(k): str(v) for k, v in tags.items()} for root, tags in project_tags_raw.items()
+        },
+        alerts=[_alert_from_dict(a) for a in alerts_raw],
+        maintenance=[_maintenance_from_dict(m) for m in raw.get("maintenance", [])],
+        heartbeat=HeartbeatConfig(
+            ping_url=heartbeat_raw.get("ping_url", ""),
+            stale_minutes=int(heartbeat_raw.get("stale_minutes", 15)),
+            sink=heartbeat_raw.get("sink", ""),
+            require_ok=bool(heartbeat_raw.get("require_ok", True)),
+        ),
+        store=StoreConfig(
+            backend=store_raw.get("backend", "sqlite"),
+            path=store_raw.get("path", ""),
+            url=store_raw.get("url", ""),
+            fsync=_fsync_policy(store_raw.get("fsync", "normal")),
+        ),
+        federation=FederationConfig(
+            targets=[str(t) for t in federation_raw.get("targets", [])],
+            dns_name=federation_raw.get("dns_name", ""),
+            srv_name=federation_raw.get("srv_name", ""),
+            timeout_seconds=float(federation_raw.get("timeout_seconds", 3.0)),
+            compression=_compression(federation_raw.get("compression", "gzip"), "federation.compression"),
+            target_compression={
+                str(k).rstrip("/"): _compression(v, f"federation.target_compression.{k}")
+                for k, v in federation_raw.get("target_compression", {}).items()
+            },
+            delta=bool(federation_raw.get("delta", False)),
+            full_sync_every=int(federation_raw.get("full_sync_every", 10)),
+        ),
+        history=HistoryConfig(
+            enabled=bool(history_raw.get("enabled", True)),
+            min_interval_seconds=int(history_raw.get("min_interval_seconds", 60)),
+            retention_days=int(history_raw.get("retention_days", 90)),
+        ),
+        digest=DigestConfig(
+            sink=digest_raw.get("sink", ""),
+            smtp_host=digest_raw.get("smtp_host", "localhost"),
+            smtp_port=int(digest_raw.get("smtp_port", 25)),
+            smtp_from=digest_raw.get("smtp_from", "usagedash@localhost"),
+            smtp_username=digest_raw.get("smtp_username", ""),
+            smtp_password_env=digest_raw.get("smtp_password_env", "USAGEDASH_SMTP_PASSWORD"),
+        ),
+        log=LogConfig(
+            sinks=_log_sinks(log_raw.get("sinks", [])),
+            ident=log_raw.get("ident", "usagedash"),
+            min_level=log_raw.get("min_level", "info"),
+        ),
+        flags=FlagsConfig(
+            enabled=bool(flags_raw.get("enabled", True)),
+            dir=str(flags_raw.get("dir", "")),
+            warn_pct=float(flags_raw.get("warn_pct", 80.0)),
+            exceeded_pct=float(flags_raw.get("exceeded_pct", 100.0)),
+        ),
+        mirror=MirrorConfig(
+            fields=_mirror_fields(mirror_raw.get("fields", [])),
+            compression=_compression(mirror_raw.get("compression", "none"), "mirror.compression"),
+        ),
+        bus=BusConfig(
+            queued=_bus_queued(bus_raw.get("queued", [])),
+            max_queue=max(1, int(bus_raw.get("max_queue", 16))),
+            policy=str(bus_raw.get("policy", "drop_oldest")),
+        ),
         providers={
-            "codex": _provider_from_dict(providers_raw.get("codex", {})),
-            "claude": _provider_from_dict(providers_raw.get("claude", {})),
-            "gemini": _provider_from_dict(providers_raw.get("gemini", {"enabled": False, "parser_mode": "manual"})),
+            name: provider_from_dict(providers_raw.get(name, defaults))
+            for name, defaults in PROVIDER_DEFAULTS.items()
         },
     )
     return cfg
 
 
+def _seconds_out(value: float) -> int | float:
+    return int(value) if float(value).is_integer() else value
+
+
 def save_config(cfg: Config, path: Path = CONFIG_PATH) -> None:
-    path.parent.mkdir(parents=True, exist_ok=True)
     payload = {
         "general": {
-            "refresh_seconds": cfg.general.refresh_seconds,
+            "refresh_seconds": _seconds_out(cfg.general.refresh_seconds),
+            "refresh_jitter_seconds": _seconds_out(cfg.general.refresh_jitter_seconds),
+            "reuse_snapshot_seconds": cfg.general.reuse_snapshot_seconds,
+            "read_only": cfg.general.read_only,
+            "pretty_state": cfg.general.pretty_state,
+            "state_format": cfg.general.state_format,
             "timezone": cfg.general.timezone,
+            "locale": cfg.general.locale,
+            "clock": cfg.general.clock,
+            "messages_file": cfg.general.messages_file,
             "state_file": cfg.general.state_file,
             "windows_state_path": cfg.general.windows_state_path,
+            "detect_processes": cfg.general.detect_processes,
+            "detect_windows_processes": cfg.general.detect_windows_processes,
         },
         "tray": {
             "enabled": cfg.tray.enabled,
-            "poll_seconds": cfg.tray.poll_seconds,
+            "poll_seconds": _seconds_out(cfg.tray.poll_seconds),
             "autostart": cfg.tray.autostart,
         },
+        "templates": dict(cfg.templates),
+        "rate_limits": dict(cfg.rate_limits),
+        "project_tags": {root: dict(tags) for root, tags in cfg.project_tags.items()},
+        "alerts": [_alert_to_dict(a) for a in cfg.alerts],
+        "maintenance": [
+            {"name": m.name, "cron": m.cron, "providers": list(m.providers)} for m in cfg.maintenance
+        ],
+        "heartbeat": {
+            "ping_url": cfg.heartbeat.ping_url,
+            "stale_minutes": cfg.heartbeat.stale_minutes,
+            "sink": cfg.heartbeat.sink,
+            "require_ok": cfg.heartbeat.require_ok,
+        },
+        "store": {
+            "backend": cfg.store.backend,
+            "path": cfg.store.path,
+            "url": cfg.store.url,
+            "fsync": cfg.store.fsync,
+        },
+        "federation": {
+            "targets": list(cfg.federation.targets),
+            "dns_name": cfg.federation.dns_name,
+            "srv_name": cfg.federation.srv_name,
+            "timeout_seconds": cfg.federation.timeout_seconds,
+            "compression": cfg.federation.compression,
+            "target_compression": dict(cfg.federation.target_compression),
+            "delta": cfg.federation.delta,
+            "full_sync_every": cfg.federation.full_sync_every,
+        },
+        "history": {
+            "enabled": cfg.history.enabled,
+            "min_interval_seconds": cfg.history.min_interval_seconds,
+            "retention_days": cfg.history.retention_days,
+        },
+        "digest": {
+            "sink": cfg.digest.sink,
+            "smtp_host": cfg.digest.smtp_host,
+            "smtp_port": cfg.digest.smtp_port,
+            "smtp_from": cfg.digest.smtp_from,
+            "smtp_username": cfg.digest.smtp_username,
+            "smtp_password_env": cfg.digest.smtp_password_env,
+        },
+        "log": {
+            "sinks": list(cfg.log.sinks),
+            "ident": cfg.log.ident,
+            "min_level": cfg.log.min_level,
+        },
+        "flags": {
+            "enabled": cfg.flags.enabled,
+            "dir": cfg.flags.dir,
+            "warn_pct": cfg.flags.warn_pct,
+            "exceeded_pct": cfg.flags.exceeded_pct,
+        },
+        "mirror": {"fields": list(cfg.mirror.fields), "compression": cfg.mirror.compression},
+        "bus": {"queued": list(cfg.bus.queued), "max_queue": cfg.bus.max_queue, "policy": cfg.bus.policy},
         "providers": {name: _provider_to_dict(pc) for name, pc in cfg.providers.items()},
     }
-    path.write_text(tomli_w.dumps(payload))
+    try:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(tomli_w.dumps(payload))
+    except OSError as exc:
+        raise ConfigError(
+            f"cannot write {path}: {exc.strerror or exc}; set USAGEDASH_CONFIG_DIR to a writable directory"
+        ) from exc
 
 
 def set_config_value(cfg: Config, dotted_key: str, value: str) -> None:
     if dotted_key == "general.refresh_seconds":
-        cfg.general.refresh_seconds = int(value)
+        cfg.general.refresh_seconds = seconds_value(value)
+        return
+    if dotted_key == "general.refresh_jitter_seconds":
+        cfg.general.refresh_jitter_seconds = seconds_value(value)
+        return
+    if dotted_key == "general.reuse_snapshot_seconds":
+        cfg.general.reuse_snapshot_seconds = int(value)
+        return
+    if dotted_key == "general.pretty_state":
+        cfg.general.pretty_state = value.strip().lower() in ("1", "true", "yes", "on")
+        return
+    if dotted_key == "general.state_format":
+        cfg.general.state_format = _state_format(value)
         return
     if dotted_key == "general.windows_state_path":
         cfg.general.windows_state_path = value
         return
+    if dotted_key == "general.locale":
+        cfg.general.locale = value
+        return
+    if dotted_key == "general.clock":
+        if value not in CLOCK_CHOICES:
+            raise ValueError(f"general.clock must be one of {', '.join(CLOCK_CHOICES)}")
+        cfg.general.clock = value
+        return
 
     keys = dotted_key.split(".")
     if len(keys) == 4 and keys[0] == "providers" and keys[2] == "manual":
@@ -162,3 +696,55 @@ def set_config_value(cfg: Config, dotted_key: str, value: str) -> None:
             setattr(manual, field_name, datetime.fromisoformat(value))
             return
     raise ValueError(f"unsupported key: {dotted_key}")
+
+
+ENV_PREFIX = "USAGEDASH_"
+
+
+def _coerce(annotation: str, raw: str) -> object:
+    if "bool" in annotation:
+        return raw.strip().lower() in {"1", "true", "yes", "on"}
+    if raw == "" and "None" in annotation:
+        return None
+    if "int" in annotation:
+        return int(raw)
+    if "float" in annotation:
+        return seconds_value(raw)
+    if "datetime" in annotation:
+        return datetime.fromisoformat(raw)
+    return raw
+
+
+def apply_env_overrides(cfg: Config, environ: dict[str, str] | None = None) -> list[str]:
+    """Override config fields from `USAGEDASH_<SECTION>__<FIELD>` variables.
+
+    Nested tables use further `__` separators, e.g.
+    `USAGEDASH_PROVIDERS__CLAUDE__ENABLED=false`. This lets containers run
+    with no config file at all. Returns the dotted keys that were applied.
+    """
+    env = os.environ if environ is None else environ
+    applied: list[str] = []
+    for key, raw in env.items():
+        if not key.startswith(ENV_PREFIX) or "__" not in key:
+            continue
+        parts = key[len(ENV_PREFIX):].lower().split("__")
+        target: object = cfg
+        for part in parts[:-1]:
+            if isinstance(target, dict):
+                target = target.get(part)
+            else:
+                target = getattr(target, part, None)
+            if target is None:
+                break
+        if target is None or not is_dataclass(target):
+            raise ValueError(f"{key}: unknown config section {'.'.join(parts[:-1])}")
+        by_name = {f.name: f for f in fields(target)}
+        leaf = parts[-1]
+        if leaf not in by_name or is_dataclass(getattr(target, leaf)):
+            raise ValueError(f"{key}: unknown config key {'.'.join(parts)}")
+        try:
+            setattr(target, leaf, _coerce(str(by_name[leaf].type), raw))
+        except ValueError as exc:
+            raise ValueError(f"{key}: {exc}") from exc
+        applied.append(".".join(parts))
+    return applied
@@ -0,0 +1,72 @@
+from __future__ import annotations
+
+from dataclasses import dataclass
+from datetime import datetime
+
+# (name, low, high) for minute, hour, day of month, month, day of week.
+_FIELDS = (("minute", 0, 59), ("hour", 0, 23), ("day", 1, 31), ("month", 1, 12), ("weekday", 0, 7))
+
+
+@dataclass(frozen=True)
+class CronExpr:
+    """A standard five-field cron expression, used as a predicate over minutes."""
+
+    text: str
+    minutes: frozenset[int]
+    hours: frozenset[int]
+    days: frozenset[int]
+    months: frozenset[int]
+    weekdays: frozenset[int]
+    any_day: bool
+    any_weekday: bool
+
+    def matches(self, dt: datetime) -> bool:
+        if dt.minute not in self.minutes or dt.hour not in self.hours or dt.month not in self.months:
+            return False
+        weekday = dt.isoweekday() % 7  # cron: 0 = Sunday
+        day_ok = dt.day in self.days
+        weekday_ok = weekday in self.weekdays
+        # Like cron, a restricted day-of-month and day-of-week are OR-ed.
+        if self.any_day or self.any_weekday:
+            return day_ok and weekday_ok
+        return day_ok or weekday_ok
+
+
+def _parse_field(text: str, name: str, low: int, high: int) -> frozenset[int]:
+    values: set[int] = set()
+    for part in text.split(","):
+        body, _, step_text = part.partition("/")
+        step = int(step_text) if step_text else 1
+        if body == "*":
+            start, end = low, high
+        elif "-" in body:
+            a, b = body.split("-", 1)
+            start, end = int(a), int(b)
+        else:
+            start = int(body)
+            end = high if step_text else start
+        if step < 1 or not (low <= start <= end <= high):
+            raise ValueError(f"cron {name} out of range: {part!r}")
+        values.update(range(start, end + 1, step))
+    return frozenset(values)
+
+
+def parse_cron(text: str) -> CronExpr:
+    parts = text.split()
+    if len(parts) != 5:
+        raise ValueError(f"cron expression needs 5 fields: {text!r}")
+    try:
+        parsed = [_parse_field(p, *spec) for p, spec in zip(parts, _FIELDS)]
+    except ValueError as exc:
+        raise ValueError(f"invalid cron expression {text!r}: {exc}") from exc
+    weekdays = frozenset(d % 7 for d in parsed[4])
+    return CronExpr(
+        text=text,
+        minutes=parsed[0],
+        hours=parsed[1],
+        days=parsed[2],
+        months=parsed[3],
+        weekdays=weekdays,
+        any_day=parts[2] == "*",
+        any_weekday=parts[4] == "*",
+    )
@@ -0,0 +1,157 @@
+from __future__ import annotations
+
+import json
+import secrets
+from collections import OrderedDict
+from dataclasses import dataclass, field
+from threading import Lock
+
+from usagedash.models import UsageSnapshot
+from usagedash.snapshot import snapshot_to_json
+
+# Sequences the server can still diff against; older bases get a full snapshot.
+DEFAULT_KEEP = 32
+
+# Stamped on every cycle whether or not anything changed; a row or snapshot
+# differing only in these has not changed.
+VOLATILE_ROW_KEYS = ("updated_at",)
+VOLATILE_DIAGNOSTIC_KEYS = ("last_seen", "count", "logged_at")
+
+
+def row_key(provider: dict) -> str:
+    account = provider.get("account") or ""
+    return f"{provider['provider']}:{account}" if account else str(provider["provider"])
+
+
+def _rows(data: dict) -> dict[str, dict]:
+    return {row_key(p): p for p in data["providers"]}
+
+
+def _content(row: dict) -> dict:
+    out = {k: v for k, v in row.items() if k not in VOLATILE_ROW_KEYS}
+    if isinstance(out.get("diagnostics"), list):
+        out["diagnostics"] = [
+            {k: v for k, v in d.items() if k not in VOLATILE_DIAGNOSTIC_KEYS} if isinstance(d, dict) else d
+            for d in out["diagnostics"]
+        ]
+    return out
+
+
+def _same_content(a: dict, b: dict) -> bool:
+    keys = ("schema_version", "paused", "paused_until")
+    return (
+        all(a.get(k) == b.get(k) for k in keys)
+        and list(_rows(a)) == list(_rows(b))
+        and all(_content(row) == _content(_rows(b)[key]) for key, row in _rows(a).items())
+    )
+
+
+def diff(base: dict, current: dict) -> dict:
+    """Per-provider changed fields between two serialized snapshots.
+
+    Rows whose only changes are per-cycle timestamps are left out.
+    """
+    old, new = _rows(base), _rows(current)
+    changed: dict[str, dict] = {}
+    for key, row in new.items():
+        prev = old.get(key, {})
+        if prev and _content(prev) == _content(row):
+            continue
+        fields = {k: v for k, v in row.items() if k not in prev or prev[k] != v}
+        if fields:
+            changed[key] = fields
+    return {
+        "schema_version": current.get("schema_version"),
+        "generated_at": current["generated_at"],
+        "paused": current.get("paused", False),
+        "paused_until": current.get("paused_until"),
+        "changed": changed,
+        "removed": [k for k in old if k not in new],
+        "order": list(new),
+    }
+
+
+def apply(base: dict, delta: dict) -> dict:
+    """Rebuild the full serialized snapshot from `base` plus a `diff` result."""
+    rows = _rows(base)
+    for key, fields in delta["changed"].items():
+        rows[key] = {**rows.get(key, {}), **fields}
+    return {
+        "schema_version": delta.get("schema_version", base.get("schema_version")),
+        "generated_at": delta["generated_at"],
+        "providers": [rows[k] for k in delta["order"]],
+        "paused": delta.get("paused", False),
+        "paused_until": delta.get("paused_until"),
+    }
+
+
+class DeltaLog:
+    """Sequence-numbered history of served snapshots for `/snapshot?since=N`.
+
+    The sequence only advances when the snapshot changes, not when only its
+    collection timestamps do, so an idle collector answers followers with
+    empty deltas. Sequences restart with the process; `instance` tells a
+    follower its base belongs to an earlier run. Followers pull: nothing is
+    pushed, a delta is only computed when one asks.
+    """
+
+    def __init__(self, keep: int = DEFAULT_KEEP) -> None:
+        self.keep = keep
+        self.seq = 0
+        self.instance = secrets.token_hex(8)
+        self._lock = Lock()
+        self._history: OrderedDict[int, dict] = OrderedDict()
+
+    def record(self, snapshot: UsageSnapshot) -> int:
+        data = json.loads(snapshot_to_json(snapshot, pretty=False))
+        with self._lock:
+            last = next(reversed(self._history.values()), None)
+            if last is not None and _same_content(last, data):
+                self._history[self.seq] = data  # fresh timestamps, same seq
+                return self.seq
+            self.seq += 1
+            self._history[self.seq] = data
+            while len(self._history) > self.keep:
+                self._history.popitem(last=False)
+            return self.seq
+
+    def full(self) -> dict | None:
+        with self._lock:
+            if not self._history:
+                return None
+            return {**self._history[self.seq], "seq": self.seq, "instance": self.instance}
+
+    def since(self, base: int) -> dict | None:
+        """Delta from `base` to the latest snapshot, or None when `base` is unknown."""
+        with self._lock:
+            old = self._history.get(base)
+            if old is None:
+                return None
+            return {"delta": True, "base": base, "seq": self.seq, "instance": self.instance, **diff(old, self._history[self.seq])}
+
+
+@dataclass
+class FollowState:
+    """What a follower last acknowledged from one target."""
+
+    seq: int = 0
+    data: dict = field(default_factory=dict)
+    deltas: int = 0
+    instance: str = ""
+
+    def want_delta(self, full_sync_every: int) -> bool:
+        return self.seq > 0 and bool(self.data) and (full_sync_every <= 0 or self.deltas < full_sync_every)
+
+    def accept(self, body: dict) -> dict | None:
+        """Fold a response in; None when a delta does not match our base or target run."""
+        if body.get("delta"):
+            if body.get("base") != self.seq or body.get("instance") != self.instance or not self.data:
+                return None
+            self.data = apply(self.data, body)
+            self.deltas += 1
+        else:
+            self.data = {k: v for k, v in body.items() if k not in ("seq", "instance")}
+            self.instance = str(body.get("instance") or "")
+            self.deltas = 0
+        self.seq = int(body.get("seq", 0))
+        return self.data
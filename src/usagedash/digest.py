@@ -0,0 +1,110 @@
+from __future__ import annotations
+
+import os
+import smtplib
+from datetime import datetime, timedelta
+from email.message import EmailMessage
+
+from usagedash.config import Config
+from usagedash.events import iter_claude_events
+from usagedash.providers.pricing import ModelPrice
+from usagedash.report import group_events
+from usagedash.sinks import SinkError, deliver
+from usagedash.store import Store
+
+# A weekly percentage falling by at least this much between two snapshots is
+# treated as a reset boundary when splitting the week into limit cycles.
+RESET_DROP_PCT = 20.0
+
+
+def _cycle_peaks(values: list[float]) -> list[float]:
+    peaks: list[float] = []
+    peak: float | None = None
+    prev: float | None = None
+    for v in values:
+        if prev is not None and peak is not None and v < prev - RESET_DROP_PCT:
+            peaks.append(peak)
+            peak = None
+        peak = v if peak is None else max(peak, v)
+        prev = v
+    if peak is not None:
+        peaks.append(peak)
+    return peaks
+
+
+def build_digest(
+    store: Store,
+    days: int = 7,
+    now: datetime | None = None,
+    prices: list[tuple[str, ModelPrice]] | None = None,
+) -> str:
+    """Plain-text summary of the last `days`; Claude cost is priced with `prices` (see `report.group_events`)."""
+    now = now or datetime.now()
+    since_local = now - timedelta(days=days)
+    snapshots = store.snapshots(since=datetime.utcnow() - timedelta(days=days))
+
+    lines = [f"UsageDash digest: {since_local:%b %d} - {now:%b %d}", ""]
+    if not snapshots:
+        lines.append("No history recorded in this period.")
+
+    per_provider: dict[str, dict[str, list]] = {}
+    for snap in snapshots:
+        for p in snap.providers:
+            bucket = per_provider.setdefault(p.provider.value, {"session": [], "weekly": []})
+            if p.session_used_pct is not None:
+                bucket["session"].append(p.session_used_pct)
+            if p.weekly_used_pct is not None:
+                bucket["weekly"].append(p.weekly_used_pct)
+
+    for name, bucket in per_provider.items():
+        lines.append(name.upper())
+        if bucket["session"]:
+            lines.append(f"  peak session usage: {max(bucket['session']):.1f}%")
+        if bucket["weekly"]:
+            peaks = ", ".join(f"{p:.1f}%" for p in _cycle_peaks(bucket["weekly"]))
+            lines.append(f"  closest to weekly limit before reset: {peaks}")
+        lines.append("")
+
+    fired = store.alerts(since=since_local)
+    if fired:
+        critical = sum(1 for r in fired if r.get("level") == "critical")
+        lines.append(f"Alerts fired: {len(fired)} ({critical} critical)")
+
+    events = list(iter_claude_events(since_local))
+    if events:
+        tokens = sum(e.tokens for e in events)
+        cost = sum(r.cost_usd for r in group_events(events, "model", prices=prices))
+        lines.append(f"Claude tokens in the last {days} days: {tokens:,.0f} (est. ${cost:,.2f})")
+
+    projects = group_events(events, "project", prices=prices)
+    if projects:
+        lines.append("Top Claude projects by tokens:")
+        for row in projects[:5]:
+            lines.append(f"  {row.key}: {row.tokens:,.0f} (est. ${row.cost_usd:,.2f})")
+
+    return "\n".join(lines).rstrip() + "\n"
+
+
+def send_digest(cfg: Config, body: str) -> None:
+    """Deliver through `digest.sink`; `email:<addr>` goes out via the [digest] SMTP settings."""
+    sink = cfg.digest.sink
+    if not sink:
+        raise SinkError("digest.sink is not configured")
+    kind, _, target = sink.partition(":")
+    if kind != "email":
+        deliver(sink, body, {"kind": "digest"})
+        return
+
+    msg = EmailMessage()
+    msg["Subject"] = "UsageDash weekly digest"
+    msg["From"] = cfg.digest.smtp_from
+    msg["To"] = target
+    msg.set_content(body)
+    try:
+        with smtplib.SMTP(cfg.digest.smtp_host, cfg.digest.smtp_port, timeout=30) as smtp:
+            if cfg.digest.smtp_username:
+                smtp.starttls()
+                smtp.login(cfg.digest.smtp_username, os.environ.get(cfg.digest.smtp_password_env, ""))
+            smtp.send_message(msg)
+    except (OSError, smtplib.SMTPException) as exc:
+        raise SinkError(f"email failed: {exc}") from exc
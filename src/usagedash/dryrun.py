@@ -0,0 +1,40 @@
+from __future__ import annotations
+
+from dataclasses import asdict, replace
+
+from usagedash.config import Config
+from usagedash.models import StatusKind, UsageSnapshot
+from usagedash.snapshot import build_snapshot
+
+
+def _flatten(value: object, prefix: str = "") -> dict[str, object]:
+    if isinstance(value, dict) and value:
+        out: dict[str, object] = {}
+        for key, item in value.items():
+            out.update(_flatten(item, f"{prefix}.{key}" if prefix else str(key)))
+        return out
+    return {prefix: value}
+
+
+def config_diff(old: Config, new: Config) -> list[str]:
+    """`key: old -> new` for every effective setting that differs, sorted by key."""
+    before, after = _flatten(asdict(old)), _flatten(asdict(new))
+    lines: list[str] = []
+    for key in sorted(before.keys() | after.keys()):
+        if key not in after:
+            lines.append(f"- {key}: {before[key]!r}")
+        elif key not in before:
+            lines.append(f"+ {key}: {after[key]!r}")
+        elif before[key] != after[key]:
+            lines.append(f"~ {key}: {before[key]!r} -> {after[key]!r}")
+    return lines
+
+
+def validation_pass(cfg: Config) -> UsageSnapshot:
+    """Collect once with `cfg` without writing state, history, flags, alerts or
+    rate-limit buckets, and without running custom commands or plugins."""
+    return build_snapshot(replace(cfg, general=replace(cfg.general, read_only=True)), run_commands=False)
+
+
+def failed_providers(snapshot: UsageSnapshot) -> list[str]:
+    return [p.label for p in snapshot.providers if p.status == StatusKind.ERROR]
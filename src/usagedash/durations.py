@@ -0,0 +1,55 @@
+from __future__ import annotations
+
+import re
+from datetime import timedelta
+
+_DURATION_RE = re.compile(r"([0-9]+(?:\.[0-9]+)?)\s*(ms|s|m|h|d|w)")
+_UNITS = {
+    "ms": timedelta(milliseconds=1),
+    "s": timedelta(seconds=1),
+    "m": timedelta(minutes=1),
+    "h": timedelta(hours=1),
+    "d": timedelta(days=1),
+    "w": timedelta(weeks=1),
+}
+
+
+def parse_duration(value: str) -> timedelta:
+    """Parse compact durations such as `90s`, `2h`, `7d`, or `1h30m`."""
+    text = value.strip().lower()
+    if not text:
+        raise ValueError("empty duration")
+    total = timedelta()
+    pos = 0
+    for m in _DURATION_RE.finditer(text):
+        if m.start() != pos:
+            break
+        total += float(m.group(1)) * _UNITS[m.group(2)]
+        pos = m.end()
+    if pos != len(text):
+        raise ValueError(f"invalid duration: {value!r} (expected e.g. 30m, 12h, 7d)")
+    return total
+
+
+def seconds_value(value: object) -> float:
+    """Config intervals: a plain number of seconds or a duration string like `500ms`."""
+    if isinstance(value, bool):
+        raise ValueError(f"invalid interval: {value!r}")
+    if isinstance(value, (int, float)):
+        return float(value)
+    text = str(value).strip()
+    try:
+        return float(text)
+    except ValueError:
+        return parse_duration(text).total_seconds()
+
+
+def format_seconds(seconds: float) -> str:
+    """Inverse of `seconds_value` for display: `500ms`, `2s`, `1m30s`."""
+    if seconds < 1:
+        return f"{round(seconds * 1000)}ms"
+    whole = int(seconds)
+    if whole != seconds or whole < 60:
+        return f"{seconds:g}s"
+    minutes, secs = divmod(whole, 60)
+    return f"{minutes}m{secs}s" if secs else f"{minutes}m"
@@ -0,0 +1,151 @@
+from __future__ import annotations
+
+import csv
+import json
+from collections.abc import Iterator
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import IO
+
+from usagedash.paths import home_dir
+from usagedash.providers.claude import (
+    _entry_identity,
+    _is_primary_assistant_usage_entry,
+    _parse_ts,
+    _usage_total_tokens,
+)
+
+
+@dataclass
+class UsageEvent:
+    """One billed model turn, as found in a provider's local transcripts."""
+
+    timestamp: datetime
+    provider: str
+    project: str
+    model: str
+    input_tokens: int = 0
+    output_tokens: int = 0
+    cache_read_tokens: int = 0
+    cache_creation_tokens: int = 0
+    tags: dict[str, str] = field(default_factory=dict)
+
+    @property
+    def tokens(self) -> int:
+        return self.input_tokens + self.output_tokens
+
+
+def iter_claude_events(since: datetime, projects_path: Path | None = None) -> Iterator[UsageEvent]:
+    """Yield deduplicated Claude turns newer than `since` from ~/.claude/projects.
+
+    `project` is the working directory recorded in each transcript entry,
+    falling back to Claude's escaped project folder name for older transcripts.
+    """
+    root = projects_path or home_dir() / ".claude/projects"
+    if not root.exists():
+        return
+
+    seen_ids: set[str] = set()
+    for jsonl_path in root.rglob("*.jsonl"):
+        try:
+            if datetime.fromtimestamp(jsonl_path.stat().st_mtime) < since:
+                continue
+            with jsonl_path.open("r", encoding="utf-8", errors="ignore") as fh:
+                for line in fh:
+                    if "\"usage\"" not in line:
+                        continue
+                    try:
+                        obj = json.loads(line)
+                    except json.JSONDecodeError:
+                        continue
+                    if not _is_primary_assistant_usage_entry(obj):
+                        continue
+                    ts = _parse_ts(obj.get("timestamp"))
+                    if ts is None or ts < since:
+                        continue
+                    entry_id = _entry_identity(obj)
+                    if not entry_id or entry_id in seen_ids:
+                        continue
+                    seen_ids.add(entry_id)
+                    msg = obj["message"]
+                    usage = msg["usage"]
+                    if _usage_total_tokens(usage) <= 0:
+                        continue
+                    yield UsageEvent(
+                        timestamp=ts,
+                        provider="claude",
+                        project=obj.get("cwd") or _project_dir(root, jsonl_path),
+                        model=msg.get("model", "unknown"),
+                        input_tokens=_int(usage.get("input_tokens")),
+                        output_tokens=_int(usage.get("output_tokens")),
+                        cache_read_tokens=_int(usage.get("cache_read_input_tokens")),
+                        cache_creation_tokens=_int(usage.get("cache_creation_input_tokens")),
+                    )
+        except OSError:
+            continue
+
+
+EVENT_COLUMNS = (
+    "timestamp",
+    "provider",
+    "project",
+    "model",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_tokens",
+    "cache_creation_tokens",
+    "tags",
+)
+
+
+def select_columns(spec: str | None) -> list[str]:
+    if not spec:
+        return list(EVENT_COLUMNS)
+    columns = [c.strip() for c in spec.split(",") if c.strip()]
+    unknown = [c for c in columns if c not in EVENT_COLUMNS]
+    if unknown:
+        raise ValueError(f"unknown column(s): {', '.join(unknown)} (available: {', '.join(EVENT_COLUMNS)})")
+    return columns
+
+
+def _row(event: UsageEvent, columns: list[str]) -> dict[str, object]:
+    row: dict[str, object] = {}
+    for col in columns:
+        if col == "timestamp":
+            row[col] = event.timestamp.isoformat()
+        elif col == "tags":
+            row[col] = json.dumps(event.tags, sort_keys=True)
+        else:
+            row[col] = getattr(event, col)
+    return row
+
+
+def write_csv(events: list[UsageEvent], columns: list[str], out: IO[str]) -> None:
+    writer = csv.DictWriter(out, fieldnames=columns)
+    writer.writeheader()
+    for event in events:
+        writer.writerow(_row(event, columns))
+
+
+def write_parquet(events: list[UsageEvent], columns: list[str], path: Path) -> None:
+    """Write events as Parquet; needs the optional `parquet` extra (pyarrow)."""
+    try:
+        import pyarrow as pa  # type: ignore[import-not-found]
+        import pyarrow.parquet as pq  # type: ignore[import-not-found]
+    except ImportError as exc:
+        raise RuntimeError("parquet export requires: pip install 'usagedash[parquet]'") from exc
+    data: dict[str, list[object]] = {col: [] for col in columns}
+    for event in events:
+        for col in columns:
+            data[col].append(event.timestamp if col == "timestamp" else _row(event, [col])[col])
+    pq.write_table(pa.table(data), path)
+
+
+def _int(value: object) -> int:
+    return int(value) if isinstance(value, (int, float)) else 0
+
+
+def _project_dir(root: Path, jsonl_path: Path) -> str:
+    rel = jsonl_path.relative_to(root)
+    return rel.parts[0] if len(rel.parts) > 1 else jsonl_path.stem
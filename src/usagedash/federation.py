@@ -0,0 +1,128 @@
+from __future__ import annotations
+
+import json
+import socket
+import urllib.request
+from concurrent.futures import ThreadPoolExecutor
+
+from usagedash.compression import accept_encoding, decompress
+from usagedash.config import Config
+from usagedash.delta import FollowState
+from usagedash.formats import render_prometheus, render_prometheus_many
+from usagedash.models import UsageSnapshot
+from usagedash.snapshot import snapshot_from_dict
+
+
+def discover_targets(cfg: Config) -> list[str]:
+    """Static targets plus every address behind `dns_name` and SRV `srv_name`.
+
+    `dns_name` ("host:port") suits a Kubernetes headless Service, whose A
+    records are the pod IPs. SRV lookups need the optional dnspython package.
+    """
+    fed = cfg.federation
+    targets = list(fed.targets)
+
+    if fed.dns_name:
+        host, _, port = fed.dns_name.rpartition(":")
+        try:
+            infos = socket.getaddrinfo(host, int(port), proto=socket.IPPROTO_TCP)
+        except (OSError, ValueError):
+            infos = []
+        for info in infos:
+            addr = info[4][0]
+            addr = f"[{addr}]" if ":" in addr else addr
+            targets.append(f"http://{addr}:{port}")
+
+    if fed.srv_name:
+        try:
+            import dns.resolver  # type: ignore[import-not-found]
+
+            for record in dns.resolver.resolve(fed.srv_name, "SRV"):
+                targets.append(f"http://{str(record.target).rstrip('.')}:{record.port}")
+        except ImportError:
+            pass
+        except Exception:
+            # NXDOMAIN / timeouts: keep serving the targets we could resolve.
+            pass
+
+    seen: set[str] = set()
+    unique: list[str] = []
+    for t in targets:
+        t = t.rstrip("/")
+        if t not in seen:
+            seen.add(t)
+            unique.append(t)
+    return unique
+
+
+# Per-target sequence and last snapshot when `federation.delta` is on.
+_FOLLOW: dict[str, FollowState] = {}
+
+
+def _get_json(url: str, timeout: float, compression: str) -> dict:
+    req = urllib.request.Request(url, headers={"Accept-Encoding": accept_encoding(compression)})
+    with urllib.request.urlopen(req, timeout=timeout) as resp:
+        return json.loads(decompress(resp.read()))
+
+
+def fetch_remote(
+    target: str, timeout: float, compression: str = "gzip", follow: FollowState | None = None, full_sync_every: int = 10
+) -> UsageSnapshot | None:
+    """The target's snapshot; with `follow`, fetched as a delta against the last one seen.
+
+    This is pull-only: one request per scrape, with no push or long-poll
+    channel from the target. A delta whose base is not ours (target
+    restarted, so its `instance` changed, or we missed a response) is
+    dropped and the full snapshot fetched instead.
+    """
+    try:
+        if follow is None:
+            return snapshot_from_dict(_get_json(f"{target}/snapshot", timeout, compression))
+        data = None
+        if follow.want_delta(full_sync_every):
+            data = follow.accept(_get_json(f"{target}/snapshot?since={follow.seq}", timeout, compression))
+        if data is None:
+            follow.seq, follow.data, follow.instance = 0, {}, ""
+            # since=0 is never a known base, so this is the full snapshot plus its seq.
+            data = follow.accept(_get_json(f"{target}/snapshot?since=0", timeout, compression))
+        return snapshot_from_dict(data) if data is not None else None
+    except (OSError, ValueError, KeyError):
+        if follow is not None:
+            follow.seq, follow.data, follow.instance = 0, {}, ""
+        return None
+
+
+def federated_metrics(cfg: Config, local: UsageSnapshot) -> str:
+    """Local metrics plus every reachable target, each labeled with `instance`.
+
+    Without any federation targets configured this is plain `render_prometheus`.
+    """
+    targets = discover_targets(cfg)
+    if not targets:
+        return render_prometheus(local)
+    items: list[tuple[UsageSnapshot, dict[str, str]]] = [
+        (local, {"instance": socket.gethostname()})
+    ]
+    up: list[str] = []
+    timeout = cfg.federation.timeout_seconds
+    per_target = cfg.federation.target_compression
+
+    def fetch(target: str) -> UsageSnapshot | None:
+        follow = _FOLLOW.setdefault(target, FollowState()) if cfg.federation.delta else None
+        compression = per_target.get(target, cfg.federation.compression)
+        return fetch_remote(target, timeout, compression, follow, cfg.federation.full_sync_every)
+
+    with ThreadPoolExecutor(max_workers=min(16, len(targets))) as pool:
+        results = list(pool.map(fetch, targets))
+    for target, snap in zip(targets, results):
+        instance = target.split("://", 1)[-1]
+        up.append(f'usagedash_federation_target_up{{instance="{instance}"}} {int(snap is not None)}')
+        if snap is not None:
+            items.append((snap, {"instance": instance}))
+
+    body = render_prometheus_many(items)
+    if up:
+        body += "# HELP usagedash_federation_target_up Whether the last scrape of a federated target succeeded.\n"
+        body += "# TYPE usagedash_federation_target_up gauge\n"
+        body += "\n".join(up) + "\n"
+    return body
@@ -0,0 +1,57 @@
+from __future__ import annotations
+
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.models import UsageSnapshot
+
+LEVELS = ("warn", "exceeded")
+
+
+def flags_dir(cfg: Config) -> Path:
+    return Path(cfg.flags.dir).expanduser() if cfg.flags.dir else Path(cfg.general.state_file).parent / "flags"
+
+
+def desired_flags(cfg: Config, snapshot: UsageSnapshot) -> dict[str, float]:
+    """Flag file name -> the usage that raised it, e.g. `claude.weekly.exceeded`."""
+    thresholds = {"warn": cfg.flags.warn_pct, "exceeded": cfg.flags.exceeded_pct}
+    out: dict[str, float] = {}
+    for p in snapshot.providers:
+        prefix = p.key.replace(":", ".")
+        # Named after the window the provider measures, e.g. `copilot.monthly.warn`.
+        details = p.details or {}
+        for window, value in (
+            (str(details.get("session_window", "session")), p.session_used_pct),
+            (str(details.get("long_window", "weekly")), p.weekly_used_pct),
+        ):
+            if value is None:
+                continue
+            for level in LEVELS:
+                if value >= thresholds[level]:
+                    out[f"{prefix}.{window}.{level}"] = value
+    return out
+
+
+def write_flags(cfg: Config, snapshot: UsageSnapshot) -> None:
+    """Create and remove flag files so each exists exactly while its threshold is crossed.
+
+    Files are only touched when a flag changes, so `[ -f ... ]` tests and
+    inotify watchers see one event per crossing. A file holds the usage
+    percentage at the moment it was raised.
+    """
+    if not cfg.flags.enabled:
+        return
+    root = flags_dir(cfg)
+    wanted = desired_flags(cfg, snapshot)
+    try:
+        root.mkdir(parents=True, exist_ok=True)
+        for path in root.iterdir():
+            if path.suffix.lstrip(".") in LEVELS and path.name not in wanted:
+                path.unlink()
+        for name, value in wanted.items():
+            path = root / name
+            if not path.exists():
+                path.write_text(f"{value:.1f}\n")
+    except OSError:
+        # Flags are a convenience for scripts; never fail a collection over them.
+        return
@@ -0,0 +1,16 @@
+from usagedash.formats.html import render_html
+from usagedash.formats.prometheus import render_prometheus, render_prometheus_many
+from usagedash.formats.plain import paused_line, render_plain, render_project_plain, render_summary
+from usagedash.formats.template import render_template, resolve_template
+
+__all__ = [
+    "paused_line",
+    "render_html",
+    "render_plain",
+    "render_project_plain",
+    "render_prometheus",
+    "render_prometheus_many",
+    "render_summary",
+    "render_template",
+    "resolve_template",
+]
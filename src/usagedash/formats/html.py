@@ -0,0 +1,53 @@
+from __future__ import annotations
+
+from datetime import datetime
+from html import escape
+
+from usagedash.models import Percent, UsageSnapshot
+from usagedash.timefmt import format_clock, format_reset
+
+_STATUS_COLORS = {"ok": "#2be38f", "partial": "#f2c94c", "error": "#ff5e6c"}
+
+
+def render_html(snapshot: UsageSnapshot, refresh_seconds: int = 0) -> str:
+    """Render a self-contained page suitable for nginx or a digital-signage browser."""
+    rows: list[str] = []
+    for p in snapshot.providers:
+        color = _STATUS_COLORS.get(p.status.value, "#7184d6")
+        rows.append(
+            "<tr>"
+            f"<td>{escape(p.label if p.display_name or p.account else p.provider.value.upper())}</td>"
+            f'<td style="color:{color}">{escape(p.status.value.upper())}</td>'
+            f"<td>{_pct_cell(p.session_used_pct)}</td>"
+            f"<td>{escape(format_reset(p.session_reset_at))}</td>"
+            f"<td>{_pct_cell(p.weekly_used_pct)}</td>"
+            f"<td>{escape(format_reset(p.weekly_reset_at))}</td>"
+            f"<td class=\"notes\">{escape(' | '.join(p.rendered_messages(detail=False)))}</td>"
+            "</tr>"
+        )
+
+    refresh = f'<meta http-equiv="refresh" content="{refresh_seconds}">' if refresh_seconds > 0 else ""
+    return (
+        "<!doctype html>\n"
+        '<html><head><meta charset="utf-8">'
+        f"{refresh}<title>UsageDash</title>"
+        "<style>"
+        "body{background:#0c0f1a;color:#e8ecff;font-family:monospace;margin:2em}"
+        "table{border-collapse:collapse;width:100%}"
+        "th,td{padding:.4em .8em;text-align:left;border-bottom:1px solid #141830}"
+        "meter{width:10em}.notes{color:#7184d6;font-style:italic}"
+        "</style></head><body>\n"
+        "<table><thead><tr><th>Provider</th><th>Status</th><th>Session</th><th>Resets</th>"
+        "<th>Weekly</th><th>Resets</th><th>Notes</th></tr></thead><tbody>\n"
+        + "\n".join(rows)
+        + "\n</tbody></table>\n"
+        f"<p>updated {escape(format_clock(datetime.now()))}</p>\n"
+        "</body></html>\n"
+    )
+
+
+def _pct_cell(value: float | None) -> str:
+    if value is None:
+        return "-"
+    shown = max(0.0, value)
+    return f'<meter min="0" max="100" low="50" high="80" value="{Percent(shown).bar:.1f}"></meter> {shown:.1f}%'
@@ -0,0 +1,126 @@
+from __future__ import annotations
+
+from datetime import datetime
+
+from usagedash.models import ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.projects import ProjectUsage
+from usagedash.timefmt import format_reset
+
+
+def render_plain(providers: list[ProviderSnapshot], now: datetime | None = None, detail: bool = True) -> str:
+    """Render one labeled sentence per field, for screen readers and braille displays.
+
+    No box drawing, color, or column alignment: every line stands on its own.
+    """
+    now = now or datetime.now()
+    lines: list[str] = []
+    for p in providers:
+        name = p.label
+        lines.append(f"{name} status {p.status.value}, source {p.source.value}")
+        session_window = str((p.details or {}).get("session_window", "session"))
+        lines.append(_window_line(name, session_window, p.session_used_pct, p.session_reset_at, now))
+        long_window = str((p.details or {}).get("long_window", "weekly"))
+        lines.append(_window_line(name, long_window, p.weekly_used_pct, p.weekly_reset_at, now))
+        context = (p.details or {}).get("context")
+        if isinstance(context, dict) and context.get("window_tokens"):
+            lines.append(
+                f"{name} context used {context.get('used_pct', 0):.0f} percent, "
+                f"{context.get('used_tokens', 0):,} of {context['window_tokens']:,} tokens"
+            )
+        split = ((p.details or {}).get("dynamic_limits") or {}).get("model_split") or {}
+        for family, bucket in split.items() if detail else ():
+            line = f"{name} model {family} {bucket.get('pct', 0):.0f} percent of session tokens"
+            if bucket.get("subagent_tokens"):
+                line += f", {bucket['subagent_tokens']:,.0f} tokens via subagents"
+            lines.append(line)
+        costs = (p.details or {}).get("costs")
+        if isinstance(costs, dict):
+            lines.append(
+                f"{name} estimated cost {costs.get('today_usd', 0):.2f} dollars today, "
+                f"{costs.get('week_usd', 0):.2f} dollars over 7 days"
+            )
+        for msg in p.rendered_messages(detail):
+            lines.append(f"{name} note: {msg}")
+    return "\n".join(lines)
+
+
+def render_summary(providers: list[ProviderSnapshot]) -> str:
+    """One compact line for logs, then a `key=value` trailer counting statuses.
+
+    Used when stdout is not a terminal, so cron mail and CI logs get
+    something grep-able instead of box drawing and ANSI escapes.
+    """
+    parts: list[str] = []
+    for p in providers:
+        fields = [p.label, p.status.value]
+        for window, used in (
+            (str((p.details or {}).get("session_window", "session")), p.session_used_pct),
+            (str((p.details or {}).get("long_window", "weekly")), p.weekly_used_pct),
+        ):
+            if used is not None:
+                fields.append(f"{window}={used:.0f}%")
+        context = (p.details or {}).get("context")
+        if isinstance(context, dict) and context.get("window_tokens"):
+            fields.append(f"context={context.get('used_pct', 0):.0f}%")
+        parts.append(" ".join(fields))
+    counts = {kind.value: 0 for kind in StatusKind}
+    for p in providers:
+        counts[p.status.value] += 1
+    trailer = " ".join(f"{k}={v}" for k, v in counts.items())
+    return "\n".join(["; ".join(parts) or "no providers enabled", trailer])
+
+
+def paused_line(snapshot: UsageSnapshot) -> str | None:
+    """`collection paused [until ...]`, or None while collecting normally."""
+    if not snapshot.paused:
+        return None
+    if snapshot.paused_until is None:
+        return "collection paused"
+    return f"collection paused until {format_reset(snapshot.paused_until)}"
+
+
+def render_project_plain(usage: ProjectUsage) -> str:
+    name = usage.budget.name
+    lines: list[str] = []
+    for window, used, limit, pct in (
+        ("daily", usage.daily_tokens, usage.budget.daily_tokens, usage.daily_pct),
+        ("weekly", usage.weekly_tokens, usage.budget.weekly_tokens, usage.weekly_pct),
+    ):
+        if limit is None or pct is None:
+            continue
+        lines.append(f"project {name} {window} used {pct:.0f} percent, {used:,.0f} of {limit:,.0f} tokens")
+    return "\n".join(lines)
+
+
+def _window_line(
+    name: str,
+    window: str,
+    used: float | None,
+    reset_at: datetime | None,
+    now: datetime,
+) -> str:
+    if used is None:
+        line = f"{name} {window} usage unknown"
+    else:
+        line = f"{name} {window} used {used:.0f} percent"
+    if reset_at is not None:
+        seconds = (reset_at - now).total_seconds()
+        if seconds > 0:
+            line += f", resets in {spoken_duration(seconds)}"
+        else:
+            line += ", reset time has passed"
+    return line
+
+
+def spoken_duration(total_seconds: float) -> str:
+    total = max(0, int(total_seconds))
+    days, rem = divmod(total, 86400)
+    hours, rem = divmod(rem, 3600)
+    minutes = rem // 60
+    parts: list[str] = []
+    for value, unit in ((days, "day"), (hours, "hour"), (minutes, "minute")):
+        if value:
+            parts.append(f"{value} {unit}" + ("" if value == 1 else "s"))
+    if not parts:
+        return "less than a minute"
+    return " ".join(parts[:2])
@@ -0,0 +1,68 @@
+from __future__ import annotations
+
+from datetime import timezone
+
+from usagedash.models import UsageSnapshot
+
+_STATUS_VALUES = {"ok": 0, "partial": 1, "error": 2}
+
+_FAMILIES = (
+    ("usagedash_session_percent_used", "Percent of the session (5h) limit used."),
+    ("usagedash_weekly_percent_used", "Percent of the weekly limit used."),
+    ("usagedash_session_reset_timestamp_seconds", "Unix time the session limit resets."),
+    ("usagedash_weekly_reset_timestamp_seconds", "Unix time the weekly limit resets."),
+    ("usagedash_provider_status", "Collection status: 0 ok, 1 partial, 2 error."),
+    ("usagedash_snapshot_timestamp_seconds", "Unix time the snapshot was generated."),
+)
+
+
+def _escape(value: str) -> str:
+    return value.replace("\\", "\\\\").replace("\n", "\\n").replace('"', '\\"')
+
+
+def _labels(pairs: dict[str, str]) -> str:
+    if not pairs:
+        return ""
+    return "{" + ",".join(f'{k}="{_escape(v)}"' for k, v in pairs.items()) + "}"
+
+
+def _num(value: float) -> str:
+    """Full precision: `:g` would print a reset timestamp as 1.79349e+09."""
+    value = float(value)
+    return str(int(value)) if value.is_integer() and abs(value) < 2**53 else repr(value)
+
+
+def render_prometheus(snapshot: UsageSnapshot, labels: dict[str, str] | None = None) -> str:
+    """Prometheus text exposition format, also valid for node_exporter's textfile collector."""
+    return render_prometheus_many([(snapshot, labels or {})])
+
+
+def render_prometheus_many(items: list[tuple[UsageSnapshot, dict[str, str]]]) -> str:
+    """Render several snapshots (e.g. one per federated host) under shared metric families."""
+    samples: dict[str, list[str]] = {name: [] for name, _ in _FAMILIES}
+    for snapshot, extra in items:
+        extra = dict(sorted(extra.items()))
+        for p in snapshot.providers:
+            label = _labels({"provider": p.provider.value, **({"account": p.account} if p.account else {}), **extra})
+            session_reset = p.session_reset_at.timestamp() if p.session_reset_at else None
+            weekly_reset = p.weekly_reset_at.timestamp() if p.weekly_reset_at else None
+            for name, value in (
+                ("usagedash_session_percent_used", p.session_used_pct),
+                ("usagedash_weekly_percent_used", p.weekly_used_pct),
+                ("usagedash_session_reset_timestamp_seconds", session_reset),
+                ("usagedash_weekly_reset_timestamp_seconds", weekly_reset),
+                ("usagedash_provider_status", _STATUS_VALUES.get(p.status.value, 2)),
+            ):
+                if value is not None:
+                    samples[name].append(f"{name}{label} {_num(value)}")
+        generated = snapshot.generated_at.replace(tzinfo=timezone.utc).timestamp()
+        samples["usagedash_snapshot_timestamp_seconds"].append(
+            f"usagedash_snapshot_timestamp_seconds{_labels(extra)} {_num(generated)}"
+        )
+
+    lines: list[str] = []
+    for name, help_text in _FAMILIES:
+        lines.append(f"# HELP {name} {help_text}")
+        lines.append(f"# TYPE {name} gauge")
+        lines.extend(samples[name])
+    return "\n".join(lines) + "\n"
@@ -0,0 +1,68 @@
+from __future__ import annotations
+
+import re
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.models import Percent, ProviderName, UsageSnapshot
+from usagedash.timefmt import format_duration, format_reset
+
+PLACEHOLDER_RE = re.compile(r"\{\{\s*([A-Za-z0-9_.]+)\s*\}\}")
+
+
+def build_context(snapshot: UsageSnapshot, now: datetime | None = None) -> dict[str, str]:
+    """Flatten the snapshot into the dotted names templates can reference.
+
+    Every known provider gets entries even when disabled, rendered as "-",
+    so a template keeps working when a provider is switched off.
+    """
+    now = now or datetime.now()
+    ctx: dict[str, str] = {"generated_at": snapshot.generated_at.isoformat(timespec="seconds")}
+    for name in ProviderName:
+        ctx[f"{name.value}.name"] = name.value
+        for key in ("status", "source", "messages"):
+            ctx[f"{name.value}.{key}"] = "-"
+        for window in ("session", "weekly"):
+            for key in ("percent", "percent_exact", "percent_left", "reset", "resets_in"):
+                ctx[f"{name.value}.{window}.{key}"] = "-"
+
+    for p in snapshot.providers:
+        prefix = p.key.replace(":", ".")
+        ctx[f"{prefix}.name"] = p.label
+        ctx[f"{prefix}.status"] = p.status.value
+        ctx[f"{prefix}.source"] = p.source.value
+        ctx[f"{prefix}.messages"] = " | ".join(p.rendered_messages())
+        for window, used, reset_at in (
+            ("session", p.session_used_pct, p.session_reset_at),
+            ("weekly", p.weekly_used_pct, p.weekly_reset_at),
+        ):
+            if used is not None:
+                ctx[f"{prefix}.{window}.percent"] = f"{used:.0f}"
+                ctx[f"{prefix}.{window}.percent_exact"] = f"{used:.1f}"
+                ctx[f"{prefix}.{window}.percent_left"] = f"{Percent(used).left:.0f}"
+            if reset_at is not None:
+                ctx[f"{prefix}.{window}.reset"] = format_reset(reset_at)
+                ctx[f"{prefix}.{window}.resets_in"] = format_duration((reset_at - now).total_seconds())
+    return ctx
+
+
+def render_template(template: str, snapshot: UsageSnapshot, now: datetime | None = None) -> str:
+    ctx = build_context(snapshot, now)
+
+    def _sub(match: re.Match[str]) -> str:
+        key = match.group(1)
+        if key not in ctx:
+            raise ValueError(f"unknown template field: {key}")
+        return ctx[key]
+
+    return PLACEHOLDER_RE.sub(_sub, template)
+
+
+def resolve_template(cfg: Config, value: str) -> str:
+    """`--template` accepts a name from `[templates]`, `@path/to/file`, or inline text."""
+    if value in cfg.templates:
+        return Path(cfg.templates[value]).expanduser().read_text(encoding="utf-8")
+    if value.startswith("@"):
+        return Path(value[1:]).expanduser().read_text(encoding="utf-8")
+    return value
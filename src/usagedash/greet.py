@@ -0,0 +1,77 @@
+from __future__ import annotations
+
+import os
+import subprocess
+import sys
+import time
+from pathlib import Path
+
+from rich.text import Text
+
+from usagedash.config import Config
+from usagedash.models import UsageSnapshot
+from usagedash.pipeline import fresh_snapshot
+from usagedash.snapshot import read_snapshot
+
+
+def _pct_color(pct: float) -> str:
+    if pct >= 80.0:
+        return "red"
+    if pct >= 50.0:
+        return "yellow"
+    return "green"
+
+
+def greeting_line(snapshot: UsageSnapshot, stale_seconds: float | None = None) -> Text:
+    line = Text()
+    for i, p in enumerate(snapshot.providers):
+        if i > 0:
+            line.append("  │  ", style="bright_black")
+        line.append(p.label, style="bold bright_white")
+        for label, value in (("S", p.session_used_pct), ("W", p.weekly_used_pct)):
+            line.append(f" {label} ", style="dim")
+            if value is None:
+                line.append("-", style="dim")
+            else:
+                line.append(f"{value:.0f}%", style=f"bold {_pct_color(value)}")
+    if stale_seconds is not None:
+        line.append(f"  (as of {int(stale_seconds // 60)}m ago)", style="dim italic")
+    return line
+
+
+def refresh_command(config_path: Path) -> list[str]:
+    """The detached `usagedash snapshot` run, pinned to the config the greeting was read with."""
+    return [sys.executable, "-m", "usagedash", "--config", str(config_path), "snapshot"]
+
+
+def greet(cfg: Config, config_path: Path, max_age_seconds: float, budget_seconds: float) -> Text | None:
+    """Summarize quota for a shell rc file or session-start hook without slowing it down.
+
+    A fresh snapshot is printed straight away. Otherwise a background refresh
+    is started and given `budget_seconds`; if it has not finished by then the
+    older snapshot is shown, marked with its age, and the refresh carries on
+    detached so the next shell gets fresh numbers.
+    """
+    snap = fresh_snapshot(cfg, max_age_seconds)
+    if snap is not None:
+        return greeting_line(snap)
+
+    if not cfg.general.read_only:
+        proc = subprocess.Popen(
+            refresh_command(config_path),
+            stdin=subprocess.DEVNULL,
+            stdout=subprocess.DEVNULL,
+            stderr=subprocess.DEVNULL,
+            start_new_session=os.name != "nt",
+        )
+        deadline = time.monotonic() + budget_seconds
+        while time.monotonic() < deadline and proc.poll() is None:
+            time.sleep(0.02)
+
+    path = Path(cfg.general.state_file)
+    try:
+        age = time.time() - path.stat().st_mtime
+        snap = read_snapshot(path)
+    except (OSError, ValueError, KeyError):
+        return None
+    return greeting_line(snap, stale_seconds=age if age > max_age_seconds else None)
@@ -0,0 +1,89 @@
+from __future__ import annotations
+
+import json
+import urllib.request
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.alerts import AlertEvent, dispatch, load_alert_state, record_history, save_alert_state
+from usagedash.config import Config
+from usagedash.models import StatusKind, UsageSnapshot
+from usagedash.pause import pause_state
+from usagedash.store import Store
+
+HEARTBEAT_RULE = "heartbeat"
+
+
+def heartbeat_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).parent / "heartbeat.json"
+
+
+def beat(cfg: Config, now: datetime | None = None) -> None:
+    """Mark a successful collection and ping the external dead-man's-switch URL.
+
+    The ping is what catches a dead machine: services like healthchecks.io or
+    a cron-monitor alert on their own when the pings stop arriving.
+    """
+    now = now or datetime.now()
+    path = heartbeat_path(cfg)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps({"last_success": now.isoformat()}))
+
+    if cfg.heartbeat.ping_url:
+        try:
+            urllib.request.urlopen(cfg.heartbeat.ping_url, timeout=5).close()
+        except OSError:
+            # Missing a ping is exactly what the remote side watches for.
+            pass
+
+
+def counts_as_success(cfg: Config, snapshot: UsageSnapshot) -> bool:
+    """Whether `snapshot` should beat; see `heartbeat.require_ok`."""
+    if not cfg.heartbeat.require_ok:
+        return True
+    return any(p.status == StatusKind.OK for p in snapshot.providers)
+
+
+def last_success(cfg: Config) -> datetime | None:
+    try:
+        raw = json.loads(heartbeat_path(cfg).read_text())
+        return datetime.fromisoformat(raw["last_success"])
+    except (OSError, json.JSONDecodeError, KeyError, TypeError, ValueError):
+        return None
+
+
+def check_heartbeat(cfg: Config, store: Store, now: datetime | None = None) -> AlertEvent | None:
+    """Fire once per outage when no collection has succeeded for `stale_minutes`.
+
+    Meant to run from cron or another host reading the mirrored state dir,
+    so it never depends on the collector that may have died.
+    """
+    now = now or datetime.now()
+    if pause_state(cfg, now) is not None:
+        # A deliberate pause is not an outage.
+        return None
+    last = last_success(cfg)
+    stale = last is None or now - last > timedelta(minutes=cfg.heartbeat.stale_minutes)
+
+    state = load_alert_state(cfg)
+    if not stale:
+        if state.pop(HEARTBEAT_RULE, None) is not None:
+            save_alert_state(cfg, state)
+        return None
+    if HEARTBEAT_RULE in state:
+        return None
+
+    age = "never" if last is None else f"{int((now - last).total_seconds() // 60)}m ago"
+    event = AlertEvent(
+        rule=HEARTBEAT_RULE,
+        level="critical",
+        provider="usagedash",
+        window="heartbeat",
+        value=0.0,
+        sink=cfg.heartbeat.sink or "stderr",
+        message=f"[CRITICAL] usagedash has not collected successfully (last success: {age})",
+    )
+    record_history(store, dispatch([event]), now)
+    state[HEARTBEAT_RULE] = {"level": "critical", "acked": False, "last_fired": now.isoformat()}
+    save_alert_state(cfg, state)
+    return event
@@ -0,0 +1,42 @@
+from __future__ import annotations
+
+from datetime import datetime, timedelta, timezone
+
+from usagedash.config import Config
+from usagedash.models import UsageSnapshot
+from usagedash.store import Store
+
+
+def append_history(cfg: Config, store: Store, snapshot: UsageSnapshot) -> bool:
+    """Append `snapshot` unless the previous entry is younger than `min_interval_seconds`.
+
+    Returns whether a record was written. The interval keeps a 2s dashboard
+    refresh from growing the store by tens of megabytes a day.
+    """
+    if not cfg.history.enabled:
+        return False
+    last = store.last_snapshot_at()
+    if last is not None and (snapshot.generated_at - last).total_seconds() < cfg.history.min_interval_seconds:
+        return False
+    store.append_snapshot(snapshot)
+    return True
+
+
+def prune_history(cfg: Config, store: Store, now: datetime | None = None) -> int:
+    """Drop snapshots older than `retention_days`; returns how many were removed."""
+    if cfg.history.retention_days <= 0:
+        return 0
+    cutoff = (now or datetime.utcnow()) - timedelta(days=cfg.history.retention_days)
+    return store.prune(cutoff)
+
+
+def snapshot_at(store: Store, at: datetime, lookback: timedelta = timedelta(days=1)) -> UsageSnapshot | None:
+    """The last stored snapshot at or before local time `at`, within `lookback`.
+
+    History is keyed by UTC, so `at` is converted first. Returns None when
+    nothing was recorded in the lookback window, rather than reporting a
+    much older state as if it were current at `at`.
+    """
+    at_utc = at.astimezone(timezone.utc).replace(tzinfo=None)
+    found = store.snapshots(since=at_utc - lookback, until=at_utc)
+    return found[-1] if found else None
@@ -0,0 +1,96 @@
+from __future__ import annotations
+
+import json
+import os
+import shlex
+import signal
+import subprocess
+import sys
+from pathlib import Path
+
+from usagedash.atomic import write_atomic
+from usagedash.config import AlertRule, Config
+from usagedash.processes import agent_pids
+
+COMMAND_TIMEOUT_SECONDS = 30.0
+
+
+def stopped_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).parent / "stopped-agents.json"
+
+
+def load_stopped(cfg: Config) -> dict[str, list[int]]:
+    """Provider -> pids the `stop` action suspended and nothing has resumed yet."""
+    try:
+        raw = json.loads(stopped_path(cfg).read_text())
+    except (OSError, json.JSONDecodeError):
+        return {}
+    if not isinstance(raw, dict):
+        return {}
+    return {str(k): [int(p) for p in v if isinstance(p, int)] for k, v in raw.items() if isinstance(v, list)}
+
+
+def _save_stopped(cfg: Config, stopped: dict[str, list[int]]) -> None:
+    write_atomic(stopped_path(cfg), json.dumps({k: v for k, v in stopped.items() if v}, indent=2), cfg.store.fsync)
+
+
+def resume_agents(cfg: Config, provider: str | None = None) -> list[int]:
+    """Send SIGCONT to the agents `stop` suspended (all providers, or one); returns the pids resumed."""
+    stopped = load_stopped(cfg)
+    resumed: list[int] = []
+    for name in list(stopped):
+        if provider is not None and name != provider:
+            continue
+        for pid in stopped.pop(name):
+            try:
+                os.kill(pid, signal.SIGCONT)
+            except OSError:
+                continue  # exited or killed meanwhile: nothing left to resume
+            resumed.append(pid)
+    _save_stopped(cfg, stopped)
+    return resumed
+
+
+def take_action(cfg: Config, rule: AlertRule) -> tuple[str, str | None]:
+    """Run `rule.action` against the rule's provider; returns (what was done, error).
+
+    Nothing happens unless the provider's own table opts in with
+    `kill_switch = true`, so one stray alert rule can't stop an agent.
+    """
+    provider = rule.provider.split(":", 1)[0]
+    pcfg = cfg.providers.get(provider)
+    if pcfg is None or pcfg.options.get("kill_switch") is not True:
+        return "", f"kill_switch is not enabled for {provider}"
+
+    if rule.action.startswith("command:"):
+        argv = shlex.split(rule.action.removeprefix("command:"))
+        env = {**os.environ, "USAGEDASH_PROVIDER": rule.provider, "USAGEDASH_RULE": rule.name}
+        try:
+            done = subprocess.run(argv, env=env, capture_output=True, text=True, timeout=COMMAND_TIMEOUT_SECONDS, check=False)
+        except (OSError, ValueError, subprocess.TimeoutExpired) as exc:
+            return "", f"{rule.action}: {exc}"
+        if done.returncode != 0:
+            return "", f"{rule.action}: exit status {done.returncode}"
+        return rule.action, None
+
+    if rule.action == "stop" and sys.platform == "win32":
+        return "", "stop is not supported on Windows; use terminate"
+    pids = agent_pids(provider)
+    if not pids:
+        return "", f"no running {provider} process"
+    sig = signal.SIGTERM if rule.action == "terminate" else signal.SIGSTOP
+    sent: list[str] = []
+    for pid in pids:
+        try:
+            os.kill(pid, sig)
+        except OSError:
+            continue
+        sent.append(str(pid))
+    if not sent:
+        return "", f"could not signal {provider} pids {', '.join(map(str, pids))}"
+    if sig == signal.SIGSTOP:
+        # Remembered so `usagedash alerts resume` can SIGCONT them later.
+        stopped = load_stopped(cfg)
+        stopped[provider] = sorted({*stopped.get(provider, []), *map(int, sent)})
+        _save_stopped(cfg, stopped)
+    return f"{rule.action} pid {', '.join(sent)}", None
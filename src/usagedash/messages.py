@@ -0,0 +1,155 @@
+from __future__ import annotations
+
+import tomllib
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+
+# Built-in English text for every diagnostic code. Codes are stable API:
+# exports and translations key on them, so rename only with a migration.
+CATALOG: dict[str, str] = {
+    "note": "{text}",
+    "parsed_from": "parsed from {provider} session: {file}",
+    "derived_from": "derived {provider} metrics from current session file: {file}",
+    "missing_source_file": "missing {path}",
+    "no_session_files": "no {provider} session files found in {path}",
+    "no_rate_limit_data": "no rate limit data found in {provider} session files",
+    "no_usage_tokens": "no usage tokens found in project logs",
+    "usage_not_inferred": "unable to infer {provider} usage from {sources}",
+    "parser_not_implemented": "{provider} parser is not implemented; use manual fields",
+    "reset_missing": "usage detected but reset timestamps missing",
+    "no_usage_metrics": "no usage metrics detected; configure providers.{provider}.manual.*",
+    "credentials_missing": "no {provider} credentials; set {hint}",
+    "invalid_credentials": "{provider} credentials are invalid: {reason}",
+    "fetch_failed": "{error}",
+    "unexpected_response": "unexpected {provider} response{detail}",
+    "budget_missing": "set monthly_budget or monthly_token_cap to report usage as a percentage",
+    "daily_spend": "{day}: {amount}",
+    "maintenance": "maintenance window {window}: not collected",
+    "command_failed": "{command} failed: {error}",
+    "call_log_missing": "no {provider} call log at {path}; nothing writes it unless each call is recorded with {hint}",
+    "command_skipped": "{command} not run during a dry run",
+    "invalid_option": "providers.{provider}.{key} must be a number, got {value}",
+    "probe_disabled": "{provider} limits are only readable by spending a request; set {hint} to probe",
+    "rate_limited": "[rate_limits] budget for {key} used up; showing the last collected values",
+    "limit_missing": "set {option} to report a percentage",
+    "no_monthly_cap": "{provider} production keys have no monthly cap; set {option} for a percentage",
+    "no_fast_request_limit": "{provider} plan has no fast-request limit",
+    "no_max_budget": "key has no max_budget; spend only",
+    "source_missing": "set {options} to collect usage",
+    "unknown_tier": "unknown tier {tier}; use {choices}",
+    "spent": "{amount} spent",
+    "spend_today_last_session": "today {today}, last session {session}",
+    "spend_today_week": "today {today}, 7 days {week}",
+    "balance": "balance {amount} {currency}",
+    "credits_remaining": "{amount} credits remaining",
+    "prompt_credits_used": "{used} of {total} prompt credits used",
+    "addon_credits_left": "{amount} add-on credits left",
+    "busiest_minute_quota": "busiest per-minute quota: {metric}",
+    "busiest_day_quota": "busiest per-day quota: {metric}",
+    "models_loaded": "models loaded: {count} ({models}, {vram} GiB VRAM)",
+    "no_models_loaded": "no models loaded",
+    "session_requests": "{requests} requests",
+    "tokens_generated": "{tokens} tokens generated",
+}
+
+SEVERITIES = ("info", "warn", "error")
+
+# Codes not listed here are informational.
+DEFAULT_SEVERITY: dict[str, str] = {
+    "missing_source_file": "warn",
+    "no_session_files": "warn",
+    "no_rate_limit_data": "warn",
+    "no_usage_tokens": "warn",
+    "usage_not_inferred": "warn",
+    "parser_not_implemented": "warn",
+    "reset_missing": "warn",
+    "no_usage_metrics": "error",
+    "credentials_missing": "error",
+    "invalid_credentials": "error",
+    "fetch_failed": "error",
+    "unexpected_response": "error",
+    "command_failed": "error",
+    "call_log_missing": "warn",
+    "invalid_option": "error",
+    "probe_disabled": "warn",
+    "rate_limited": "warn",
+    "source_missing": "warn",
+    "unknown_tier": "error",
+}
+
+_overrides: dict[str, str] = {}
+
+
+@dataclass
+class Diagnostic:
+    """A provider message as a stable code plus parameters, rendered on demand."""
+
+    code: str
+    params: dict[str, str] = field(default_factory=dict)
+    severity: str = ""
+    # Carried across cycles through the state file, so a message repeated
+    # every refresh reads as one entry; naive UTC.
+    first_seen: datetime | None = None
+    last_seen: datetime | None = None
+    count: int = 1
+    logged_at: datetime | None = None
+
+    def __post_init__(self) -> None:
+        if self.severity not in SEVERITIES:
+            self.severity = DEFAULT_SEVERITY.get(self.code, "info")
+
+    @property
+    def rank(self) -> int:
+        return SEVERITIES.index(self.severity)
+
+    def render(self) -> str:
+        return render_message(self)
+
+    @property
+    def identity(self) -> tuple[str, tuple[tuple[str, str], ...]]:
+        return self.code, tuple(sorted(self.params.items()))
+
+
+def dedupe(diagnostics: list[Diagnostic]) -> list[Diagnostic]:
+    """Drop repeats of the same code and parameters, keeping the first occurrence."""
+    seen: set[tuple[str, tuple[tuple[str, str], ...]]] = set()
+    out: list[Diagnostic] = []
+    for d in diagnostics:
+        key = d.identity
+        if key not in seen:
+            seen.add(key)
+            out.append(d)
+    return out
+
+
+def msg(code: str, severity: str = "", **params: object) -> Diagnostic:
+    if code not in CATALOG:
+        raise KeyError(f"unknown message code: {code}")
+    return Diagnostic(code=code, params={k: str(v) for k, v in params.items()}, severity=severity)
+
+
+class _Missing(dict):
+    def __missing__(self, key: str) -> str:
+        return "{" + key + "}"
+
+
+def render_message(diagnostic: Diagnostic) -> str:
+    template = _overrides.get(diagnostic.code) or CATALOG.get(diagnostic.code)
+    if template is None:
+        # Written by a newer version: fall back to any free text it carried.
+        return diagnostic.params.get("text", diagnostic.code)
+    return template.format_map(_Missing(diagnostic.params))
+
+
+def configure(catalog_path: str = "") -> None:
+    """Load `general.messages_file`, a TOML table of `code = "template"` overrides."""
+    _overrides.clear()
+    if not catalog_path:
+        return
+    raw = tomllib.loads(Path(catalog_path).expanduser().read_text())
+    _overrides.update({str(k): str(v) for k, v in raw.items() if isinstance(v, str)})
+
+
+def as_diagnostic(message: str | Diagnostic) -> Diagnostic:
+    return message if isinstance(message, Diagnostic) else Diagnostic(code="note", params={"text": message})
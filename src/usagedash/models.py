@@ -1,14 +1,42 @@
 from __future__ import annotations
 
 from dataclasses import dataclass, field
-from datetime import datetime
+from datetime import datetime, timedelta, timezone
 from enum import Enum
 
+from usagedash.messages import Diagnostic
+from usagedash.timefmt import format_duration, format_reset, format_since
+
+
+# Written into every serialized snapshot. Bump when a field is removed or
+# changes meaning; additions, and renames listed in
+# `snapshot.PROVIDER_FIELD_ALIASES`, keep the version.
+SNAPSHOT_SCHEMA_VERSION = 1
+
 
 class ProviderName(str, Enum):
     CODEX = "codex"
     CLAUDE = "claude"
     GEMINI = "gemini"
+    COPILOT = "copilot"
+    CURSOR = "cursor"
+    OPENAI = "openai"
+    GROQ = "groq"
+    DEEPSEEK = "deepseek"
+    XAI = "xai"
+    VERTEX = "vertex"
+    WINDSURF = "windsurf"
+    AIDER = "aider"
+    OLLAMA = "ollama"
+    COHERE = "cohere"
+    CUSTOM = "custom"
+    PLUGIN = "plugin"
+    AMAZON_Q = "amazon_q"
+    JETBRAINS = "jetbrains"
+    GITHUB_MODELS = "github_models"
+    CLINE = "cline"
+    FIREWORKS = "fireworks"
+    LITELLM = "litellm"
 
 
 class StatusKind(str, Enum):
@@ -23,21 +51,146 @@ class SourceKind(str, Enum):
     MIXED = "mixed"
 
 
+def _clamp(value: float) -> float:
+    return min(100.0, max(0.0, value))
+
+
+class Percent(float):
+    """Share of a limit used. Serializes as the plain float.
+
+    Above 100 the value is kept as reported: spend over budget reads above
+    100 so exports and alert thresholds see the overage, and only `bar`
+    clamps it. Below 0 nothing is meaningful, so it is floored at 0.
+    """
+
+    def __new__(cls, value: float) -> Percent:
+        return super().__new__(cls, max(0.0, float(value)))
+
+    @classmethod
+    def ratio(cls, used: float | None, limit: float | None) -> Percent | None:
+        """`used` out of a hard `limit`, capped at 0-100; None when either is unknown or the limit is 0."""
+        if used is None or not limit:
+            return None
+        return cls(_clamp(float(used) / float(limit) * 100.0))
+
+    @classmethod
+    def from_left(cls, left: float) -> Percent:
+        """From a "percent left" reading, as Codex and Copilot report it."""
+        return cls(_clamp(100.0 - float(left)))
+
+    @property
+    def left(self) -> Percent:
+        return Percent(max(0.0, 100.0 - self))
+
+    @property
+    def bar(self) -> float:
+        """How much of a 0-100 gauge to fill."""
+        return _clamp(self)
+
+    def display(self, digits: int = 1) -> str:
+        return f"{float(self):.{digits}f}%"
+
+
+class ResetAt(datetime):
+    """When a window resets; a datetime that also knows how to describe itself."""
+
+    @classmethod
+    def coerce(cls, value: datetime) -> ResetAt:
+        if isinstance(value, cls):
+            return value
+        return cls(
+            value.year, value.month, value.day, value.hour, value.minute, value.second,
+            value.microsecond, value.tzinfo, fold=value.fold,
+        )
+
+    def remaining(self, now: datetime | None = None) -> timedelta:
+        return self - (now or datetime.now(self.tzinfo))
+
+    def humanize(self, now: datetime | None = None) -> str:
+        """`in 3h 20m`, or `due` once the reset time has passed."""
+        left = self.remaining(now).total_seconds()
+        return f"in {format_duration(left)}" if left > 0 else "due"
+
+    def display(self) -> str:
+        return format_reset(self)
+
+
+_PERCENT_FIELDS = frozenset({"session_used_pct", "weekly_used_pct"})
+_RESET_FIELDS = frozenset({"session_reset_at", "weekly_reset_at"})
+
+
 @dataclass
 class ProviderSnapshot:
     provider: ProviderName
     status: StatusKind
-    session_used_pct: float | None = None
-    session_reset_at: datetime | None = None
-    weekly_used_pct: float | None = None
-    weekly_reset_at: datetime | None = None
+    session_used_pct: Percent | None = None
+    session_reset_at: ResetAt | None = None
+    weekly_used_pct: Percent | None = None
+    weekly_reset_at: ResetAt | None = None
     source: SourceKind = SourceKind.MANUAL
     messages: list[str] = field(default_factory=list)
     details: dict[str, object] = field(default_factory=dict)
     updated_at: datetime = field(default_factory=datetime.utcnow)
+    display_name: str = ""
+    # Set when one provider reports several accounts or profiles, one row each.
+    account: str = ""
+    # Structured form of `messages`; renderers prefer these so they can localize.
+    diagnostics: list[Diagnostic] = field(default_factory=list)
+
+    def __setattr__(self, name: str, value: object) -> None:
+        # Construction and every later assignment go through here, so the
+        # types live in one place rather than in each provider.
+        if value is not None and name in _PERCENT_FIELDS:
+            value = Percent(value)  # type: ignore[arg-type]
+        elif value is not None and name in _RESET_FIELDS:
+            value = ResetAt.coerce(value)  # type: ignore[arg-type]
+        super().__setattr__(name, value)
+
+    def rendered_messages(self, detail: bool = True) -> list[str]:
+        """All messages, or with `detail=False` only the most severe one.
+
+        In detail, a message repeated across cycles says how often and since when.
+        """
+        if not self.diagnostics:
+            return list(self.messages) if detail else self.messages[:1]
+        if detail:
+            now = datetime.now(timezone.utc).replace(tzinfo=None)
+            return [
+                f"{d.render()} ({d.count}x, first {format_duration((now - d.first_seen).total_seconds())} ago)"
+                if d.count > 1 and d.first_seen is not None else d.render()
+                for d in self.diagnostics
+            ]
+        # max() keeps the first of equally severe messages, i.e. the adapter's own.
+        return [max(self.diagnostics, key=lambda d: d.rank).render()]
+
+    @property
+    def label(self) -> str:
+        """`display_name` from config, else the provider id, plus the account if any."""
+        base = self.display_name or self.provider.value
+        return f"{base} ({self.account})" if self.account else base
+
+    @property
+    def key(self) -> str:
+        """`provider` or `provider:account`; unique within a snapshot."""
+        return f"{self.provider.value}:{self.account}" if self.account else self.provider.value
+
+    @property
+    def activity(self) -> str:
+        """"active", "idle since 14:05", or "" for providers without transcripts."""
+        raw = (self.details or {}).get("last_activity_at")
+        if not isinstance(raw, str):
+            return ""
+        if (self.details or {}).get("session_active"):
+            return "active"
+        try:
+            return f"idle since {format_since(datetime.fromisoformat(raw))}"
+        except ValueError:
+            return ""
 
 
 @dataclass
 class UsageSnapshot:
     generated_at: datetime
     providers: list[ProviderSnapshot]
+    paused: bool = False
+    paused_until: datetime | None = None
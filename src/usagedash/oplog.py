@@ -0,0 +1,64 @@
+from __future__ import annotations
+
+import sys
+
+from usagedash.config import Config
+
+LOG_LEVELS = {"info": 0, "warning": 1, "error": 2}
+
+# Event ids shown in the Windows Event Viewer; stable so filters keep working.
+EVENT_IDS = {"start": 1, "stop": 2, "config_reloaded": 3, "source_restored": 4, "alert": 10, "collect_failed": 20, "sink_failed": 21, "config_reload_failed": 22, "source_missing": 23, "provider_message": 24, "kill_switch": 30}
+
+_SYSLOG_PRIORITIES = {"info": 6, "warning": 4, "error": 3}  # LOG_INFO, LOG_WARNING, LOG_ERR
+_EVENTLOG_TYPES = {"info": 0x0004, "warning": 0x0002, "error": 0x0001}
+
+
+def log_event(cfg: Config, event: str, message: str, level: str = "info", stderr_fallback: bool = False) -> None:
+    """Send a daemon lifecycle or failure event to each `[log] sinks` entry.
+
+    `syslog` reaches journald too on systemd hosts; `eventlog` writes to the
+    Windows Application log. Failures are swallowed: an unreachable log
+    must never stop collection. With no sinks configured, events passed
+    `stderr_fallback` (data that was not written) still go to stderr.
+    """
+    if LOG_LEVELS.get(level, 0) < LOG_LEVELS.get(cfg.log.min_level, 0):
+        return
+    sinks = cfg.log.sinks or (["stderr"] if stderr_fallback else [])
+    for sink in sinks:
+        try:
+            if sink == "syslog":
+                _syslog(cfg.log.ident, event, message, level)
+            elif sink == "eventlog":
+                _eventlog(cfg.log.ident, event, message, level)
+            elif sink == "stderr":
+                print(f"{cfg.log.ident}[{level}] {event}: {message}", file=sys.stderr)
+        except (OSError, ImportError, AttributeError):
+            continue
+
+
+def _syslog(ident: str, event: str, message: str, level: str) -> None:
+    import syslog
+
+    syslog.openlog(ident, syslog.LOG_PID, syslog.LOG_DAEMON)
+    syslog.syslog(_SYSLOG_PRIORITIES.get(level, 6), f"{event}: {message}")
+
+
+def _eventlog(ident: str, event: str, message: str, level: str) -> None:
+    # No registered message DLL, so Event Viewer prefixes the text with a
+    # "description not found" note; the message itself is still recorded.
+    import ctypes
+    from ctypes import wintypes
+
+    advapi32 = ctypes.windll.advapi32  # type: ignore[attr-defined]
+    handle = advapi32.RegisterEventSourceW(None, ident)
+    if not handle:
+        raise OSError("RegisterEventSourceW failed")
+    try:
+        strings = (wintypes.LPCWSTR * 1)(f"{event}: {message}")
+        ok = advapi32.ReportEventW(
+            handle, _EVENTLOG_TYPES.get(level, 0x0004), 0, EVENT_IDS.get(event, 0), None, 1, 0, strings, None
+        )
+        if not ok:
+            raise OSError("ReportEventW failed")
+    finally:
+        advapi32.DeregisterEventSource(handle)
@@ -0,0 +1,81 @@
+from __future__ import annotations
+
+import os
+import tempfile
+from pathlib import Path
+
+
+def _resolved_home() -> Path | None:
+    try:
+        return Path.home()
+    except (RuntimeError, KeyError):
+        return None
+
+
+def home_dir() -> Path:
+    """The user's home, or the temp dir when none can be resolved (e.g. a container
+    running as an arbitrary UID with HOME unset). `home_warning` reports the fallback."""
+    return _resolved_home() or Path(tempfile.gettempdir())
+
+
+def _xdg(var: str, fallback: str) -> Path:
+    # The spec says relative values are invalid and must be ignored.
+    value = os.environ.get(var, "")
+    return Path(value) if value and os.path.isabs(value) else home_dir() / fallback
+
+
+def xdg_config_home() -> Path:
+    return _xdg("XDG_CONFIG_HOME", ".config")
+
+
+def xdg_state_home() -> Path:
+    return _xdg("XDG_STATE_HOME", ".local/state")
+
+
+def xdg_cache_home() -> Path:
+    return _xdg("XDG_CACHE_HOME", ".cache")
+
+
+def xdg_data_home() -> Path:
+    return _xdg("XDG_DATA_HOME", ".local/share")
+
+
+def _dir(override: str, base: Path) -> Path:
+    value = os.environ.get(override)
+    return Path(value).expanduser() if value else base / "usagedash"
+
+
+def config_dir() -> Path:
+    """`USAGEDASH_CONFIG_DIR`, else `$XDG_CONFIG_HOME/usagedash` (`~/.config/usagedash`)."""
+    return _dir("USAGEDASH_CONFIG_DIR", xdg_config_home())
+
+
+def state_dir() -> Path:
+    """`USAGEDASH_STATE_DIR`, else `$XDG_STATE_HOME/usagedash` (`~/.local/state/usagedash`).
+
+    Holds the state file and, next to it, history, alert state, locks, and
+    provider checkpoints.
+    """
+    return _dir("USAGEDASH_STATE_DIR", xdg_state_home())
+
+
+def cache_dir() -> Path:
+    """`USAGEDASH_CACHE_DIR`, else `$XDG_CACHE_HOME/usagedash` (`~/.cache/usagedash`)."""
+    return _dir("USAGEDASH_CACHE_DIR", xdg_cache_home())
+
+
+def home_warning() -> str | None:
+    """Explain where files go when there is no home and nothing overrides it."""
+    if _resolved_home() is not None:
+        return None
+    missing = [
+        override
+        for override, xdg in (("USAGEDASH_CONFIG_DIR", "XDG_CONFIG_HOME"), ("USAGEDASH_STATE_DIR", "XDG_STATE_HOME"))
+        if not os.environ.get(override) and not os.path.isabs(os.environ.get(xdg, ""))
+    ]
+    if not missing:
+        return None
+    return (
+        f"no home directory; using {home_dir()} for files. "
+        f"Set {' and '.join(missing)} or pass --in-memory to keep them elsewhere."
+    )
@@ -0,0 +1,80 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.snapshot import read_snapshot, write_snapshot_files
+
+
+@dataclass
+class PauseState:
+    since: datetime
+    until: datetime | None = None
+
+
+def pause_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).parent / "paused.json"
+
+
+def pause(cfg: Config, until: datetime | None = None, now: datetime | None = None) -> PauseState:
+    """Stop collection until `until`, or until `usagedash resume` when None."""
+    state = PauseState(since=now or datetime.now(), until=until)
+    path = pause_path(cfg)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps({
+        "since": state.since.isoformat(),
+        "until": state.until.isoformat() if state.until else None,
+    }))
+    return state
+
+
+def resume(cfg: Config) -> bool:
+    """Clear the pause flag. Returns False when collection was not paused."""
+    try:
+        pause_path(cfg).unlink()
+    except FileNotFoundError:
+        return False
+    return True
+
+
+def mark_snapshot(cfg: Config, paused: bool, until: datetime | None) -> None:
+    """Flag the stored snapshot so the tray and other state readers show the pause."""
+    try:
+        snapshot = read_snapshot(cfg.general.state_file)
+    except (OSError, ValueError, KeyError):
+        return
+    snapshot.paused = paused
+    snapshot.paused_until = until
+    write_snapshot_files(cfg, snapshot)
+
+
+def clear_expired(cfg: Config, now: datetime | None = None) -> bool:
+    """Remove a `--for` pause that has run out and unflag the stored snapshot.
+
+    Returns True when one was cleared. Without this the state file keeps
+    `paused` until the next collection rewrites it.
+    """
+    path = pause_path(cfg)
+    if not path.exists() or pause_state(cfg, now) is not None:
+        return False
+    path.unlink(missing_ok=True)
+    mark_snapshot(cfg, False, None)
+    return True
+
+
+def pause_state(cfg: Config, now: datetime | None = None) -> PauseState | None:
+    """The active pause, or None. An expired `--for` pause counts as resumed."""
+    try:
+        raw = json.loads(pause_path(cfg).read_text())
+        state = PauseState(
+            since=datetime.fromisoformat(raw["since"]),
+            until=datetime.fromisoformat(raw["until"]) if raw.get("until") else None,
+        )
+    except (OSError, json.JSONDecodeError, KeyError, TypeError, ValueError):
+        return None
+    if state.until is not None and (now or datetime.now()) >= state.until:
+        return None
+    return state
@@ -0,0 +1,173 @@
+from __future__ import annotations
+
+import atexit
+import random
+import time
+from datetime import timedelta
+from pathlib import Path
+
+from usagedash.alerts import process_alerts
+from usagedash.bus import Bus
+from usagedash.config import Config
+from usagedash.flags import write_flags
+from usagedash.heartbeat import beat, counts_as_success
+from usagedash.history import append_history, prune_history
+from usagedash.locking import LockBusy, file_lock
+from usagedash.models import UsageSnapshot
+from usagedash.oplog import log_event
+from usagedash.pause import clear_expired, pause_state
+from usagedash.snapshot import build_snapshot, read_snapshot, write_snapshot_files
+from usagedash.sources import SourceWatchdog
+from usagedash.store import open_store
+
+
+MIN_REFRESH_SECONDS = 0.1
+MESSAGE_LOG_INTERVAL = timedelta(hours=1)
+
+
+def refresh_interval(cfg: Config) -> float:
+    """Seconds to sleep before the next cycle, with `refresh_jitter_seconds` of spread.
+
+    Jitter is drawn uniformly from ±jitter so a fleet started at the same
+    moment drifts apart instead of pushing to a team server in lockstep.
+    """
+    interval = cfg.general.refresh_seconds
+    jitter = cfg.general.refresh_jitter_seconds
+    if jitter > 0:
+        interval += random.uniform(-jitter, jitter)
+    return max(MIN_REFRESH_SECONDS, interval)
+
+
+def collect_lock_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).parent / "collect.lock"
+
+
+def run_cycle(cfg: Config) -> UsageSnapshot:
+    """One collection pass: collect, persist state and history, evaluate alerts, then heartbeat.
+
+    Holds the collect lock throughout so concurrent invocations can wait for
+    this pass instead of starting their own. In read-only mode nothing is
+    persisted: the snapshot is only collected and returned.
+    """
+    paused = paused_snapshot(cfg)
+    if paused is not None:
+        return paused
+    if cfg.general.read_only:
+        return build_snapshot(cfg)
+    with file_lock(collect_lock_path(cfg)):
+        return _collect(cfg)
+
+
+def paused_snapshot(cfg: Config) -> UsageSnapshot | None:
+    """While paused, the last collected snapshot flagged as paused; None otherwise.
+
+    Nothing is collected or written, so provider files are left untouched.
+    """
+    state = pause_state(cfg)
+    if state is None:
+        if not cfg.general.read_only:
+            clear_expired(cfg)
+        return None
+    try:
+        snapshot = read_snapshot(cfg.general.state_file)
+    except (OSError, ValueError, KeyError):
+        snapshot = UsageSnapshot(generated_at=state.since, providers=[])
+    snapshot.paused = True
+    snapshot.paused_until = state.until
+    return snapshot
+
+
+def _persist(cfg: Config, snapshot: UsageSnapshot) -> None:
+    with open_store(cfg) as store:
+        if append_history(cfg, store, snapshot):
+            prune_history(cfg, store)
+
+
+def _alert(cfg: Config, snapshot: UsageSnapshot) -> None:
+    # Apart from "store" so queueing it behind a slow webhook leaves history alone.
+    with open_store(cfg) as store:
+        process_alerts(cfg, store, snapshot)
+
+
+# Consumers of every collected snapshot. The state file is required: without
+# it readers see stale data, so its failure fails the cycle and skips the
+# heartbeat. This is the full list; `[bus] queued` moves any of the optional
+# ones onto its own thread.
+BUS = Bus()
+BUS.subscribe("state", write_snapshot_files, required=True)
+BUS.subscribe("flags", write_flags)
+BUS.subscribe("store", _persist)
+BUS.subscribe("alerts", _alert)
+# Long-running loops log when ~/.claude or ~/.codex vanishes and returns.
+SOURCES = SourceWatchdog()
+BUS.subscribe("sources", SOURCES.check)
+# One-shot commands exit right after collecting; let queued subscribers finish.
+atexit.register(BUS.drain, 5.0)
+
+
+def log_messages(cfg: Config, snapshot: UsageSnapshot, every: timedelta = MESSAGE_LOG_INTERVAL) -> None:
+    """Log warnings and errors from providers, each unique message at most once per `every`.
+
+    Runs before the state file is written so `logged_at` persists; otherwise a
+    message repeated every refresh would fill the log.
+    """
+    for row in snapshot.providers:
+        for d in row.diagnostics:
+            if d.severity == "info" or (d.logged_at is not None and snapshot.generated_at - d.logged_at < every):
+                continue
+            repeat = f" (seen {d.count}x)" if d.count > 1 else ""
+            log_event(cfg, "provider_message", f"{row.label}: {d.render()}{repeat}", "error" if d.severity == "error" else "warning")
+            d.logged_at = snapshot.generated_at
+
+
+def _collect(cfg: Config) -> UsageSnapshot:
+    snapshot = build_snapshot(cfg)
+    log_messages(cfg, snapshot)
+    BUS.publish(cfg, snapshot)
+    if counts_as_success(cfg, snapshot):
+        beat(cfg)
+    return snapshot
+
+
+def fresh_snapshot(cfg: Config, max_age_seconds: float) -> UsageSnapshot | None:
+    """Return the state file's snapshot if it was written within `max_age_seconds`."""
+    path = Path(cfg.general.state_file)
+    try:
+        age = time.time() - path.stat().st_mtime
+        if age > max_age_seconds:
+            return None
+        return read_snapshot(path)
+    except (OSError, ValueError, KeyError):
+        return None
+
+
+def collect_or_reuse(cfg: Config) -> UsageSnapshot:
+    """Reuse a just-written snapshot or wait for an in-flight collection.
+
+    One-off commands call this so running `panel` next to the dashboard or
+    `watch` does not parse the same history files and hit the same APIs twice.
+    """
+    paused = paused_snapshot(cfg)
+    if paused is not None:
+        return paused
+    max_age = cfg.general.reuse_snapshot_seconds
+    if max_age > 0:
+        snap = fresh_snapshot(cfg, max_age)
+        if snap is not None:
+            return snap
+    if cfg.general.read_only:
+        return build_snapshot(cfg)
+
+    lock = collect_lock_path(cfg)
+    try:
+        with file_lock(lock, blocking=False):
+            return _collect(cfg)
+    except LockBusy:
+        pass
+
+    # Another process is collecting right now: wait for it, then use its result.
+    with file_lock(lock):
+        snap = fresh_snapshot(cfg, max(max_age, 1.0))
+        if snap is not None:
+            return snap
+        return _collect(cfg)
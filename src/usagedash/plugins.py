@@ -0,0 +1,57 @@
+from __future__ import annotations
+
+import os
+import shutil
+import subprocess
+from pathlib import Path
+
+from usagedash.config import CONFIG_PATH
+
+PLUGIN_PREFIX = "usagedash-"
+# `usagedash-provider-<name>` executables are provider plugins, not subcommands.
+PROVIDER_PREFIX = "provider-"
+
+
+def find_subcommand(name: str) -> str | None:
+    """Resolve `usagedash <name>` to a `usagedash-<name>` executable on PATH."""
+    if not name or "/" in name or "\\" in name or name.startswith(PROVIDER_PREFIX):
+        return None
+    return shutil.which(f"{PLUGIN_PREFIX}{name}")
+
+
+def list_subcommands() -> dict[str, str]:
+    """Return every external subcommand on PATH, first match wins like the shell."""
+    return {k: v for k, v in _scan_path(PLUGIN_PREFIX).items() if not k.startswith(PROVIDER_PREFIX)}
+
+
+def list_provider_plugins() -> dict[str, str]:
+    """Return every `usagedash-provider-<name>` executable on PATH, keyed by name."""
+    return _scan_path(PLUGIN_PREFIX + PROVIDER_PREFIX)
+
+
+def _scan_path(prefix: str) -> dict[str, str]:
+    found: dict[str, str] = {}
+    for entry in os.environ.get("PATH", "").split(os.pathsep):
+        directory = Path(entry)
+        if not entry or not directory.is_dir():
+            continue
+        try:
+            children = sorted(directory.iterdir())
+        except OSError:
+            continue
+        for child in children:
+            if not child.name.startswith(prefix):
+                continue
+            name = child.stem if os.name == "nt" else child.name
+            name = name[len(prefix):]
+            if not name or name in found:
+                continue
+            if child.is_file() and os.access(child, os.X_OK):
+                found[name] = str(child)
+    return found
+
+
+def run_subcommand(path: str, args: list[str]) -> int:
+    env = dict(os.environ)
+    env.setdefault("USAGEDASH_CONFIG", str(CONFIG_PATH))
+    return subprocess.call([path, *args], env=env)
@@ -0,0 +1,119 @@
+from __future__ import annotations
+
+import csv
+import io
+import os
+import shutil
+import subprocess
+import sys
+from pathlib import Path
+
+# Provider id -> executable names its CLI runs as. The npm-installed CLIs run
+# under node, so the script name in argv counts too, but only behind one of
+# INTERPRETERS: `vim codex` or `less claude` is not an agent.
+AGENT_PROCESSES: dict[str, tuple[str, ...]] = {
+    "claude": ("claude",),
+    "codex": ("codex",),
+    "gemini": ("gemini",),
+}
+INTERPRETERS = ("node", "nodejs", "bun", "deno", "python", "python3")
+
+
+def _base(arg: str) -> str:
+    name = arg.replace("\\", "/").rsplit("/", 1)[-1].lower()
+    for suffix in (".exe", ".cmd", ".js", ".mjs"):
+        if name.endswith(suffix):
+            return name[: -len(suffix)]
+    return name
+
+
+def _is_interpreter(name: str) -> bool:
+    return name in INTERPRETERS or name.startswith("python3.")
+
+
+def _candidates(comm: str, argv: list[str]) -> set[str]:
+    """The executable names a process may run as: its comm and argv[0], plus
+    argv[1] when argv[0] is an interpreter running a script."""
+    names = {_base(a) for a in (comm, *argv[:1]) if a}
+    if len(argv) > 1 and argv[1] and _is_interpreter(_base(argv[0])):
+        names.add(_base(argv[1]))
+    return names
+
+
+Scan = list[tuple[int, set[str]]]
+
+
+def _proc_names(proc: Path = Path("/proc")) -> Scan:
+    """(pid, candidate names) per process, from comm and the first two argv entries."""
+    out: Scan = []
+    for entry in proc.iterdir():
+        if not entry.name.isdigit():
+            continue
+        try:
+            comm = (entry / "comm").read_text().strip()
+            argv = (entry / "cmdline").read_bytes().split(b"\0")[:2]
+        except OSError:
+            continue
+        out.append((int(entry.name), _candidates(comm, [a.decode(errors="ignore") for a in argv])))
+    return out
+
+
+def _ps_names() -> Scan:
+    try:
+        done = subprocess.run(["ps", "-Ao", "pid=,args="], capture_output=True, text=True, timeout=5, check=False)
+    except (OSError, subprocess.TimeoutExpired):
+        return []
+    out: Scan = []
+    for line in done.stdout.splitlines():
+        pid, _, args = line.strip().partition(" ")
+        if pid.isdigit():
+            out.append((int(pid), _candidates("", args.split()[:2])))
+    return out
+
+
+def _tasklist_names(exe: str) -> Scan:
+    try:
+        done = subprocess.run([exe, "/fo", "csv", "/nh"], capture_output=True, text=True, timeout=10, check=False)
+    except (OSError, subprocess.TimeoutExpired):
+        return []
+    return [(int(row[1]), {_base(row[0])}) for row in csv.reader(io.StringIO(done.stdout)) if len(row) > 1 and row[1].isdigit()]
+
+
+def _local_scan() -> Scan:
+    if sys.platform == "win32":
+        return _tasklist_names("tasklist")
+    if Path("/proc/self").exists():
+        return _proc_names()
+    return _ps_names()
+
+
+def is_wsl() -> bool:
+    try:
+        return "microsoft" in Path("/proc/version").read_text().lower()
+    except OSError:
+        return False
+
+
+def running_agents(include_windows: bool = False) -> dict[str, int]:
+    """How many processes of each agent CLI are running.
+
+    `include_windows` also lists Windows-side processes when running under
+    WSL, through the `tasklist.exe` interop binary.
+    """
+    names = _local_scan()
+    if include_windows and sys.platform != "win32" and is_wsl():
+        exe = shutil.which("tasklist.exe") or "/mnt/c/Windows/System32/tasklist.exe"
+        if os.path.exists(exe):
+            names += _tasklist_names(exe)
+
+    counts: dict[str, int] = {}
+    for provider, exes in AGENT_PROCESSES.items():
+        counts[provider] = sum(1 for _, candidates in names if candidates.intersection(exes))
+    return counts
+
+
+def agent_pids(provider: str) -> list[int]:
+    """PIDs of `provider`'s CLI on this machine (never the Windows side of WSL)."""
+    exes = AGENT_PROCESSES.get(provider, ())
+    own = os.getpid()
+    return [pid for pid, candidates in _local_scan() if pid != own and candidates.intersection(exes)]
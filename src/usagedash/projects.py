@@ -0,0 +1,126 @@
+from __future__ import annotations
+
+import os
+import tomllib
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.events import UsageEvent, iter_claude_events
+
+
+def claude_project_tokens(
+    since: datetime,
+    projects_path: Path | None = None,
+) -> dict[str, float]:
+    """Sum Claude input+output tokens per project directory since `since`."""
+    totals: dict[str, float] = {}
+    for event in iter_claude_events(since, projects_path):
+        totals[event.project] = totals.get(event.project, 0.0) + event.tokens
+    return totals
+
+
+PROJECT_FILE = ".usagedash.toml"
+
+
+@dataclass
+class ProjectBudget:
+    root: Path
+    name: str
+    daily_tokens: float | None = None
+    weekly_tokens: float | None = None
+    tags: dict[str, str] = field(default_factory=dict)
+
+
+@dataclass
+class ProjectUsage:
+    budget: ProjectBudget
+    daily_tokens: float
+    weekly_tokens: float
+
+    @property
+    def daily_pct(self) -> float | None:
+        if not self.budget.daily_tokens:
+            return None
+        return self.daily_tokens / self.budget.daily_tokens * 100.0
+
+    @property
+    def weekly_pct(self) -> float | None:
+        if not self.budget.weekly_tokens:
+            return None
+        return self.weekly_tokens / self.budget.weekly_tokens * 100.0
+
+    @property
+    def exceeded(self) -> list[str]:
+        out: list[str] = []
+        if self.daily_pct is not None and self.daily_pct >= 100.0:
+            out.append("daily")
+        if self.weekly_pct is not None and self.weekly_pct >= 100.0:
+            out.append("weekly")
+        return out
+
+
+def find_project_budget(cwd: Path | None = None) -> ProjectBudget | None:
+    """Look for `.usagedash.toml` in `cwd` and its parents, like git does for `.git`."""
+    start = (cwd or Path.cwd()).resolve()
+    for directory in (start, *start.parents):
+        path = directory / PROJECT_FILE
+        if not path.is_file():
+            continue
+        raw = tomllib.loads(path.read_text())
+        budget = raw.get("budget", {})
+        daily = budget.get("daily_tokens")
+        weekly = budget.get("weekly_tokens")
+        return ProjectBudget(
+            root=directory,
+            name=str(budget.get("name", directory.name)),
+            daily_tokens=float(daily) if daily is not None else None,
+            weekly_tokens=float(weekly) if weekly is not None else None,
+            tags={str(k): str(v) for k, v in raw.get("tags", {}).items()},
+        )
+    return None
+
+
+def _under(project: str, root: Path) -> bool:
+    root_str = str(root)
+    return project == root_str or project.startswith(root_str.rstrip(os.sep) + os.sep)
+
+
+def project_usage(
+    budget: ProjectBudget,
+    now: datetime | None = None,
+    projects_path: Path | None = None,
+) -> ProjectUsage:
+    """Attribute Claude tokens to the project by the working directory of each turn."""
+    now = now or datetime.now()
+    day_start = datetime.combine(now.date(), datetime.min.time())
+    totals = claude_project_tokens(now - timedelta(days=7), projects_path)
+    weekly = sum(v for k, v in totals.items() if _under(k, budget.root))
+    today = claude_project_tokens(day_start, projects_path)
+    daily = sum(v for k, v in today.items() if _under(k, budget.root))
+    return ProjectUsage(budget=budget, daily_tokens=daily, weekly_tokens=weekly)
+
+
+def tag_events(cfg: Config, events: list[UsageEvent]) -> list[UsageEvent]:
+    """Attach cost-allocation tags to each event from its project.
+
+    Tags come from the nearest `.usagedash.toml` `[tags]` table above the
+    event's working directory, overlaid by `[project_tags."<dir>"]` in the
+    main config for projects that cannot carry their own file.
+    """
+    cache: dict[str, dict[str, str]] = {}
+    for event in events:
+        if event.project not in cache:
+            tags: dict[str, str] = {}
+            path = Path(event.project)
+            if path.is_absolute():
+                budget = find_project_budget(path) if path.exists() else None
+                if budget is not None:
+                    tags.update(budget.tags)
+            for root, extra in cfg.project_tags.items():
+                if _under(event.project, Path(root).expanduser()):
+                    tags.update(extra)
+            cache[event.project] = tags
+        event.tags = {**cache[event.project], **event.tags}
+    return events
@@ -0,0 +1,69 @@
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from datetime import datetime
+
+# Versions this build can speak, oldest first. A peer offers its own list in
+# `hello` and both sides use the highest version they share, so an older
+# tray keeps working against a newer CLI until support for its version is
+# dropped here.
+SUPPORTED_VERSIONS = (1,)
+PROTOCOL_VERSION = SUPPORTED_VERSIONS[-1]
+
+# Message types per version; unknown types are rejected rather than ignored.
+MESSAGE_TYPES = {
+    1: frozenset({"hello", "welcome", "get_snapshot", "snapshot", "pause", "resume", "reload", "ok", "error"}),
+}
+
+
+class ProtocolError(ValueError):
+    pass
+
+
+@dataclass
+class Message:
+    type: str
+    version: int = PROTOCOL_VERSION
+    payload: dict = field(default_factory=dict)
+
+
+def negotiate(offered: list[int]) -> int:
+    common = set(offered) & set(SUPPORTED_VERSIONS)
+    if not common:
+        raise ProtocolError(
+            f"no common protocol version: peer offers {sorted(offered)}, "
+            f"this build supports {list(SUPPORTED_VERSIONS)}"
+        )
+    return max(common)
+
+
+def _json_default(obj: object) -> str:
+    if isinstance(obj, datetime):
+        return obj.isoformat()
+    raise TypeError(f"not serializable: {type(obj)!r}")
+
+
+def encode(message: Message) -> str:
+    """One message per line: `{"v": 1, "type": "...", ...payload}`."""
+    return json.dumps({"v": message.version, "type": message.type, **message.payload}, default=_json_default)
+
+
+def decode(line: str | bytes) -> Message:
+    try:
+        raw = json.loads(line)
+    except json.JSONDecodeError as exc:
+        raise ProtocolError(f"malformed message: {exc}") from exc
+    if not isinstance(raw, dict):
+        raise ProtocolError("message must be a JSON object")
+    version = raw.pop("v", None)
+    msg_type = raw.pop("type", None)
+    if version not in SUPPORTED_VERSIONS:
+        raise ProtocolError(f"unsupported protocol version {version!r}")
+    if msg_type not in MESSAGE_TYPES[version]:
+        raise ProtocolError(f"unknown message type {msg_type!r} for protocol v{version}")
+    return Message(type=msg_type, version=version, payload=raw)
+
+
+def error(text: str, version: int = PROTOCOL_VERSION) -> Message:
+    return Message(type="error", version=version, payload={"error": text})
@@ -1,5 +1,52 @@
+from usagedash.providers.base import ProviderAdapter
+from usagedash.providers.aider import AiderAdapter
+from usagedash.providers.amazon_q import AmazonQAdapter
 from usagedash.providers.claude import ClaudeAdapter
+from usagedash.providers.cline import ClineAdapter
 from usagedash.providers.codex import CodexAdapter
+from usagedash.providers.cohere import CohereAdapter
+from usagedash.providers.copilot import CopilotAdapter
+from usagedash.providers.cursor import CursorAdapter
+from usagedash.providers.custom import CustomAdapter
+from usagedash.providers.deepseek import DeepSeekAdapter
+from usagedash.providers.fireworks import FireworksAdapter
 from usagedash.providers.gemini import GeminiAdapter
+from usagedash.providers.github_models import GitHubModelsAdapter
+from usagedash.providers.groq import GroqAdapter
+from usagedash.providers.jetbrains import JetBrainsAdapter
+from usagedash.providers.litellm import LiteLLMAdapter
+from usagedash.providers.ollama import OllamaAdapter
+from usagedash.providers.openai_billing import OpenAIBillingAdapter
+from usagedash.providers.plugin import PluginAdapter
+from usagedash.providers.vertex import VertexAdapter
+from usagedash.providers.windsurf import WindsurfAdapter
+from usagedash.providers.xai import XaiAdapter
 
-__all__ = ["CodexAdapter", "ClaudeAdapter", "GeminiAdapter"]
+# Keys match `[providers.<name>]` tables. Order here does not matter: rows are
+# collected and shown by `order`, then id (see `snapshot.build_snapshot`).
+ADAPTERS: dict[str, type[ProviderAdapter]] = {
+    "codex": CodexAdapter,
+    "claude": ClaudeAdapter,
+    "gemini": GeminiAdapter,
+    "copilot": CopilotAdapter,
+    "cursor": CursorAdapter,
+    "openai": OpenAIBillingAdapter,
+    "groq": GroqAdapter,
+    "deepseek": DeepSeekAdapter,
+    "xai": XaiAdapter,
+    "vertex": VertexAdapter,
+    "windsurf": WindsurfAdapter,
+    "aider": AiderAdapter,
+    "ollama": OllamaAdapter,
+    "cohere": CohereAdapter,
+    "amazon_q": AmazonQAdapter,
+    "jetbrains": JetBrainsAdapter,
+    "github_models": GitHubModelsAdapter,
+    "cline": ClineAdapter,
+    "fireworks": FireworksAdapter,
+    "litellm": LiteLLMAdapter,
+    "custom": CustomAdapter,
+    "plugin": PluginAdapter,
+}
+
+__all__ = ["ADAPTERS", "AiderAdapter", "AmazonQAdapter", "CodexAdapter", "ClaudeAdapter", "ClineAdapter", "CohereAdapter", "CopilotAdapter", "CursorAdapter", "CustomAdapter", "DeepSeekAdapter", "FireworksAdapter", "GeminiAdapter", "GitHubModelsAdapter", "GroqAdapter", "JetBrainsAdapter", "LiteLLMAdapter", "OllamaAdapter", "OpenAIBillingAdapter", "PluginAdapter", "VertexAdapter", "WindsurfAdapter", "XaiAdapter"]
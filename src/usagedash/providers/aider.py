@@ -0,0 +1,174 @@
+from __future__ import annotations
+
+import json
+import os
+import re
+from dataclasses import dataclass, field
+from datetime import date, datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.paths import home_dir, state_dir
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage, option_float
+
+HISTORY_NAME = ".aider.chat.history.md"
+STARTED_RE = re.compile(r"^# aider chat started at (\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2})")
+COST_RE = re.compile(r"Cost: \$([0-9.]+) message, \$([0-9.]+) session")
+# Never descended into while looking for history files.
+SKIP_DIRS = {"node_modules", "venv", "target", "__pycache__"}
+# How long a search of the roots is reused before walking them again.
+DEFAULT_RESCAN_HOURS = 24.0
+
+
+@dataclass
+class AiderSpend:
+    daily: dict[date, float] = field(default_factory=dict)
+    last_session: float = 0.0
+    last_session_at: datetime | None = None
+    files: int = 0
+
+
+def find_histories(roots: list[Path], depth: int) -> list[Path]:
+    """`.aider.chat.history.md` files up to `depth` directories below each root."""
+    found: list[Path] = []
+    for root in roots:
+        base = len(root.parts)
+        for dirpath, dirnames, filenames in os.walk(root):
+            here = Path(dirpath)
+            if HISTORY_NAME in filenames:
+                found.append(here / HISTORY_NAME)
+            if len(here.parts) - base >= depth:
+                dirnames.clear()
+            else:
+                dirnames[:] = [d for d in dirnames if not d.startswith(".") and d not in SKIP_DIRS]
+    return found
+
+
+def parse_history(path: Path, spend: AiderSpend) -> None:
+    """Add each message cost to the day its chat started on, tracking the newest session.
+
+    The history records no time per message, so a chat left open past
+    midnight books all of it to the day it started; the analytics log does not.
+    """
+    started: datetime | None = None
+    with path.open(errors="ignore") as fh:
+        for line in fh:
+            m = STARTED_RE.match(line)
+            if m:
+                started = datetime.fromisoformat(m.group(1))
+                continue
+            m = COST_RE.search(line)
+            if m is None or started is None:
+                continue
+            spend.daily[started.date()] = spend.daily.get(started.date(), 0.0) + float(m.group(1))
+            if spend.last_session_at is None or started >= spend.last_session_at:
+                spend.last_session_at = started
+                spend.last_session = float(m.group(2))
+
+
+def parse_analytics(path: Path, spend: AiderSpend) -> None:
+    """Add the cost of each `message_send` event in an `--analytics-log` to the day it was sent."""
+    with path.open(errors="ignore") as fh:
+        for line in fh:
+            try:
+                event = json.loads(line)
+                if event.get("event") != "message_send":
+                    continue
+                props = event.get("properties") or {}
+                sent = datetime.fromtimestamp(float(event["time"]))
+                cost = float(props.get("cost") or 0.0)
+            except (json.JSONDecodeError, AttributeError, KeyError, TypeError, ValueError, OSError):
+                continue
+            spend.daily[sent.date()] = spend.daily.get(sent.date(), 0.0) + cost
+            if spend.last_session_at is None or sent >= spend.last_session_at:
+                spend.last_session_at = sent
+                spend.last_session = float(props.get("total_cost") or cost)
+
+
+class AiderAdapter(ProviderAdapter):
+    """Aider spend summed from its analytics log or chat histories across repos.
+
+    With `analytics_log` (a path, or list of paths, given to aider's
+    `--analytics-log`) each reply's cost is booked to the day it was sent and
+    histories are not read. Otherwise aider's `Cost: $x message, $y session`
+    lines are read from each repo's `.aider.chat.history.md`: the files in
+    `history_files` when set, else those found under `search_roots`
+    (default: home) up to `search_depth`. That search is kept next to the
+    state file and repeated every `rescan_hours`. Today's total fills the
+    session slot, labeled daily, against `daily_budget`.
+    """
+
+    name = ProviderName.AIDER
+
+    def __init__(self, now: datetime | None = None) -> None:
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        logs = _paths(cfg.options.get("analytics_log"))
+        reader, paths = (parse_analytics, logs) if logs else (parse_history, self._histories(cfg))
+        spend = AiderSpend()
+        for path in paths:
+            try:
+                reader(path, spend)
+            except OSError:
+                continue
+            spend.files += 1
+        if not spend.files:
+            return PartialUsage(messages=[msg("missing_source_file", path=str(logs[0]) if logs else HISTORY_NAME)])
+
+        now = self.now or datetime.now()
+        today = spend.daily.get(now.date(), 0.0)
+        budget = option_float(cfg, "daily_budget") or None
+        pct = Percent.ratio(today, budget)
+        messages = [msg("spend_today_last_session", today=f"${today:,.2f}", session=f"${spend.last_session:,.2f}")]
+        if not budget:
+            messages.append(msg("limit_missing", option="daily_budget"))
+        return PartialUsage(
+            session_used_pct=pct,
+            session_reset_at=datetime(now.year, now.month, now.day) + timedelta(days=1),
+            details={
+                "session_window": "daily",
+                "today_spend": round(today, 4),
+                "last_session_spend": spend.last_session,
+                "daily_budget": budget,
+                "history_files": spend.files,
+            },
+            messages=messages,
+        )
+
+    def _histories(self, cfg: ProviderConfig) -> list[Path]:
+        explicit = _paths(cfg.options.get("history_files"))
+        if explicit:
+            return explicit
+        roots = [r for r in _paths(cfg.options.get("search_roots") or [str(home_dir())]) if r.is_dir()]
+        depth = int(option_float(cfg, "search_depth", 4))
+        rescan = timedelta(hours=option_float(cfg, "rescan_hours", DEFAULT_RESCAN_HOURS))
+        key = {"roots": [str(r) for r in roots], "depth": depth}
+        now = self.now or datetime.now()
+        cache = (self.state_dir or state_dir()) / "aider-histories.json"
+        try:
+            saved = json.loads(cache.read_text())
+            if {k: saved[k] for k in key} == key and now - datetime.fromisoformat(saved["scanned_at"]) < rescan:
+                return [Path(p) for p in saved["paths"]]
+        except (OSError, json.JSONDecodeError, KeyError, TypeError, ValueError):
+            pass
+        found = find_histories(roots, depth)
+        if not self.read_only:
+            try:
+                cache.parent.mkdir(parents=True, exist_ok=True)
+                cache.write_text(json.dumps({**key, "scanned_at": now.isoformat(), "paths": [str(p) for p in found]}))
+            except OSError:
+                pass
+        return found
+
+
+def _paths(raw: object) -> list[Path]:
+    items = [raw] if isinstance(raw, str) else raw if isinstance(raw, list) else []
+    return [Path(str(item)).expanduser() for item in items if item]
@@ -0,0 +1,156 @@
+from __future__ import annotations
+
+import json
+import os
+import sqlite3
+import sys
+from datetime import datetime, timezone
+from pathlib import Path
+from urllib.parse import urlencode
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.paths import home_dir, xdg_data_home
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_str,
+)
+
+DEFAULT_API_BASE = "https://q.us-east-1.amazonaws.com"
+TOKEN_KEY = "codewhisperer:odic:token"
+
+
+def q_data_db() -> Path:
+    """The Amazon Q CLI's own state database, which caches its Builder ID token."""
+    if sys.platform == "darwin":
+        return home_dir() / "Library/Application Support/amazon-q/data.sqlite3"
+    return xdg_data_home() / "amazon-q/data.sqlite3"
+
+
+class AmazonQAdapter(ProviderAdapter):
+    """Amazon Q Developer monthly request allotment from `GetUsageLimits`.
+
+    The bearer token comes from `token_env`, else the Q CLI's state
+    database, else the newest unexpired AWS SSO cache entry (what the IDE
+    toolkits write after a Builder ID or Identity Center sign-in). Agentic
+    requests fill the weekly slot, labeled monthly; pick another allotment
+    with `resource_type`.
+    """
+
+    name = ProviderName.AMAZON_Q
+
+    def __init__(
+        self,
+        q_db: Path | None = None,
+        sso_cache: Path | None = None,
+        fetch: FetchJson | None = None,
+    ) -> None:
+        self.q_db = q_db or q_data_db()
+        self.sso_cache = sso_cache or home_dir() / ".aws/sso/cache"
+        self.fetch = fetch
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self._parse(cfg), cfg)
+
+    def _token(self, cfg: ProviderConfig) -> str | None:
+        env_name = option_str(cfg, "token_env")
+        if env_name and os.environ.get(env_name):
+            return os.environ[env_name]
+        return _q_cli_token(self.q_db) or _sso_cache_token(self.sso_cache)
+
+    def _parse(self, cfg: ProviderConfig) -> PartialUsage:
+        token = self._token(cfg)
+        if token is None:
+            return PartialUsage(messages=[msg("credentials_missing", provider="Amazon Q", hint="token_env or sign in with `q login`")])
+        params = {"origin": option_str(cfg, "origin", "AI_EDITOR"), "resourceType": option_str(cfg, "resource_type", "AGENTIC_REQUEST")}
+        if option_str(cfg, "profile_arn"):
+            params["profileArn"] = option_str(cfg, "profile_arn")
+        api_base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        try:
+            raw = (self.fetch or fetch_json)(f"{api_base}/getUsageLimits?{urlencode(params)}", {"Authorization": f"Bearer {token}"})
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        if not isinstance(raw, dict):
+            return PartialUsage(messages=[msg("unexpected_response", provider="Amazon Q", detail="")])
+        return parse_usage_limits(raw, params["resourceType"])
+
+
+def _unexpired(expires: object, now: datetime) -> bool:
+    if not isinstance(expires, str):
+        return True
+    try:
+        at = datetime.fromisoformat(expires.replace("Z", "+00:00"))
+    except ValueError:
+        return True
+    if at.tzinfo is None:
+        at = at.replace(tzinfo=timezone.utc)
+    return at > now
+
+
+def _q_cli_token(db: Path) -> str | None:
+    if not db.exists():
+        return None
+    try:
+        conn = sqlite3.connect(f"file:{db}?mode=ro", uri=True)
+        try:
+            row = conn.execute("SELECT value FROM auth_kv WHERE key = ?", (TOKEN_KEY,)).fetchone()
+        finally:
+            conn.close()
+        entry = json.loads(row[0]) if row and row[0] else None
+    except (sqlite3.Error, json.JSONDecodeError):
+        return None
+    if not isinstance(entry, dict) or not entry.get("access_token"):
+        return None
+    if not _unexpired(entry.get("expires_at"), datetime.now(timezone.utc)):
+        return None
+    return str(entry["access_token"])
+
+
+def _sso_cache_token(cache: Path) -> str | None:
+    if not cache.is_dir():
+        return None
+    now = datetime.now(timezone.utc)
+    for path in sorted(cache.glob("*.json"), key=lambda p: p.stat().st_mtime, reverse=True):
+        try:
+            entry = json.loads(path.read_text())
+        except (OSError, json.JSONDecodeError):
+            continue
+        if isinstance(entry, dict) and entry.get("accessToken") and _unexpired(entry.get("expiresAt"), now):
+            return str(entry["accessToken"])
+    return None
+
+
+def _number(item: dict, key: str) -> float | None:
+    value = item.get(f"{key}WithPrecision", item.get(key))
+    return float(value) if isinstance(value, (int, float)) and not isinstance(value, bool) else None
+
+
+def parse_usage_limits(raw: dict, resource_type: str = "AGENTIC_REQUEST") -> PartialUsage:
+    breakdowns = [b for b in raw.get("usageBreakdownList") or [] if isinstance(b, dict)]
+    item = next((b for b in breakdowns if b.get("resourceType") == resource_type), breakdowns[0] if breakdowns else None)
+    if item is None:
+        return PartialUsage(messages=[msg("unexpected_response", provider="Amazon Q", detail=": no usage breakdown")])
+
+    used = _number(item, "currentUsage")
+    limit = _number(item, "usageLimit")
+    pct = Percent.ratio(used, limit)
+    reset = item.get("nextDateReset", raw.get("nextDateReset"))
+    reset_at = datetime.fromtimestamp(reset) if isinstance(reset, (int, float)) else None
+    return PartialUsage(
+        weekly_used_pct=pct,
+        weekly_reset_at=reset_at,
+        details={
+            "long_window": "monthly",
+            "resource_type": item.get("resourceType", resource_type),
+            "requests_used": used,
+            "request_limit": limit,
+        },
+    )
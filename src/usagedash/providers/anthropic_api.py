@@ -0,0 +1,75 @@
+from __future__ import annotations
+
+import os
+from datetime import date, datetime, timezone
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_float,
+    option_str,
+)
+from usagedash.providers.billing import month_start, spend_usage
+
+DEFAULT_API_BASE = "https://api.anthropic.com"
+DEFAULT_KEY_ENV = "ANTHROPIC_ADMIN_KEY"
+API_VERSION = "2023-06-01"
+
+
+class AnthropicApiAdapter(ProviderAdapter):
+    """Month-to-date organization spend from the Anthropic Admin API cost report.
+
+    Used directly by the Claude provider when `parser_mode = "api"`; needs an
+    admin key in `api_key_env` (default ANTHROPIC_ADMIN_KEY).
+    """
+
+    name = ProviderName.CLAUDE
+
+    def __init__(self, fetch: FetchJson | None = None, now: datetime | None = None) -> None:
+        self.fetch = fetch or fetch_json
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        key_env = option_str(cfg, "api_key_env", DEFAULT_KEY_ENV)
+        key = os.environ.get(key_env)
+        if not key:
+            return PartialUsage(messages=[msg("credentials_missing", provider="Anthropic", hint=f"{key_env} to an admin key")])
+
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        starting_at = month_start(now).strftime("%Y-%m-%dT%H:%M:%SZ")
+        url = f"{base}/v1/organizations/cost_report?starting_at={starting_at}&bucket_width=1d&limit=31"
+        headers = {"x-api-key": key, "anthropic-version": API_VERSION}
+        daily: dict[date, float] = {}
+        page: str | None = None
+        try:
+            while True:
+                raw = self.fetch(url + (f"&page={page}" if page else ""), headers)
+                if not isinstance(raw, dict):
+                    return PartialUsage(messages=[msg("unexpected_response", provider="Anthropic cost report", detail="")])
+                for bucket in raw.get("data", []):
+                    day = datetime.fromisoformat(str(bucket["starting_at"]).replace("Z", "+00:00")).date()
+                    for result in bucket.get("results", []):
+                        # Amounts are decimal strings in the currency's lowest unit (cents).
+                        daily[day] = daily.get(day, 0.0) + float(result.get("amount") or 0) / 100.0
+                page = raw.get("next_page") if raw.get("has_more") else None
+                if not page:
+                    break
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (KeyError, TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="Anthropic cost report", detail=f": {exc}")])
+
+        usage = spend_usage(daily, option_float(cfg, "monthly_budget") or None, now)
+        usage.as_of = now
+        return usage
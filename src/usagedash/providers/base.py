@@ -1,11 +1,18 @@
 from __future__ import annotations
 
+import json
+import urllib.error
+import urllib.request
 from abc import ABC, abstractmethod
+from collections.abc import Callable, Iterable
 from dataclasses import dataclass
 from datetime import datetime, timezone
+from pathlib import Path
 
 from usagedash.config import ProviderConfig
+from usagedash.messages import Diagnostic, as_diagnostic, dedupe, msg
 from usagedash.models import ProviderName, ProviderSnapshot, SourceKind, StatusKind
+from usagedash.ratelimit import allow_request
 
 
 @dataclass
@@ -15,21 +22,188 @@ class PartialUsage:
     weekly_used_pct: float | None = None
     weekly_reset_at: datetime | None = None
     details: dict[str, object] | None = None
-    messages: list[str] | None = None
+    messages: list[str | Diagnostic] | None = None
+    # When the source data was current; used to pick between two sources.
+    as_of: datetime | None = None
+
+
+# details key naming the window a slot measures, and its default.
+SLOT_WINDOWS = {"session": ("session_window", "session"), "weekly": ("long_window", "weekly")}
+
+
+def merge_partials(a: PartialUsage, b: PartialUsage) -> PartialUsage:
+    """Combine two sources field by field, preferring the fresher one's values.
+
+    A source without `as_of` counts as older than one with it. Sources only
+    mix within a slot when they measure the same window: otherwise the slot
+    stays `a`'s (month-to-date spend never replaces a weekly limit), and `b`
+    only fills it, with its window label, when `a` has nothing there.
+    """
+    b_newer = (b.as_of or datetime.min) > (a.as_of or datetime.min)
+    first, second = (b, a) if b_newer else (a, b)
+    details = {**(second.details or {}), **(first.details or {})}
+    merged = PartialUsage(
+        details=details,
+        messages=[*(a.messages or []), *(b.messages or [])],
+        as_of=max((p.as_of for p in (a, b) if p.as_of), default=None),
+    )
+    for slot, (key, default) in SLOT_WINDOWS.items():
+        used, reset = f"{slot}_used_pct", f"{slot}_reset_at"
+        a_window, b_window = ((p.details or {}).get(key, default) for p in (a, b))
+        if a_window == b_window:
+            order = (first, second)
+        else:
+            owner = a if getattr(a, used) is not None or getattr(a, reset) is not None else b
+            order = (owner, owner)
+            details[key] = a_window if owner is a else b_window
+        for name in (used, reset):
+            value = getattr(order[0], name)
+            setattr(merged, name, value if value is not None else getattr(order[1], name))
+    return merged
+
+
+class ProviderError(Exception):
+    """A remote source could not be read; the message ends up in the snapshot."""
+
+
+FetchJson = Callable[[str, dict[str, str]], object]
+
+
+def _check_budget(url: str) -> None:
+    key = allow_request()
+    if key is not None:
+        raise ProviderError(f"{url}: skipped, [rate_limits] budget for {key} is used up")
+
+
+def fetch_json(url: str, headers: dict[str, str], timeout: float = 10.0) -> object:
+    """GET `url` and decode JSON, mapping every failure to ProviderError."""
+    _check_budget(url)
+    req = urllib.request.Request(url, headers={"Accept": "application/json", **headers})
+    try:
+        with urllib.request.urlopen(req, timeout=timeout) as resp:
+            return json.loads(resp.read())
+    except urllib.error.HTTPError as exc:
+        raise ProviderError(f"{url}: HTTP {exc.code}") from exc
+    except (OSError, ValueError) as exc:
+        raise ProviderError(f"{url}: {exc}") from exc
+
+
+FetchHeaders = Callable[[str, dict[str, str], bytes | None], dict[str, str]]
+
+
+def fetch_headers(url: str, headers: dict[str, str], body: bytes | None = None, timeout: float = 10.0) -> dict[str, str]:
+    """Send a request (POST when `body` is set) and return the lower-cased response headers.
+
+    A 429 still carries rate-limit headers, so it is returned rather than raised.
+    """
+    _check_budget(url)
+    req = urllib.request.Request(url, data=body, headers={"Content-Type": "application/json", **headers})
+    try:
+        with urllib.request.urlopen(req, timeout=timeout) as resp:
+            return {k.lower(): v for k, v in resp.headers.items()}
+    except urllib.error.HTTPError as exc:
+        if exc.code == 429:
+            return {k.lower(): v for k, v in exc.headers.items()}
+        raise ProviderError(f"{url}: HTTP {exc.code}") from exc
+    except OSError as exc:
+        raise ProviderError(f"{url}: {exc}") from exc
+
+
+def context_gauge(used_tokens: float, window_tokens: float) -> dict[str, float]:
+    """`details["context"]`: how full the active conversation's context window is."""
+    return {
+        "used_tokens": int(used_tokens),
+        "window_tokens": int(window_tokens),
+        "used_pct": round(min(100.0, used_tokens / window_tokens * 100.0), 1) if window_tokens else 0.0,
+    }
+
+
+DEFAULT_ACTIVE_MINUTES = 10.0
+
+
+def activity_details(last_at: datetime, cfg: ProviderConfig, now: datetime | None = None) -> dict[str, object]:
+    """`details` keys saying whether an agent wrote its transcript recently.
+
+    A session counts as active while the latest write is within the
+    provider's `active_minutes` option; `last_activity_at` is local time.
+    """
+    now = now or datetime.now()
+    try:
+        window = float(cfg.options.get("active_minutes", DEFAULT_ACTIVE_MINUTES))
+    except (TypeError, ValueError):
+        window = DEFAULT_ACTIVE_MINUTES
+    return {
+        "last_activity_at": last_at.isoformat(timespec="seconds"),
+        "session_active": (now - last_at).total_seconds() <= window * 60.0,
+    }
+
+
+def latest_write(paths: Iterable[Path]) -> datetime | None:
+    """Local time of the most recent modification among `paths`."""
+    stamps = []
+    for path in paths:
+        try:
+            stamps.append(path.stat().st_mtime)
+        except OSError:
+            continue
+    return datetime.fromtimestamp(max(stamps)) if stamps else None
+
+
+def option_str(cfg: ProviderConfig, key: str, default: str = "") -> str:
+    value = cfg.options.get(key, default)
+    return str(value) if value is not None else default
+
+
+class OptionError(Exception):
+    """An option holds a value the adapter cannot use; the row reports `invalid_option`."""
+
+    def __init__(self, key: str, value: object) -> None:
+        super().__init__(f"{key} must be a number, got {value!r}")
+        self.key = key
+        self.value = value
+
+    def diagnostic(self, provider: str) -> Diagnostic:
+        return msg("invalid_option", provider=provider, key=self.key, value=repr(self.value))
+
+
+def option_float(cfg: ProviderConfig, key: str, default: float | None = None) -> float | None:
+    """`cfg.options[key]` as a number; unset or empty gives `default`, anything else raises OptionError."""
+    value = cfg.options.get(key)
+    if value is None or value == "":
+        return default
+    if isinstance(value, bool):
+        raise OptionError(key, value)
+    try:
+        return float(value)
+    except (TypeError, ValueError) as exc:
+        raise OptionError(key, value) from exc
 
 
 class ProviderAdapter(ABC):
     name: ProviderName
+    # Set by build_snapshot from [general]: adapters that keep their own state
+    # put it in state_dir, next to the state file, and never write it read-only.
+    state_dir: Path | None = None
+    read_only: bool = False
+    # False during a dry run: adapters that execute user commands skip them.
+    run_commands: bool = True
+    # Built on an endpoint or payload no public docs or captured response
+    # confirm yet; capabilities reports it so frontends can say so.
+    experimental: bool = False
 
     @abstractmethod
     def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
         raise NotImplementedError
 
+    def collect_all(self, cfg: ProviderConfig) -> list[ProviderSnapshot]:
+        """One row per configured account; adapters without accounts return one."""
+        return [self.collect(cfg)]
+
 
 def merge_usage(name: ProviderName, partial: PartialUsage | None, cfg: ProviderConfig) -> ProviderSnapshot:
     now = datetime.now(timezone.utc).replace(tzinfo=None)
     parsed = partial or PartialUsage(messages=[])
-    messages = list(parsed.messages or [])
+    diagnostics = [as_diagnostic(m) for m in parsed.messages or []]
 
     session_used = parsed.session_used_pct if parsed.session_used_pct is not None else cfg.manual.session_used_pct
     session_reset = parsed.session_reset_at or cfg.manual.session_reset_at
@@ -55,12 +229,12 @@ def merge_usage(name: ProviderName, partial: PartialUsage | None, cfg: ProviderC
             status = StatusKind.OK
         else:
             status = StatusKind.PARTIAL
-            messages.append("usage detected but reset timestamps missing")
+            diagnostics.append(msg("reset_missing"))
     elif parsed_any or manual_any:
         status = StatusKind.PARTIAL
     else:
         status = StatusKind.ERROR
-        messages.append("no usage metrics detected; configure providers.<name>.manual.*")
+        diagnostics.append(msg("no_usage_metrics", provider=name.value))
 
     return ProviderSnapshot(
         provider=name,
@@ -70,7 +244,8 @@ def merge_usage(name: ProviderName, partial: PartialUsage | None, cfg: ProviderC
         weekly_used_pct=weekly_used,
         weekly_reset_at=weekly_reset,
         source=source,
-        messages=messages,
+        messages=[d.render() for d in dedupe(diagnostics)],
+        diagnostics=dedupe(diagnostics),
         details=parsed.details or {},
         updated_at=now,
     )
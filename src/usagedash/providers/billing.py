@@ -0,0 +1,60 @@
+from __future__ import annotations
+
+from datetime import date, datetime
+
+from usagedash.messages import Diagnostic, msg
+from usagedash.providers.base import PartialUsage
+
+# Per-day lines kept in messages; older days are still counted in the total.
+DAILY_MESSAGES = 7
+
+
+def month_start(now: datetime) -> datetime:
+    return datetime(now.year, now.month, 1)
+
+
+def next_month_start(now: datetime) -> datetime:
+    if now.month == 12:
+        return datetime(now.year + 1, 1, 1)
+    return datetime(now.year, now.month + 1, 1)
+
+
+def spend_usage(
+    daily: dict[date, float],
+    budget: float | None,
+    now: datetime,
+    currency: str = "usd",
+    daily_tokens: dict[date, float] | None = None,
+    token_cap: float | None = None,
+) -> PartialUsage:
+    """Month-to-date spend against a monthly budget, in the weekly slot labeled monthly.
+
+    Providers that report tokens can be capped by `token_cap` instead; a
+    cost budget wins when both are configured.
+    """
+    total = sum(daily.values())
+    tokens = sum((daily_tokens or {}).values())
+    pct = None
+    if budget:
+        pct = total / budget * 100.0
+    elif token_cap:
+        pct = tokens / token_cap * 100.0
+    symbol = "$" if currency.lower() == "usd" else f"{currency.upper()} "
+    messages: list[str | Diagnostic] = [
+        msg("daily_spend", day=day.isoformat(), amount=f"{symbol}{amount:,.2f}")
+        for day, amount in sorted(daily.items())[-DAILY_MESSAGES:]
+    ]
+    if not budget and not token_cap:
+        messages.append(msg("budget_missing"))
+    return PartialUsage(
+        weekly_used_pct=pct,
+        weekly_reset_at=next_month_start(now),
+        details={
+            "long_window": "monthly",
+            "month_to_date_spend": round(total, 4),
+            "monthly_budget": budget,
+            "currency": currency,
+            **({"month_to_date_tokens": tokens, "monthly_token_cap": token_cap} if daily_tokens else {}),
+        },
+        messages=messages,
+    )
@@ -1,33 +1,80 @@
 from __future__ import annotations
 
-from datetime import datetime, timedelta
+from datetime import datetime, timedelta, timezone
 from pathlib import Path
 import json
 import math
 from statistics import quantiles
 
 from usagedash.config import ProviderConfig
+from usagedash.paths import home_dir
+from usagedash.messages import Diagnostic, msg
 from usagedash.models import ProviderName, ProviderSnapshot
-from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage
+from usagedash.providers.anthropic_api import AnthropicApiAdapter
+from usagedash.providers.claude_oauth import ClaudeOAuthUsage
+from usagedash.providers.pricing import ModelPrice, price_for, price_table, usage_cost
+from usagedash.providers.base import (
+    PartialUsage,
+    ProviderAdapter,
+    activity_details,
+    context_gauge,
+    latest_write,
+    merge_partials,
+    merge_usage,
+    option_float,
+)
 
 DEFAULT_SESSION_TOKEN_LIMIT = 300_000.0
 DEFAULT_WEEKLY_TOKEN_LIMIT = 3_000_000.0
+DEFAULT_CONTEXT_WINDOW = 200_000
+LONG_CONTEXT_WINDOW = 1_000_000
 
 
 class ClaudeAdapter(ProviderAdapter):
     name = ProviderName.CLAUDE
 
-    def __init__(self, stats_path: Path | None = None, projects_path: Path | None = None) -> None:
-        self.stats_path = stats_path or Path.home() / ".claude/stats-cache.json"
-        self.projects_path = projects_path or Path.home() / ".claude/projects"
+    def __init__(
+        self,
+        stats_path: Path | None = None,
+        projects_path: Path | None = None,
+        api: AnthropicApiAdapter | None = None,
+        oauth: ClaudeOAuthUsage | None = None,
+    ) -> None:
+        self.stats_path = stats_path or home_dir() / ".claude/stats-cache.json"
+        self.projects_path = projects_path or home_dir() / ".claude/projects"
+        self.api = api or AnthropicApiAdapter()
+        self.oauth = oauth or ClaudeOAuthUsage()
+        # From `context_window`; None infers it from the context's size.
+        self.context_window: float | None = None
 
     def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
-        partial = self._parse()
-        return merge_usage(self.name, partial, cfg)
-
-    def _parse(self) -> PartialUsage:
+        prices = price_table(cfg.options.get("pricing"))
+        self.context_window = option_float(cfg, "context_window") or None
+        if cfg.parser_mode == "transcripts":
+            # Skip stats-cache.json entirely: everything comes from the transcripts.
+            partial = self._parse_from_projects(prices)
+        else:
+            partial = self._parse(prices)
+        if cfg.parser_mode == "api":
+            # Subscription limits straight from Anthropic when signed in with
+            # OAuth; the admin cost report covers API-key organizations.
+            oauth = self.oauth.parse(cfg)
+            if oauth is not None and (oauth.session_used_pct is not None or oauth.weekly_used_pct is not None):
+                partial = merge_partials(partial, oauth)
+            else:
+                partial = merge_partials(partial, self.api.parse(cfg))
+                if oauth is not None:
+                    partial = merge_partials(partial, oauth)
+        snap = merge_usage(self.name, partial, cfg)
+        # Subagent transcripts count: a parent waiting on them is still working.
+        last_at = latest_write(self.projects_path.rglob("*.jsonl")) if self.projects_path.exists() else None
+        if last_at is not None:
+            snap.details = {**snap.details, **activity_details(last_at, cfg)}
+        return snap
+
+    def _parse(self, prices: list[tuple[str, ModelPrice]] | None = None) -> PartialUsage:
         if not self.stats_path.exists():
-            return PartialUsage(messages=[f"missing {self.stats_path}"])
+            return PartialUsage(messages=[msg("missing_source_file", path=self.stats_path)])
 
         data = json.loads(self.stats_path.read_text(errors="ignore"))
         session_used = _pick_float(data, [
@@ -51,13 +98,19 @@ class ClaudeAdapter(ProviderAdapter):
             ("weekly", "reset_at"),
             ("weekly_reset_at",),
         ])
+        # Opus has its own, smaller weekly cap on some plans.
+        opus_weekly_used = _pick_float(data, [
+            ("limits", "weekly_opus", "percent_used"),
+            ("weekly_opus", "percent_used"),
+            ("weekly_opus_percent_used",),
+        ])
 
-        messages: list[str] = []
+        messages: list[str | Diagnostic] = []
         details: dict[str, object] | None = None
 
         # Always attempt project-log analysis — it provides richer metrics
         # (burn rate, P90 limits, model distribution) than stats-cache alone.
-        project_partial = self._parse_from_projects()
+        project_partial = self._parse_from_projects(prices)
         if project_partial.details:
             details = project_partial.details
         messages.extend(project_partial.messages or [])
@@ -72,7 +125,9 @@ class ClaudeAdapter(ProviderAdapter):
         weekly_reset = project_partial.weekly_reset_at or weekly_reset
 
         if session_used is None and weekly_used is None:
-            messages.append("unable to infer Claude usage from stats-cache.json or project logs")
+            messages.append(msg("usage_not_inferred", provider="Claude", sources="stats-cache.json or project logs"))
+        if opus_weekly_used is not None:
+            details = {**(details or {}), "opus_weekly_used_pct": opus_weekly_used}
 
         return PartialUsage(
             session_used_pct=session_used,
@@ -81,11 +136,12 @@ class ClaudeAdapter(ProviderAdapter):
             weekly_reset_at=weekly_reset,
             details=details,
             messages=messages,
+            as_of=datetime.fromtimestamp(self.stats_path.stat().st_mtime, timezone.utc).replace(tzinfo=None),
         )
 
-    def _parse_from_projects(self) -> PartialUsage:
+    def _parse_from_projects(self, prices: list[tuple[str, ModelPrice]] | None = None) -> PartialUsage:
         if not self.projects_path.exists():
-            return PartialUsage(messages=[f"missing {self.projects_path}"])
+            return PartialUsage(messages=[msg("missing_source_file", path=self.projects_path)])
 
         now = datetime.now()
         five_hours_ago = now - timedelta(hours=5)
@@ -97,51 +153,39 @@ class ClaudeAdapter(ProviderAdapter):
 
         # Consider top-level session files (exclude subagents) and pick the
         # most active one in the last 5h as "current session".
-        candidates = [
-            p for p in self.projects_path.rglob("*.jsonl")
-            if "/subagents/" not in str(p).replace("\\", "/")
-        ]
+        all_files = list(self.projects_path.rglob("*.jsonl"))
+        candidates = [p for p in all_files if not _is_subagent_file(p)]
         if not candidates:
-            return PartialUsage(messages=["no Claude session files found in ~/.claude/projects"])
+            return PartialUsage(messages=[msg("no_session_files", provider="Claude", path=self.projects_path)])
 
         seen_ids: set[str] = set()
+        costs = CostTally(prices or price_table(), now)
         per_file_entries: dict[Path, list[tuple[datetime, float, str]]] = {p: [] for p in candidates}
+        weekly_opus_tokens = 0.0
 
         for jsonl_path in candidates:
-            try:
-                with jsonl_path.open("r", encoding="utf-8", errors="ignore") as fh:
-                    for line in fh:
-                        if "\"usage\"" not in line:
-                            continue
-                        try:
-                            obj = json.loads(line)
-                        except json.JSONDecodeError:
-                            continue
-                        ts = _parse_ts(obj.get("timestamp"))
-                        if ts is None:
-                            continue
-                        if not _is_primary_assistant_usage_entry(obj):
-                            continue
-                        msg = obj.get("message") or {}
-                        usage = msg.get("usage") or {}
-                        entry_id = _entry_identity(obj)
-                        if not entry_id or entry_id in seen_ids:
-                            continue
-                        seen_ids.add(entry_id)
-
-                        tokens = _usage_total_tokens(usage)
-                        if tokens <= 0:
-                            continue
-                        model = msg.get("model", "unknown")
-                        historical_entries.append((ts, tokens, model))
-                        per_file_entries[jsonl_path].append((ts, tokens, model))
-                        if ts >= seven_days_ago:
-                            weekly_tokens += tokens
-            except OSError:
+            for ts, tokens, model in _read_usage_entries(jsonl_path, seen_ids, costs):
+                historical_entries.append((ts, tokens, model))
+                per_file_entries[jsonl_path].append((ts, tokens, model))
+                if ts >= seven_days_ago:
+                    weekly_tokens += tokens
+                    if model_family(model) == "opus":
+                        weekly_opus_tokens += tokens
+
+        # Subagent turns are kept out of the session limits above but count
+        # toward the per-model split of the session that spawned them.
+        subagent_entries: dict[Path, list[tuple[datetime, float, str]]] = {}
+        for jsonl_path in all_files:
+            if not _is_subagent_file(jsonl_path):
                 continue
+            entries = _read_usage_entries(jsonl_path, seen_ids, costs)
+            subagent_entries.setdefault(_subagent_parent(jsonl_path), []).extend(entries)
+            weekly_opus_tokens += sum(
+                t for ts, t, m in entries if ts >= seven_days_ago and model_family(m) == "opus"
+            )
 
         if not historical_entries:
-            return PartialUsage(messages=["no usage tokens found in project logs"])
+            return PartialUsage(messages=[msg("no_usage_tokens")])
 
         historical_entries.sort(key=lambda x: x[0])
         session_file = _pick_active_session_file(per_file_entries, five_hours_ago)
@@ -192,6 +236,8 @@ class ClaudeAdapter(ProviderAdapter):
         model_distribution = {
             k: round((v / session_tokens * 100.0), 1) for k, v in model_tokens.items()
         } if session_tokens > 0 else {}
+        session_subagents = [e for e in subagent_entries.get(session_file, []) if e[0] >= five_hours_ago]
+        split = model_split(current_entries, session_subagents)
 
         details: dict[str, object] = {
             "dynamic_limits": {
@@ -206,12 +252,21 @@ class ClaudeAdapter(ProviderAdapter):
                 "predicted_tokens_runout_at": predicted_runout.isoformat() if predicted_runout else None,
                 "session_reset_at": session_reset_at.isoformat(),
                 "model_distribution": model_distribution,
+                "model_split": split,
+                "subagent_tokens": int(sum(e[1] for e in session_subagents)),
+                "weekly_opus_tokens": int(weekly_opus_tokens),
             }
         }
 
-        messages = [f"derived Claude metrics from current session file: {session_file.name}"]
+        details["costs"] = costs.summary()
+
+        context = _last_context(session_file, self.context_window)
+        if context is not None and current_entries:
+            details["context"] = context_gauge(*context)
+
+        messages: list[str | Diagnostic] = [msg("derived_from", provider="Claude", file=session_file.name)]
         if session_tokens <= 0 and weekly_tokens <= 0:
-            messages.append("no usage tokens found in project logs")
+            messages.append(msg("no_usage_tokens"))
             return PartialUsage(messages=messages)
 
         return PartialUsage(
@@ -276,6 +331,164 @@ def _usage_total_tokens(usage: dict) -> float:
     return total
 
 
+def model_family(model: str) -> str:
+    """`opus`, `sonnet`, or `haiku` for Claude model ids; the id itself otherwise."""
+    lowered = model.lower()
+    for family in ("opus", "sonnet", "haiku"):
+        if family in lowered:
+            return family
+    return model
+
+
+def model_split(
+    turns: list[tuple[datetime, float, str]],
+    subagent_turns: list[tuple[datetime, float, str]],
+) -> dict[str, dict[str, float]]:
+    """Tokens per model family across a session's own and subagent turns.
+
+    Each family gets `tokens`, `subagent_tokens`, and `pct` of the combined
+    total, largest first.
+    """
+    split: dict[str, dict[str, float]] = {}
+    for entries, key in ((turns, "tokens"), (subagent_turns, "subagent_tokens")):
+        for _, tokens, model in entries:
+            bucket = split.setdefault(model_family(model), {"tokens": 0.0, "subagent_tokens": 0.0})
+            bucket["tokens"] += tokens
+            if key == "subagent_tokens":
+                bucket["subagent_tokens"] += tokens
+    total = sum(b["tokens"] for b in split.values())
+    for bucket in split.values():
+        bucket["pct"] = round(bucket["tokens"] / total * 100.0, 1) if total else 0.0
+    return dict(sorted(split.items(), key=lambda kv: kv[1]["tokens"], reverse=True))
+
+
+def _last_context(path: Path, window: float | None = None) -> tuple[float, float] | None:
+    """(tokens in context, window size) as of the session's latest assistant turn.
+
+    Everything sent with that turn, cached or not, plus its output is what
+    the next turn starts from. Transcripts record the plain model id even
+    with the 1M-context beta on, so without a configured `window` the 1M
+    window is assumed only once the context has outgrown the default one.
+    """
+    last: dict | None = None
+    try:
+        with path.open("r", encoding="utf-8", errors="ignore") as fh:
+            for line in fh:
+                if "\"usage\"" not in line:
+                    continue
+                try:
+                    obj = json.loads(line)
+                except json.JSONDecodeError:
+                    continue
+                if _is_primary_assistant_usage_entry(obj) and not obj.get("isSidechain"):
+                    last = obj["message"]
+    except OSError:
+        return None
+    if last is None:
+        return None
+    usage = last["usage"]
+    used = sum(
+        float(usage.get(k) or 0)
+        for k in ("input_tokens", "cache_read_input_tokens", "cache_creation_input_tokens", "output_tokens")
+    )
+    if window is None:
+        window = LONG_CONTEXT_WINDOW if used > DEFAULT_CONTEXT_WINDOW else DEFAULT_CONTEXT_WINDOW
+    return used, float(window)
+
+
+def _is_subagent_file(path: Path) -> bool:
+    return "/subagents/" in str(path).replace("\\", "/")
+
+
+def _subagent_parent(path: Path) -> Path:
+    # <project>/<session-id>/subagents/agent-*.jsonl belongs to <project>/<session-id>.jsonl
+    session_dir = path.parent.parent
+    return session_dir.parent / f"{session_dir.name}.jsonl"
+
+
+class CostTally:
+    """Token counts by kind and estimated cost per model over the last seven days."""
+
+    KINDS = ("input_tokens", "output_tokens", "cache_creation_input_tokens", "cache_read_input_tokens")
+
+    def __init__(self, prices: list[tuple[str, ModelPrice]], now: datetime) -> None:
+        self.prices = prices
+        self.day_start = datetime(now.year, now.month, now.day)
+        self.week_start = now - timedelta(days=7)
+        self.models: dict[str, dict[str, float]] = {}
+        self.today_usd = 0.0
+        self.unpriced: set[str] = set()
+
+    def add(self, ts: datetime, model: str, usage: dict) -> None:
+        if ts < self.week_start or not any(usage.get(kind) for kind in self.KINDS):
+            return
+        bucket = self.models.setdefault(model, {kind: 0.0 for kind in (*self.KINDS, "cost_usd")})
+        for kind in self.KINDS:
+            value = usage.get(kind)
+            if isinstance(value, (int, float)):
+                bucket[kind] += float(value)
+        price = price_for(model, self.prices)
+        if price is None:
+            self.unpriced.add(model)
+            return
+        cost = usage_cost(usage, price)
+        bucket["cost_usd"] += cost
+        if ts >= self.day_start:
+            self.today_usd += cost
+
+    def summary(self) -> dict[str, object]:
+        models = {
+            model: {k: (round(v, 4) if k == "cost_usd" else int(v)) for k, v in bucket.items()}
+            for model, bucket in sorted(self.models.items(), key=lambda kv: kv[1]["cost_usd"], reverse=True)
+        }
+        return {
+            "today_usd": round(self.today_usd, 4),
+            "week_usd": round(sum(b["cost_usd"] for b in self.models.values()), 4),
+            "models": models,
+            "unpriced_models": sorted(self.unpriced),
+        }
+
+
+def _read_usage_entries(
+    path: Path, seen_ids: set[str], costs: CostTally | None = None
+) -> list[tuple[datetime, float, str]]:
+    """(timestamp, tokens, model) for each assistant turn not already in `seen_ids`.
+
+    Every such turn also goes into `costs`, even one with only cache tokens.
+    """
+    entries: list[tuple[datetime, float, str]] = []
+    try:
+        with path.open("r", encoding="utf-8", errors="ignore") as fh:
+            for line in fh:
+                if "\"usage\"" not in line:
+                    continue
+                try:
+                    obj = json.loads(line)
+                except json.JSONDecodeError:
+                    continue
+                ts = _parse_ts(obj.get("timestamp"))
+                if ts is None:
+                    continue
+                if not _is_primary_assistant_usage_entry(obj):
+                    continue
+                message = obj.get("message") or {}
+                usage = message.get("usage") or {}
+                entry_id = _entry_identity(obj)
+                if not entry_id or entry_id in seen_ids:
+                    continue
+                seen_ids.add(entry_id)
+                if costs is not None:
+                    costs.add(ts, str(message.get("model", "unknown")), usage)
+
+                tokens = _usage_total_tokens(usage)
+                if tokens <= 0:
+                    continue
+                entries.append((ts, tokens, message.get("model", "unknown")))
+    except OSError:
+        pass
+    return entries
+
+
 def _is_primary_assistant_usage_entry(obj: dict) -> bool:
     if not isinstance(obj, dict):
         return False
@@ -0,0 +1,96 @@
+from __future__ import annotations
+
+import json
+from datetime import datetime, timezone
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.paths import home_dir
+from usagedash.providers.base import FetchJson, PartialUsage, ProviderError, fetch_json, option_str
+
+DEFAULT_API_BASE = "https://api.anthropic.com"
+OAUTH_BETA = "oauth-2025-04-20"
+
+
+def read_oauth_token(path: Path, now: datetime | None = None) -> str | None:
+    """The Claude Code OAuth access token, unless missing or expired.
+
+    Claude Code keeps it in `~/.claude/.credentials.json` on Linux and
+    Windows (macOS uses the keychain; point `credentials_path` at an export).
+    """
+    try:
+        raw = json.loads(path.read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return None
+    entry = raw.get("claudeAiOauth") if isinstance(raw, dict) else None
+    if not isinstance(entry, dict) or not entry.get("accessToken"):
+        return None
+    expires_ms = entry.get("expiresAt")
+    if isinstance(expires_ms, (int, float)):
+        now = now or datetime.now(timezone.utc)
+        if datetime.fromtimestamp(expires_ms / 1000, timezone.utc) <= now:
+            return None
+    return str(entry["accessToken"])
+
+
+def _window(raw: dict, key: str) -> tuple[float | None, datetime | None]:
+    window = raw.get(key)
+    if not isinstance(window, dict):
+        return None, None
+    used = window.get("utilization")
+    reset = window.get("resets_at")
+    reset_at = None
+    if isinstance(reset, str) and reset:
+        reset_at = datetime.fromisoformat(reset.replace("Z", "+00:00")).astimezone().replace(tzinfo=None)
+    return (float(used) if isinstance(used, (int, float)) else None), reset_at
+
+
+def parse_oauth_usage(raw: object) -> PartialUsage:
+    """Map the `/api/oauth/usage` body: `five_hour` and `seven_day` utilization in percent."""
+    if not isinstance(raw, dict):
+        raise ValueError("expected an object")
+    session_pct, session_reset = _window(raw, "five_hour")
+    weekly_pct, weekly_reset = _window(raw, "seven_day")
+    details: dict[str, object] = {"limits_source": "oauth"}
+    for family in ("opus", "sonnet"):
+        pct, reset = _window(raw, f"seven_day_{family}")
+        if pct is not None:
+            # Same key the stats-cache path writes, so either source renders.
+            details[f"{family}_weekly_used_pct"] = pct
+    return PartialUsage(
+        session_used_pct=session_pct,
+        session_reset_at=session_reset,
+        weekly_used_pct=weekly_pct,
+        weekly_reset_at=weekly_reset,
+        details=details,
+    )
+
+
+class ClaudeOAuthUsage:
+    """Exact 5-hour and weekly limit status for a Claude subscription.
+
+    Used by the Claude provider when `parser_mode = "api"` and a valid OAuth
+    token is on disk; the result overrides the local stats-cache estimate.
+    """
+
+    def __init__(self, credentials_path: Path | None = None, fetch: FetchJson | None = None) -> None:
+        self.credentials_path = credentials_path or home_dir() / ".claude/.credentials.json"
+        self.fetch = fetch
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage | None:
+        """None when there is no usable token, so callers fall back silently."""
+        path = Path(option_str(cfg, "credentials_path")).expanduser() if option_str(cfg, "credentials_path") else self.credentials_path
+        token = read_oauth_token(path)
+        if token is None:
+            return None
+        base = option_str(cfg, "oauth_api_base", DEFAULT_API_BASE).rstrip("/")
+        headers = {"Authorization": f"Bearer {token}", "anthropic-beta": OAUTH_BETA}
+        try:
+            usage = parse_oauth_usage((self.fetch or fetch_json)(f"{base}/api/oauth/usage", headers))
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="Claude usage", detail=f": {exc}")])
+        usage.as_of = datetime.now(timezone.utc).replace(tzinfo=None)
+        return usage
@@ -0,0 +1,135 @@
+from __future__ import annotations
+
+import json
+import os
+from dataclasses import dataclass, field
+from datetime import date, datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.paths import home_dir
+from usagedash.processes import is_wsl
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage
+from usagedash.providers.vscdb import state_db_path
+
+# globalStorage folder names of the extensions that share Cline's task format.
+EXTENSIONS = {"saoudrizwan.claude-dev": "Cline", "rooveterinaryinc.roo-cline": "Roo Code"}
+EDITORS = ("Code", "Code - Insiders", "Cursor", "Windsurf", "VSCodium")
+WEEK_DAYS = 7
+
+
+@dataclass
+class ClineSpend:
+    daily: dict[date, float] = field(default_factory=dict)
+    tokens: dict[date, int] = field(default_factory=dict)
+    by_extension: dict[str, float] = field(default_factory=dict)
+    tasks: int = 0
+
+    def add(self, day: date, cost: float, tokens: int, extension: str) -> None:
+        self.daily[day] = self.daily.get(day, 0.0) + cost
+        self.tokens[day] = self.tokens.get(day, 0) + tokens
+        self.by_extension[extension] = self.by_extension.get(extension, 0.0) + cost
+
+
+def storage_roots() -> list[Path]:
+    """globalStorage directories of every local editor, the VS Code server, and,
+    under WSL, each Windows user's editors on the mounted C: drive."""
+    roots = [state_db_path(app).parent for app in EDITORS]
+    roots.append(home_dir() / ".vscode-server/data/User/globalStorage")
+    if os.name != "nt" and is_wsl():
+        for user in Path("/mnt/c/Users").glob("*"):
+            roots.extend(user / "AppData/Roaming" / app / "User/globalStorage" for app in EDITORS)
+    return [r for r in roots if r.is_dir()]
+
+
+def parse_task(path: Path, since: datetime, spend: ClineSpend, extension: str) -> None:
+    """Add every `api_req_started` cost in one task's `ui_messages.json` newer than `since`."""
+    messages = json.loads(path.read_text(encoding="utf-8", errors="ignore"))
+    if not isinstance(messages, list):
+        return
+    for item in messages:
+        if not isinstance(item, dict) or item.get("say") != "api_req_started" or not isinstance(item.get("ts"), (int, float)):
+            continue
+        at = datetime.fromtimestamp(item["ts"] / 1000)
+        if at < since:
+            continue
+        try:
+            info = json.loads(item.get("text") or "{}")
+        except json.JSONDecodeError:
+            continue
+        cost = info.get("cost")
+        if not isinstance(cost, (int, float)):
+            continue
+        tokens = sum(int(info.get(k) or 0) for k in ("tokensIn", "tokensOut", "cacheWrites", "cacheReads"))
+        spend.add(at.date(), float(cost), tokens, extension)
+
+
+class ClineAdapter(ProviderAdapter):
+    """Cline and Roo Code spend from the per-task logs in VS Code's globalStorage.
+
+    Each API request is logged with its cost in `tasks/<id>/ui_messages.json`.
+    Today's spend fills the session slot, labeled daily, against
+    `daily_budget`; the last seven days fill the weekly slot against
+    `weekly_budget`. `storage_dirs` replaces the searched globalStorage
+    directories.
+    """
+
+    name = ProviderName.CLINE
+
+    def __init__(self, roots: list[Path] | None = None, now: datetime | None = None) -> None:
+        self.roots = roots
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        configured = cfg.options.get("storage_dirs")
+        roots = [Path(str(r)).expanduser() for r in configured] if configured else self.roots or storage_roots()
+        now = self.now or datetime.now()
+        today = datetime(now.year, now.month, now.day)
+        since = today - timedelta(days=WEEK_DAYS - 1)
+        spend = ClineSpend()
+        found = False
+        for root in roots:
+            for folder, extension in EXTENSIONS.items():
+                tasks = root / folder / "tasks"
+                if not tasks.is_dir():
+                    continue
+                found = True
+                for path in tasks.glob("*/ui_messages.json"):
+                    try:
+                        if datetime.fromtimestamp(path.stat().st_mtime) < since:
+                            continue  # untouched since before the window: nothing to count
+                        parse_task(path, since, spend, extension)
+                    except (OSError, ValueError):
+                        continue
+                    spend.tasks += 1
+        if not found:
+            return PartialUsage(messages=[msg("missing_source_file", path="globalStorage/saoudrizwan.claude-dev/tasks")])
+
+        today_spend = spend.daily.get(today.date(), 0.0)
+        week_spend = sum(spend.daily.values())
+        daily_budget = cfg.options.get("daily_budget")
+        weekly_budget = cfg.options.get("weekly_budget")
+        messages = [msg("spend_today_week", today=f"${today_spend:,.2f}", week=f"${week_spend:,.2f}")]
+        if not daily_budget and not weekly_budget:
+            messages.append(msg("limit_missing", option="daily_budget or weekly_budget"))
+        return PartialUsage(
+            session_used_pct=Percent.ratio(today_spend, float(daily_budget) if daily_budget else None),
+            session_reset_at=today + timedelta(days=1),
+            weekly_used_pct=Percent.ratio(week_spend, float(weekly_budget) if weekly_budget else None),
+            details={
+                "session_window": "daily",
+                "today_spend": round(today_spend, 4),
+                "week_spend": round(week_spend, 4),
+                "today_tokens": spend.tokens.get(today.date(), 0),
+                "by_extension": {k: round(v, 4) for k, v in spend.by_extension.items()},
+                "tasks": spend.tasks,
+            },
+            messages=messages,
+        )
@@ -0,0 +1,162 @@
+from __future__ import annotations
+
+import urllib.parse
+from datetime import datetime, timedelta, timezone
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_str,
+)
+from usagedash.providers.gcloud import AccessToken, adc_access_token, adc_project
+
+SERVICE_USAGE = "https://serviceusage.googleapis.com/v1beta1"
+MONITORING = "https://monitoring.googleapis.com/v3"
+USAGE_METRIC = "serviceruntime.googleapis.com/quota/rate/net_usage"
+
+# Service Usage unit fragments for the two windows shown.
+_WINDOWS = {"minute": "/min/", "day": "/d/"}
+
+
+def quota_limits(raw: object) -> dict[str, dict[str, float]]:
+    """`{window: {quota metric: limit}}` from a consumerQuotaMetrics listing.
+
+    Uses each limit's default bucket (no dimensions) when present, since that
+    is what applies to models without a per-model override.
+    """
+    if not isinstance(raw, dict):
+        raise ValueError("expected an object")
+    limits: dict[str, dict[str, float]] = {w: {} for w in _WINDOWS}
+    for metric in raw.get("metrics", []):
+        name = str(metric.get("metric", ""))
+        for limit in metric.get("consumerQuotaLimits", []):
+            unit = str(limit.get("unit", ""))
+            window = next((w for w, frag in _WINDOWS.items() if frag in unit), None)
+            if window is None:
+                continue
+            buckets = [b for b in limit.get("quotaBuckets", []) if "effectiveLimit" in b]
+            default = [b for b in buckets if not b.get("dimensions")] or buckets
+            values = [float(b["effectiveLimit"]) for b in default if float(b["effectiveLimit"]) > 0]
+            if values:
+                limits[window][name] = max(values)
+    return limits
+
+
+def quota_usage(raw: object) -> dict[str, float]:
+    """Peak usage per quota metric from a Cloud Monitoring timeSeries listing."""
+    usage: dict[str, float] = {}
+    series = raw.get("timeSeries", []) if isinstance(raw, dict) else []
+    for item in series:
+        name = str(item.get("metric", {}).get("labels", {}).get("quota_metric", ""))
+        for point in item.get("points", []):
+            value = point.get("value", {})
+            number = float(value.get("int64Value", value.get("doubleValue", 0)) or 0)
+            usage[name] = max(usage.get(name, 0.0), number)
+    return usage
+
+
+class CloudQuotaAdapter(ProviderAdapter):
+    """Google Cloud quota consumption as percent of per-minute and per-day limits.
+
+    Limits come from the Service Usage API and consumption from the quota
+    usage metric in Cloud Monitoring, both with application default
+    credentials. The busiest per-minute quota fills the session slot and the
+    busiest per-day quota the long slot; `minute_match`/`day_match` narrow
+    each window to quota metrics whose name contains that text.
+    """
+
+    title: str
+    service: str
+    minute_match = ""
+    day_match = ""
+
+    def __init__(
+        self,
+        fetch: FetchJson | None = None,
+        token: AccessToken | None = None,
+        now: datetime | None = None,
+    ) -> None:
+        self.fetch = fetch or fetch_json
+        self.token = token or adc_access_token
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        project = option_str(cfg, "project_id") or adc_project()
+        if not project:
+            return PartialUsage(messages=[msg("credentials_missing", provider=self.title, hint=f"providers.{self.name.value}.project_id or GOOGLE_CLOUD_PROJECT")])
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        try:
+            headers = {"Authorization": f"Bearer {self.token()}", "X-Goog-User-Project": project}
+            limits = quota_limits(self.fetch(
+                f"{SERVICE_USAGE}/projects/{project}/services/{self.service}/consumerQuotaMetrics?view=BASIC", headers
+            ))
+            minute = quota_usage(self.fetch(self._series_url(project, now - timedelta(minutes=2), now, 60), headers))
+            day_start = datetime(now.year, now.month, now.day)
+            day = quota_usage(self.fetch(self._series_url(project, day_start, now, 86400), headers))
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (KeyError, TypeError, ValueError, AttributeError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider=self.title, detail=f": {exc}")])
+
+        minute_pct, minute_metric = _busiest(minute, _matching(limits["minute"], self.minute_match))
+        day_pct, day_metric = _busiest(day, _matching(limits["day"], self.day_match))
+        messages = [
+            msg(code, metric=_short(metric))
+            for code, metric in (("busiest_minute_quota", minute_metric), ("busiest_day_quota", day_metric))
+            if metric
+        ]
+        if not limits["minute"] and not limits["day"]:
+            messages.append(msg("no_usage_metrics", provider=self.name.value))
+        return PartialUsage(
+            session_used_pct=minute_pct,
+            session_reset_at=now.replace(second=0, microsecond=0) + timedelta(minutes=1),
+            weekly_used_pct=day_pct,
+            weekly_reset_at=datetime(now.year, now.month, now.day) + timedelta(days=1),
+            details={
+                "session_window": "per-minute",
+                "long_window": "daily",
+                "project": project,
+                "minute_quota_metric": minute_metric,
+                "day_quota_metric": day_metric,
+            },
+            messages=messages,
+        )
+
+    def _series_url(self, project: str, start: datetime, end: datetime, period: int) -> str:
+        query = urllib.parse.urlencode({
+            "filter": f'metric.type="{USAGE_METRIC}" AND resource.labels.service="{self.service}"',
+            "interval.startTime": start.isoformat() + "Z",
+            "interval.endTime": end.isoformat() + "Z",
+            "aggregation.alignmentPeriod": f"{period}s",
+            "aggregation.perSeriesAligner": "ALIGN_SUM",
+        })
+        return f"{MONITORING}/projects/{project}/timeSeries?{query}"
+
+
+def _matching(limits: dict[str, float], text: str) -> dict[str, float]:
+    return {m: v for m, v in limits.items() if text.lower() in m.lower()}
+
+
+def _busiest(usage: dict[str, float], limits: dict[str, float]) -> tuple[float | None, str]:
+    best: tuple[float | None, str] = (None, "")
+    for metric, limit in limits.items():
+        pct = Percent.ratio(usage.get(metric, 0.0), limit)
+        if pct is not None and (best[0] is None or pct > best[0]):
+            best = (pct, metric)
+    return best
+
+
+def _short(metric: str) -> str:
+    return metric.rsplit("/", 1)[-1]
@@ -3,17 +3,40 @@ from __future__ import annotations
 from datetime import datetime, timedelta
 from pathlib import Path
 import json
+import os
 import re
 
 from usagedash.config import ProviderConfig
-from usagedash.models import ProviderName, ProviderSnapshot
-from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage
+from usagedash.paths import home_dir
+from usagedash.messages import Diagnostic, msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    PartialUsage,
+    ProviderAdapter,
+    activity_details,
+    context_gauge,
+    latest_write,
+    merge_usage,
+)
 
 FIVE_HOUR_RE = re.compile(r"5h limit:\s*\[[^\]]*\]\s*([0-9]{1,3})% left \(resets ([0-9]{2}:[0-9]{2})\)")
 WEEKLY_RE = re.compile(r"Weekly limit:\s*\[[^\]]*\]\s*([0-9]{1,3})% left \(resets ([0-9]{2}:[0-9]{2}) on ([0-9]{1,2} [A-Za-z]{3})\)")
 
 
+def codex_home() -> Path:
+    """The active Codex profile: `CODEX_HOME` as the CLI resolves it, else `~/.codex`."""
+    override = os.environ.get("CODEX_HOME")
+    return Path(override).expanduser() if override else home_dir() / ".codex"
+
+
 class CodexAdapter(ProviderAdapter):
+    """Codex limits from session logs, falling back to history.jsonl.
+
+    `[providers.codex.accounts]` maps account names to `CODEX_HOME`
+    directories and reports each as its own row; the one matching the
+    current `CODEX_HOME` is flagged active.
+    """
+
     name = ProviderName.CODEX
 
     def __init__(
@@ -21,12 +44,49 @@ class CodexAdapter(ProviderAdapter):
         history_path: Path | None = None,
         sessions_path: Path | None = None,
     ) -> None:
-        self.history_path = history_path or Path.home() / ".codex/history.jsonl"
-        self.sessions_path = sessions_path or Path.home() / ".codex/sessions"
+        self.history_path = history_path or codex_home() / "history.jsonl"
+        self.sessions_path = sessions_path or codex_home() / "sessions"
 
     def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
         partial = self._parse()
-        return merge_usage(self.name, partial, cfg)
+        snap = merge_usage(self.name, partial, cfg)
+        last_at = latest_write(self._recent_session_files()) or latest_write([self.history_path])
+        if last_at is not None:
+            snap.details = {**snap.details, **activity_details(last_at, cfg)}
+        return snap
+
+    def _recent_session_files(self, now: datetime | None = None) -> list[Path]:
+        """Rollout files from today and yesterday, where a running session writes."""
+        now = now or datetime.now()
+        files: list[Path] = []
+        for day_offset in range(2):
+            day_dir = self.sessions_path / (now - timedelta(days=day_offset)).strftime("%Y/%m/%d")
+            if day_dir.exists():
+                files.extend(day_dir.glob("rollout-*.jsonl"))
+        return files
+
+    def _latest_day_files(self) -> list[Path]:
+        """Rollout files from the newest day directory, however old it is."""
+        days = sorted(self.sessions_path.glob("[0-9]*/[0-9]*/[0-9]*"), reverse=True)
+        for day_dir in days:
+            files = list(day_dir.glob("rollout-*.jsonl"))
+            if files:
+                return files
+        return []
+
+    def collect_all(self, cfg: ProviderConfig) -> list[ProviderSnapshot]:
+        accounts = cfg.options.get("accounts")
+        if not isinstance(accounts, dict) or not accounts:
+            return [self.collect(cfg)]
+        active = codex_home().resolve()
+        rows: list[ProviderSnapshot] = []
+        for account, home in accounts.items():
+            root = Path(str(home)).expanduser()
+            snap = CodexAdapter(root / "history.jsonl", root / "sessions").collect(cfg)
+            snap.account = str(account)
+            snap.details = {**snap.details, "codex_home": str(root), "active": root.resolve() == active}
+            rows.append(snap)
+        return rows
 
     def _parse(self) -> PartialUsage:
         # Try structured session files first (much richer data).
@@ -39,24 +99,20 @@ class CodexAdapter(ProviderAdapter):
 
     def _parse_from_sessions(self) -> PartialUsage:
         if not self.sessions_path.exists():
-            return PartialUsage(messages=["no Codex sessions directory found"])
+            return PartialUsage(messages=[msg("missing_source_file", path=self.sessions_path)])
 
         now = datetime.now()
         five_hours_ago = now - timedelta(hours=5)
 
-        # Find recent session files (today + yesterday to cover edge cases).
+        # Find recent session files (today + yesterday to cover edge cases),
+        # else the newest day on disk: its limits still tell when they reset.
         # Sort by modification time (not filename) — a resumed session has an
         # earlier creation timestamp but the most recent writes.
-        candidates: list[Path] = []
-        for day_offset in range(2):
-            day = now - timedelta(days=day_offset)
-            day_dir = self.sessions_path / day.strftime("%Y/%m/%d")
-            if day_dir.exists():
-                candidates.extend(day_dir.glob("rollout-*.jsonl"))
+        candidates = self._recent_session_files(now) or self._latest_day_files()
         candidates.sort(key=lambda p: p.stat().st_mtime, reverse=True)
 
         if not candidates:
-            return PartialUsage(messages=["no recent Codex session files found"])
+            return PartialUsage(messages=[msg("no_session_files", provider="Codex", path=self.sessions_path)])
 
         # Read the most recent session file for rate_limits and token data.
         latest_rate_limits: dict | None = None
@@ -67,6 +123,8 @@ class CodexAdapter(ProviderAdapter):
         session_start: datetime | None = None
         model_name: str | None = None
         context_window: int | None = None
+        # Tokens in the prompt of the latest turn: what the context currently holds.
+        context_tokens: int | None = None
 
         for session_file in candidates:
             found_in_file = False
@@ -113,6 +171,11 @@ class CodexAdapter(ProviderAdapter):
                                 cw = info.get("model_context_window")
                                 if isinstance(cw, int) and cw > 0:
                                     context_window = cw
+                                last_usage = info.get("last_token_usage")
+                                if isinstance(last_usage, dict):
+                                    total = last_usage.get("total_tokens")
+                                    if isinstance(total, int) and total > 0:
+                                        context_tokens = total
 
                                 # total_token_usage is cumulative — always keep the latest.
                                 total_usage = info.get("total_token_usage")
@@ -140,7 +203,7 @@ class CodexAdapter(ProviderAdapter):
                 session_tokens += int(out)
 
         if latest_rate_limits is None:
-            return PartialUsage(messages=["no rate limit data found in Codex session files"])
+            return PartialUsage(messages=[msg("no_rate_limit_data", provider="Codex")])
 
         # Parse structured rate limits.
         primary = latest_rate_limits.get("primary", {})
@@ -149,8 +212,8 @@ class CodexAdapter(ProviderAdapter):
         session_used = primary.get("used_percent")
         weekly_used = secondary.get("used_percent")
 
-        session_reset = _unix_to_dt(primary.get("resets_at"))
-        weekly_reset = _unix_to_dt(secondary.get("resets_at"))
+        session_reset = _window_reset(primary, latest_rl_ts)
+        weekly_reset = _window_reset(secondary, latest_rl_ts)
 
         if isinstance(session_used, (int, float)):
             session_used = float(session_used)
@@ -180,7 +243,10 @@ class CodexAdapter(ProviderAdapter):
             }
         }
 
-        messages = [f"parsed from Codex session: {candidates[0].name}"]
+        if context_window and context_tokens is not None:
+            details["context"] = context_gauge(context_tokens, context_window)
+
+        messages: list[str | Diagnostic] = [msg("parsed_from", provider="Codex", file=candidates[0].name)]
 
         return PartialUsage(
             session_used_pct=session_used,
@@ -193,7 +259,7 @@ class CodexAdapter(ProviderAdapter):
 
     def _parse_from_history(self) -> PartialUsage:
         if not self.history_path.exists():
-            return PartialUsage(messages=[f"missing {self.history_path}"])
+            return PartialUsage(messages=[msg("missing_source_file", path=self.history_path)])
 
         lines = self.history_path.read_text(errors="ignore").splitlines()[-300:]
         lines.reverse()
@@ -202,21 +268,21 @@ class CodexAdapter(ProviderAdapter):
         session_reset = None
         weekly_used = None
         weekly_reset = None
-        messages: list[str] = []
+        messages: list[str | Diagnostic] = []
 
         for line in lines:
             if session_used is None:
                 m = FIVE_HOUR_RE.search(line)
                 if m:
                     left = float(m.group(1))
-                    session_used = max(0.0, 100.0 - left)
+                    session_used = Percent.from_left(left)
                     session_reset = datetime.combine(datetime.now().date(), datetime.strptime(m.group(2), "%H:%M").time())
 
             if weekly_used is None:
                 m = WEEKLY_RE.search(line)
                 if m:
                     left = float(m.group(1))
-                    weekly_used = max(0.0, 100.0 - left)
+                    weekly_used = Percent.from_left(left)
                     dt_str = f"{m.group(3)} {datetime.now().year} {m.group(2)}"
                     weekly_reset = datetime.strptime(dt_str, "%d %b %Y %H:%M")
 
@@ -224,7 +290,7 @@ class CodexAdapter(ProviderAdapter):
                 break
 
         if session_used is None and weekly_used is None:
-            messages.append("unable to parse Codex usage from sessions or history")
+            messages.append(msg("usage_not_inferred", provider="Codex", sources="sessions or history"))
 
         return PartialUsage(
             session_used_pct=session_used,
@@ -236,12 +302,27 @@ class CodexAdapter(ProviderAdapter):
 
 
 def _parse_ts(value: str | None) -> datetime | None:
+    """Session timestamps are UTC (`Z`); compare them as naive local time."""
     if not value:
         return None
     try:
-        return datetime.fromisoformat(value.replace("Z", "+00:00")).replace(tzinfo=None)
+        parsed = datetime.fromisoformat(value.replace("Z", "+00:00"))
     except ValueError:
         return None
+    if parsed.tzinfo is not None:
+        parsed = parsed.astimezone().replace(tzinfo=None)
+    return parsed
+
+
+def _window_reset(window: dict, observed_at: datetime | None) -> datetime | None:
+    """`resets_at` epoch, or `resets_in_seconds` from older CLIs counted from the event."""
+    reset = _unix_to_dt(window.get("resets_at"))
+    if reset is not None:
+        return reset
+    seconds = window.get("resets_in_seconds")
+    if isinstance(seconds, (int, float)) and observed_at is not None:
+        return observed_at + timedelta(seconds=seconds)
+    return None
 
 
 def _unix_to_dt(value: int | float | None) -> datetime | None:
@@ -0,0 +1,91 @@
+from __future__ import annotations
+
+import json
+from datetime import datetime, timezone
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.paths import xdg_state_home
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage, option_str
+from usagedash.providers.billing import month_start, next_month_start
+from usagedash.providers.groq import log_entry_time
+
+# Trial keys are capped at 1,000 API calls per calendar month; production
+# keys have no monthly cap, only per-minute rate limits.
+TRIAL_MONTHLY_CALLS = 1000
+
+
+def default_log_path() -> Path:
+    return xdg_state_home() / "cohere/requests.jsonl"
+
+
+def record_call(path: str | Path | None = None, billed_units: dict | None = None, now: datetime | None = None) -> None:
+    """Append one Cohere API call to the log `CohereAdapter` counts.
+
+    Call it after each request your code makes, e.g. with
+    `response.meta.billed_units` as a dict; `path` defaults to the
+    adapter's default `log_path`.
+    """
+    target = Path(path).expanduser() if path else default_log_path()
+    entry: dict[str, object] = {"timestamp": (now or datetime.now(timezone.utc)).isoformat()}
+    if billed_units:
+        entry["billed_units"] = billed_units
+    target.parent.mkdir(parents=True, exist_ok=True)
+    with target.open("a") as fh:
+        fh.write(json.dumps(entry) + "\n")
+
+
+class CohereAdapter(ProviderAdapter):
+    """Cohere API calls this calendar month, tallied from a local JSONL request log.
+
+    Cohere exposes no usage endpoint, and nothing writes this log but you:
+    call `usagedash.providers.cohere.record_call()` after each request, or
+    have your client append one JSON object per call carrying a `timestamp`
+    (ISO 8601) or `created` (Unix seconds) field to `log_path` (default:
+    `$XDG_STATE_HOME/cohere/requests.jsonl`). `key_type = "trial"` (the
+    default) reports against the trial key's monthly cap; production keys
+    report a percentage only when `monthly_call_limit` is set.
+    """
+
+    name = ProviderName.COHERE
+
+    def __init__(self, now: datetime | None = None) -> None:
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        path = Path(option_str(cfg, "log_path", str(default_log_path()))).expanduser()
+        if not path.exists():
+            return PartialUsage(messages=[msg("call_log_missing", provider="Cohere", path=path, hint="usagedash.providers.cohere.record_call")])
+
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        start = month_start(now)
+        calls = 0
+        with path.open(errors="ignore") as fh:
+            for line in fh:
+                try:
+                    entry = json.loads(line)
+                except json.JSONDecodeError:
+                    continue
+                if not isinstance(entry, dict):
+                    continue
+                at = log_entry_time(entry)
+                if at is not None and at >= start:
+                    calls += 1
+
+        key_type = option_str(cfg, "key_type", "trial")
+        limit = cfg.options.get("monthly_call_limit") or (TRIAL_MONTHLY_CALLS if key_type == "trial" else None)
+        pct = Percent.ratio(calls, float(limit) if limit else None)
+        messages = [] if limit else [msg("no_monthly_cap", provider="Cohere", option="monthly_call_limit")]
+        return PartialUsage(
+            weekly_used_pct=pct,
+            weekly_reset_at=next_month_start(now),
+            details={"long_window": "monthly", "key_type": key_type, "calls_this_month": calls, "monthly_call_limit": limit},
+            messages=messages,
+        )
@@ -0,0 +1,108 @@
+from __future__ import annotations
+
+import json
+import os
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.paths import xdg_config_home
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_str,
+)
+
+DEFAULT_API_BASE = "https://api.github.com"
+
+
+class CopilotAdapter(ProviderAdapter):
+    """GitHub Copilot quotas from the same endpoint the editor extensions poll.
+
+    The long window is monthly rather than weekly: premium requests fill the
+    weekly slot, and the chat quota (only limited on the free plan) fills the
+    session slot.
+    """
+
+    name = ProviderName.COPILOT
+
+    def __init__(self, config_dir: Path | None = None, fetch: FetchJson | None = None) -> None:
+        self.config_dir = config_dir or xdg_config_home() / "github-copilot"
+        self.fetch = fetch or fetch_json
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self._parse(cfg), cfg)
+
+    def _token(self, cfg: ProviderConfig) -> str | None:
+        env_name = option_str(cfg, "token_env")
+        if env_name and os.environ.get(env_name):
+            return os.environ[env_name]
+        # The editor plugins store their OAuth token in apps.json (newer) or hosts.json.
+        for name in ("apps.json", "hosts.json"):
+            try:
+                raw = json.loads((self.config_dir / name).read_text())
+            except (OSError, json.JSONDecodeError):
+                continue
+            if not isinstance(raw, dict):
+                continue
+            for key, entry in raw.items():
+                if "github.com" in key and isinstance(entry, dict) and entry.get("oauth_token"):
+                    return str(entry["oauth_token"])
+        return None
+
+    def _parse(self, cfg: ProviderConfig) -> PartialUsage:
+        token = self._token(cfg)
+        if token is None:
+            return PartialUsage(messages=[msg("credentials_missing", provider="Copilot", hint=f"token_env or sign in to write {self.config_dir}")])
+
+        api_base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        try:
+            raw = self.fetch(f"{api_base}/copilot_internal/user", {"Authorization": f"token {token}"})
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        if not isinstance(raw, dict):
+            return PartialUsage(messages=[msg("unexpected_response", provider="Copilot", detail="")])
+        return parse_copilot_quota(raw)
+
+
+def _used_pct(quota: object) -> float | None:
+    if not isinstance(quota, dict) or quota.get("unlimited"):
+        return None
+    remaining = quota.get("percent_remaining")
+    if remaining is None:
+        return None
+    return Percent.from_left(remaining)
+
+
+def parse_copilot_quota(raw: dict) -> PartialUsage:
+    quotas = raw.get("quota_snapshots") or {}
+    reset_at = None
+    if raw.get("quota_reset_date"):
+        try:
+            reset_at = datetime.fromisoformat(str(raw["quota_reset_date"]))
+        except ValueError:
+            reset_at = None
+
+    session = _used_pct(quotas.get("chat"))
+    monthly = _used_pct(quotas.get("premium_interactions"))
+    details: dict[str, object] = {"long_window": "monthly", "plan": raw.get("copilot_plan")}
+    for key, quota in quotas.items():
+        if isinstance(quota, dict) and not quota.get("unlimited"):
+            details[f"{key}_remaining"] = quota.get("remaining")
+            details[f"{key}_entitlement"] = quota.get("entitlement")
+    return PartialUsage(
+        session_used_pct=session,
+        session_reset_at=reset_at if session is not None else None,
+        weekly_used_pct=monthly,
+        weekly_reset_at=reset_at if monthly is not None else None,
+        details=details,
+        messages=[],
+    )
@@ -0,0 +1,116 @@
+from __future__ import annotations
+
+import base64
+import json
+import os
+from datetime import datetime, timezone
+from pathlib import Path
+from urllib.parse import quote
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_str,
+)
+from usagedash.providers.vscdb import read_item, state_db_path
+
+DEFAULT_API_BASE = "https://www.cursor.com"
+TOKEN_KEY = "cursorAuth/accessToken"
+
+
+class CursorAdapter(ProviderAdapter):
+    """Cursor fast-request usage for the current billing month.
+
+    The access token comes from `token_env` when configured, otherwise from
+    the editor's own `state.vscdb`. Fast requests fill the weekly slot, which
+    is labeled monthly because that is Cursor's billing cycle.
+    """
+
+    name = ProviderName.CURSOR
+
+    def __init__(self, state_db: Path | None = None, fetch: FetchJson | None = None) -> None:
+        self.state_db = state_db or state_db_path("Cursor")
+        self.fetch = fetch or fetch_json
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self._parse(cfg), cfg)
+
+    def _token(self, cfg: ProviderConfig) -> str | None:
+        env_name = option_str(cfg, "token_env")
+        if env_name and os.environ.get(env_name):
+            return os.environ[env_name]
+        value = read_item(self.state_db, TOKEN_KEY)
+        return value.strip('"') if value else None
+
+    def _parse(self, cfg: ProviderConfig) -> PartialUsage:
+        token = self._token(cfg)
+        if token is None:
+            return PartialUsage(messages=[msg("credentials_missing", provider="Cursor", hint=f"token_env or sign in to write {self.state_db}")])
+        user_id = _jwt_subject(token)
+        if user_id is None:
+            return PartialUsage(messages=[msg("invalid_credentials", provider="Cursor", reason="token is not a JWT")])
+
+        api_base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        cookie = f"WorkosCursorSessionToken={quote(user_id)}%3A%3A{token}"
+        try:
+            raw = self.fetch(f"{api_base}/api/usage?user={quote(user_id)}", {"Cookie": cookie})
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        if not isinstance(raw, dict):
+            return PartialUsage(messages=[msg("unexpected_response", provider="Cursor", detail="")])
+        return parse_cursor_usage(raw)
+
+
+def _jwt_subject(token: str) -> str | None:
+    try:
+        payload = token.split(".")[1]
+        claims = json.loads(base64.urlsafe_b64decode(payload + "=" * (-len(payload) % 4)))
+    except (IndexError, ValueError):
+        return None
+    sub = claims.get("sub") if isinstance(claims, dict) else None
+    if not sub:
+        return None
+    # "auth0|user_123" -> "user_123"
+    return str(sub).split("|")[-1]
+
+
+def _next_month(dt: datetime) -> datetime:
+    if dt.month == 12:
+        return dt.replace(year=dt.year + 1, month=1)
+    return dt.replace(month=dt.month + 1)
+
+
+def parse_cursor_usage(raw: dict) -> PartialUsage:
+    fast = raw.get("gpt-4") or {}
+    used = fast.get("numRequests")
+    limit = fast.get("maxRequestUsage")
+    pct = None
+    if isinstance(used, (int, float)) and isinstance(limit, (int, float)) and limit > 0:
+        pct = Percent.ratio(used, limit)
+
+    reset_at = None
+    if raw.get("startOfMonth"):
+        try:
+            start = datetime.fromisoformat(str(raw["startOfMonth"]).replace("Z", "+00:00"))
+            if start.tzinfo is not None:
+                start = start.astimezone(timezone.utc).replace(tzinfo=None)
+            reset_at = _next_month(start)
+        except ValueError:
+            reset_at = None
+
+    messages = [] if limit else [msg("no_fast_request_limit", provider="Cursor")]
+    return PartialUsage(
+        weekly_used_pct=pct,
+        weekly_reset_at=reset_at,
+        details={"long_window": "monthly", "fast_requests": used, "fast_request_limit": limit},
+        messages=messages,
+    )
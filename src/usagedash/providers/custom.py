@@ -0,0 +1,185 @@
+from __future__ import annotations
+
+import json
+import os
+import shlex
+import subprocess
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig, provider_from_dict
+from usagedash.messages import msg
+from usagedash.models import ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    OptionError,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_float,
+)
+
+DEFAULT_TIMEOUT_SECONDS = 10.0
+POINTER_FIELDS = ("session_used_pct", "session_reset_at", "weekly_used_pct", "weekly_reset_at")
+
+
+class CustomAdapter(ProviderAdapter):
+    """User-defined providers, one row per `[providers.custom.<name>]` table.
+
+    A table with `command` runs it and reads a JSON object from stdout with
+    any of `session_used_pct`, `session_reset_at`, `weekly_used_pct`,
+    `weekly_reset_at`, `details` and `messages`; a table with `path` reads
+    that JSON file instead, and one with `url` fetches it, sending
+    `token` (or the value of `token_env`) as a bearer token. A `[...pointers]` sub-table maps those field
+    names to JSON pointers (RFC 6901) for documents in some other shape.
+    Each table also takes the usual `manual`, `parser_mode` and
+    `display_name` keys.
+    """
+
+    name = ProviderName.CUSTOM
+
+    def __init__(self, fetch: FetchJson | None = None) -> None:
+        self.fetch = fetch
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        rows = self.collect_all(cfg)
+        return rows[0] if rows else merge_usage(self.name, None, cfg)
+
+    def collect_all(self, cfg: ProviderConfig) -> list[ProviderSnapshot]:
+        rows: list[ProviderSnapshot] = []
+        for entry_name, raw in cfg.options.items():
+            if not isinstance(raw, dict):
+                continue
+            entry = provider_from_dict(raw)
+            if not entry.enabled:
+                continue
+            try:
+                partial = None if entry.parser_mode == "manual" else self.parse(entry)
+            except OptionError as exc:
+                partial = PartialUsage(messages=[exc.diagnostic(f"custom.{entry_name}")])
+            snap = merge_usage(self.name, partial, entry)
+            snap.account = str(entry_name)
+            snap.display_name = entry.display_name
+            rows.append(snap)
+        return rows
+
+    def parse(self, entry: ProviderConfig) -> PartialUsage:
+        pointers = entry.options.get("pointers")
+        pointers = pointers if isinstance(pointers, dict) else None
+        command = entry.options.get("command")
+        if command and not self.run_commands:
+            return PartialUsage(messages=[msg("command_skipped", command=command)])
+        if command:
+            return run_command(command, option_float(entry, "timeout", DEFAULT_TIMEOUT_SECONDS), pointers)
+        if entry.options.get("path"):
+            return read_file(Path(str(entry.options["path"])).expanduser(), pointers)
+        if entry.options.get("url"):
+            return self._fetch(entry, pointers)
+        return PartialUsage(messages=[msg("source_missing", options="command, path or url")])
+
+    def _fetch(self, entry: ProviderConfig, pointers: dict | None) -> PartialUsage:
+        url = str(entry.options["url"])
+        headers = {str(k): str(v) for k, v in (entry.options.get("headers") or {}).items()}
+        token = str(entry.options.get("token") or os.environ.get(str(entry.options.get("token_env", "")), ""))
+        if token:
+            headers["Authorization"] = f"Bearer {token}"
+        elif entry.options.get("token_env"):
+            return PartialUsage(messages=[msg("credentials_missing", provider=url, hint=str(entry.options["token_env"]))])
+        try:
+            raw = (self.fetch or fetch_json)(url, headers)
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        return _partial(raw, pointers, url)
+
+
+def read_file(path: Path, pointers: dict | None = None) -> PartialUsage:
+    if not path.exists():
+        return PartialUsage(messages=[msg("missing_source_file", path=path)])
+    try:
+        raw = json.loads(path.read_text(errors="ignore"))
+    except (OSError, json.JSONDecodeError) as exc:
+        return PartialUsage(messages=[msg("unexpected_response", provider=path.name, detail=f": {exc}")])
+    partial = _partial(raw, pointers, path.name)
+    partial.as_of = datetime.fromtimestamp(path.stat().st_mtime)
+    return partial
+
+
+def run_command(command: str | list[str], timeout: float, pointers: dict | None = None) -> PartialUsage:
+    argv = shlex.split(command) if isinstance(command, str) else [str(a) for a in command]
+    label = argv[0] if argv else ""
+    try:
+        done = subprocess.run(argv, capture_output=True, text=True, timeout=timeout, check=False)
+    except (OSError, ValueError, subprocess.TimeoutExpired) as exc:
+        return PartialUsage(messages=[msg("command_failed", command=label, error=exc)])
+    if done.returncode != 0:
+        error = done.stderr.strip().splitlines()[-1] if done.stderr.strip() else f"exit status {done.returncode}"
+        return PartialUsage(messages=[msg("command_failed", command=label, error=error)])
+    try:
+        raw = json.loads(done.stdout)
+    except json.JSONDecodeError as exc:
+        return PartialUsage(messages=[msg("unexpected_response", provider=label, detail=f": {exc}")])
+    return _partial(raw, pointers, label)
+
+
+def _partial(raw: object, pointers: dict | None, label: str) -> PartialUsage:
+    if pointers:
+        raw = {name: resolve_pointer(raw, str(pointers[name])) for name in POINTER_FIELDS if name in pointers}
+    if not isinstance(raw, dict):
+        return PartialUsage(messages=[msg("unexpected_response", provider=label, detail=": expected a JSON object")])
+    return partial_from_json(raw)
+
+
+def resolve_pointer(doc: object, pointer: str) -> object:
+    """The value at JSON pointer `pointer` in `doc`, or None when it is absent."""
+    if pointer == "":
+        return doc
+    if not pointer.startswith("/"):
+        return None
+    node = doc
+    for token in pointer[1:].split("/"):
+        token = token.replace("~1", "/").replace("~0", "~")
+        if isinstance(node, dict):
+            node = node.get(token)
+        elif isinstance(node, list) and token.isdigit() and int(token) < len(node):
+            node = node[int(token)]
+        else:
+            return None
+    return node
+
+
+def partial_from_json(raw: dict) -> PartialUsage:
+    """Read the `PartialUsage` fields from a JSON object, skipping malformed values."""
+    details = raw.get("details")
+    messages = raw.get("messages")
+    return PartialUsage(
+        session_used_pct=_percent(raw.get("session_used_pct")),
+        session_reset_at=parse_when(raw.get("session_reset_at")),
+        weekly_used_pct=_percent(raw.get("weekly_used_pct")),
+        weekly_reset_at=parse_when(raw.get("weekly_reset_at")),
+        details=details if isinstance(details, dict) else None,
+        messages=[str(m) for m in messages] if isinstance(messages, list) else None,
+    )
+
+
+def _percent(value: object) -> float | None:
+    if isinstance(value, bool) or not isinstance(value, (int, float, str)):
+        return None
+    try:
+        return float(value)
+    except ValueError:
+        return None
+
+
+def parse_when(value: object) -> datetime | None:
+    """An ISO 8601 string or Unix epoch seconds, as naive local time."""
+    try:
+        if isinstance(value, (int, float)) and not isinstance(value, bool):
+            return datetime.fromtimestamp(value)
+        if isinstance(value, str) and value:
+            parsed = datetime.fromisoformat(value.replace("Z", "+00:00"))
+            return parsed.astimezone().replace(tzinfo=None) if parsed.tzinfo else parsed
+    except (ValueError, OverflowError, OSError):
+        return None
+    return None
@@ -0,0 +1,119 @@
+from __future__ import annotations
+
+import json
+import os
+from datetime import datetime, timezone
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.paths import state_dir
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_float,
+    option_str,
+)
+from usagedash.providers.billing import next_month_start
+
+DEFAULT_API_BASE = "https://api.deepseek.com"
+DEFAULT_KEY_ENV = "DEEPSEEK_API_KEY"
+
+
+class DeepSeekAdapter(ProviderAdapter):
+    """DeepSeek remaining balance and month-to-date spend.
+
+    The API only reports the current balance, so spend is the drop since the
+    first balance seen this month, kept in a small state file next to the
+    snapshot state file (never written read-only). Top-ups raise the
+    baseline instead of counting as negative spend.
+    """
+
+    name = ProviderName.DEEPSEEK
+
+    def __init__(self, fetch: FetchJson | None = None, now: datetime | None = None, state_path: Path | None = None) -> None:
+        self.fetch = fetch or fetch_json
+        self.now = now
+        self.state_path = state_path
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def _state_path(self, cfg: ProviderConfig) -> Path:
+        if self.state_path is not None:
+            return self.state_path
+        configured = option_str(cfg, "state_path", "")
+        if configured:
+            return Path(configured).expanduser()
+        return (self.state_dir or state_dir()) / "deepseek-balance.json"
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        key_env = option_str(cfg, "api_key_env", DEFAULT_KEY_ENV)
+        key = os.environ.get(key_env)
+        if not key:
+            return PartialUsage(messages=[msg("credentials_missing", provider="DeepSeek", hint=key_env)])
+
+        base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        try:
+            raw = self.fetch(f"{base}/user/balance", {"Authorization": f"Bearer {key}"})
+            infos = raw["balance_infos"] if isinstance(raw, dict) else []
+            info = next((i for i in infos if i.get("currency") == option_str(cfg, "currency", "USD")), None)
+            info = info or (infos[0] if infos else None)
+            if info is None:
+                raise ValueError("no balance_infos")
+            balance = float(info["total_balance"])
+            currency = str(info.get("currency", "USD"))
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (KeyError, TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="DeepSeek", detail=f": {exc}")])
+
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        spent = self._track_spend(cfg, balance, now)
+        budget = option_float(cfg, "monthly_budget") or None
+        pct = Percent.ratio(spent, budget)
+        messages = [msg("balance", amount=f"{balance:,.2f}", currency=currency)]
+        if not budget:
+            messages.append(msg("budget_missing"))
+        return PartialUsage(
+            weekly_used_pct=pct,
+            weekly_reset_at=next_month_start(now),
+            details={
+                "long_window": "monthly",
+                "balance": balance,
+                "currency": currency,
+                "month_to_date_spend": round(spent, 4),
+                "monthly_budget": budget,
+                "available": bool(raw.get("is_available", True)),
+            },
+            messages=messages,
+        )
+
+    def _track_spend(self, cfg: ProviderConfig, balance: float, now: datetime) -> float:
+        path = self._state_path(cfg)
+        month = now.strftime("%Y-%m")
+        try:
+            state = json.loads(path.read_text())
+        except (OSError, json.JSONDecodeError):
+            state = {}
+        if not isinstance(state, dict) or state.get("month") != month:
+            state = {"month": month, "baseline": balance, "last": balance}
+        if balance > float(state.get("last", balance)):
+            # Top-up: shift the baseline by the amount added.
+            state["baseline"] = float(state["baseline"]) + balance - float(state["last"])
+        state["last"] = balance
+        if self.read_only:
+            return max(0.0, float(state["baseline"]) - balance)
+        try:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            path.write_text(json.dumps(state))
+        except OSError:
+            pass
+        return max(0.0, float(state["baseline"]) - balance)
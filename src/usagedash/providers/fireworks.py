@@ -0,0 +1,71 @@
+from __future__ import annotations
+
+import os
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchHeaders,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_headers,
+    merge_usage,
+    option_float,
+    option_str,
+)
+
+DEFAULT_API_BASE = "https://api.fireworks.ai"
+DEFAULT_KEY_ENV = "FIREWORKS_API_KEY"
+
+
+def rate_limit_usage(headers: dict[str, str], limit: float | None) -> PartialUsage:
+    """Requests used this minute from `x-ratelimit-*-requests`; `limit` overrides the header's."""
+    remaining = headers.get("x-ratelimit-remaining-requests")
+    if remaining is None:
+        return PartialUsage(messages=[msg("unexpected_response", provider="Fireworks AI", detail=": no rate-limit headers")])
+    limit = limit or float(headers.get("x-ratelimit-limit-requests") or 0)
+    left = max(0.0, float(remaining))
+    return PartialUsage(
+        session_used_pct=Percent.ratio(limit - left, limit),
+        details={"session_window": "minute", "requests_remaining": int(left), "requests_per_minute": int(limit)},
+    )
+
+
+class FireworksAdapter(ProviderAdapter):
+    """Fireworks AI per-minute request headroom.
+
+    The session slot shows how much of the per-minute request limit is in
+    use, read from the rate-limit headers on the inference API's model
+    list; `requests_per_minute` replaces the limit the headers report.
+    Fireworks documents no spend API, so there is no monthly slot.
+    Experimental until the header names are checked against a captured
+    response.
+    """
+
+    name = ProviderName.FIREWORKS
+    experimental = True
+
+    def __init__(self, headers_fetch: FetchHeaders | None = None) -> None:
+        self.headers_fetch = headers_fetch or fetch_headers
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        key_env = option_str(cfg, "api_key_env", DEFAULT_KEY_ENV)
+        key = os.environ.get(key_env)
+        if not key:
+            return PartialUsage(messages=[msg("credentials_missing", provider="Fireworks AI", hint=key_env)])
+        base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        per_minute = option_float(cfg, "requests_per_minute") or None
+        try:
+            headers = self.headers_fetch(f"{base}/inference/v1/models", {"Authorization": f"Bearer {key}"}, None)
+            return rate_limit_usage(headers, per_minute)
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except ValueError as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="Fireworks AI", detail=f": {exc}")])
@@ -0,0 +1,96 @@
+from __future__ import annotations
+
+import json
+import os
+import subprocess
+import urllib.parse
+import urllib.request
+from collections.abc import Callable
+from pathlib import Path
+
+from usagedash.paths import home_dir
+from usagedash.providers.base import ProviderError
+
+TOKEN_URL = "https://oauth2.googleapis.com/token"
+CLOUD_SCOPE = "https://www.googleapis.com/auth/cloud-platform"
+
+AccessToken = Callable[[], str]
+
+
+def adc_path() -> Path:
+    override = os.environ.get("GOOGLE_APPLICATION_CREDENTIALS")
+    if override:
+        return Path(override).expanduser()
+    if os.name == "nt":
+        return Path(os.environ.get("APPDATA", home_dir())) / "gcloud/application_default_credentials.json"
+    return home_dir() / ".config/gcloud/application_default_credentials.json"
+
+
+def adc_project() -> str:
+    """Project from the environment or the ADC file's quota project, if any."""
+    for var in ("GOOGLE_CLOUD_PROJECT", "CLOUDSDK_CORE_PROJECT"):
+        if os.environ.get(var):
+            return os.environ[var]
+    try:
+        raw = json.loads(adc_path().read_text())
+    except (OSError, json.JSONDecodeError):
+        return ""
+    return str(raw.get("quota_project_id") or raw.get("project_id") or "") if isinstance(raw, dict) else ""
+
+
+def adc_access_token() -> str:
+    """An OAuth access token from application default credentials.
+
+    With the `gcloud` extra installed google-auth handles every credential
+    type. Without it, user credentials are refreshed directly and anything
+    else (service account keys, workload identity) is delegated to the
+    `gcloud` CLI, which can sign JWTs.
+    """
+    if os.environ.get("GOOGLE_OAUTH_ACCESS_TOKEN"):
+        return os.environ["GOOGLE_OAUTH_ACCESS_TOKEN"]
+    try:
+        import google.auth  # type: ignore[import-not-found]
+        import google.auth.exceptions  # type: ignore[import-not-found]
+        import google.auth.transport.requests  # type: ignore[import-not-found]
+    except ImportError:
+        pass
+    else:
+        try:
+            creds, _ = google.auth.default(scopes=[CLOUD_SCOPE])
+            creds.refresh(google.auth.transport.requests.Request())
+        except google.auth.exceptions.GoogleAuthError as exc:
+            raise ProviderError(f"application default credentials: {exc}") from exc
+        return str(creds.token)
+    try:
+        raw = json.loads(adc_path().read_text())
+    except (OSError, json.JSONDecodeError):
+        raw = None
+    if isinstance(raw, dict) and raw.get("type") == "authorized_user":
+        return _refresh_user_token(raw)
+    try:
+        proc = subprocess.run(
+            ["gcloud", "auth", "application-default", "print-access-token"],
+            capture_output=True,
+            text=True,
+            timeout=20,
+        )
+    except (OSError, subprocess.TimeoutExpired) as exc:
+        raise ProviderError(f"no application default credentials ({exc})") from exc
+    if proc.returncode != 0 or not proc.stdout.strip():
+        raise ProviderError(f"gcloud could not mint a token: {proc.stderr.strip() or proc.returncode}")
+    return proc.stdout.strip()
+
+
+def _refresh_user_token(raw: dict) -> str:
+    body = urllib.parse.urlencode({
+        "client_id": raw.get("client_id", ""),
+        "client_secret": raw.get("client_secret", ""),
+        "refresh_token": raw.get("refresh_token", ""),
+        "grant_type": "refresh_token",
+    }).encode("ascii")
+    req = urllib.request.Request(TOKEN_URL, data=body, method="POST")
+    try:
+        with urllib.request.urlopen(req, timeout=10) as resp:
+            return str(json.loads(resp.read())["access_token"])
+    except (OSError, ValueError, KeyError) as exc:
+        raise ProviderError(f"refreshing application default credentials failed: {exc}") from exc
@@ -1,13 +1,70 @@
 from __future__ import annotations
 
+from dataclasses import replace
+
 from usagedash.config import ProviderConfig
+from usagedash.messages import msg
 from usagedash.models import ProviderName, ProviderSnapshot
 from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage
+from usagedash.providers.cloud_quota import CloudQuotaAdapter
+from usagedash.providers.vertex import VertexAdapter
+
+# Rows reported in cloud mode, by `sources` entry.
+CLOUD_SOURCES = ("ai-studio", "vertex")
+
+
+class GeminiCloudAdapter(CloudQuotaAdapter):
+    """Gemini API (AI Studio key) tokens-per-minute and requests-per-day quotas."""
+
+    name = ProviderName.GEMINI
+    title = "Gemini API"
+    service = "generativelanguage.googleapis.com"
+    minute_match = "token"
+    day_match = "request"
+
+
+class GeminiVertexAdapter(VertexAdapter):
+    """Gemini models served through Vertex AI, reported under the Gemini provider."""
+
+    name = ProviderName.GEMINI
 
 
 class GeminiAdapter(ProviderAdapter):
+    """Gemini usage; `parser_mode = "cloud"` reads quotas from Google Cloud.
+
+    AI Studio keys and Vertex AI projects have separate quotas with different
+    windows, so with `sources = ["ai-studio", "vertex"]` each is its own row.
+    `vertex_project_id` points the Vertex row at a different project. The
+    CLI free tier has no local usage record yet, so other modes rely on
+    manual values.
+    """
+
     name = ProviderName.GEMINI
 
     def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
-        partial = PartialUsage(messages=["Gemini parser is not implemented in v2; use manual fields"])
+        if cfg.parser_mode == "cloud":
+            return GeminiCloudAdapter().collect(cfg)
+        partial = PartialUsage(messages=[msg("parser_not_implemented", provider="Gemini")])
         return merge_usage(self.name, partial, cfg)
+
+    def collect_all(self, cfg: ProviderConfig) -> list[ProviderSnapshot]:
+        sources = [str(s) for s in cfg.options.get("sources") or ["ai-studio"] if str(s) in CLOUD_SOURCES]
+        if cfg.parser_mode != "cloud" or not sources:
+            return [self.collect(cfg)]
+        rows: list[ProviderSnapshot] = []
+        for source in sources:
+            if source == "vertex":
+                snap = GeminiVertexAdapter().collect(_vertex_config(cfg))
+            else:
+                snap = GeminiCloudAdapter().collect(cfg)
+            if len(sources) > 1:
+                snap.account = source
+            rows.append(snap)
+        return rows
+
+
+def _vertex_config(cfg: ProviderConfig) -> ProviderConfig:
+    project = cfg.options.get("vertex_project_id")
+    if not project:
+        return cfg
+    return replace(cfg, options={**cfg.options, "project_id": project})
@@ -0,0 +1,133 @@
+from __future__ import annotations
+
+import json
+import os
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.durations import parse_duration
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.paths import cache_dir
+from usagedash.providers.base import (
+    FetchHeaders,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_headers,
+    merge_usage,
+    option_float,
+    option_str,
+)
+
+DEFAULT_API_BASE = "https://models.github.ai"
+DEFAULT_MODEL = "openai/gpt-4o-mini"
+# Requests per day on the free (and Copilot Free/Pro) plans, by model tier.
+DAILY_REQUESTS = {"low": 150, "high": 50, "embedding": 150}
+# Each probe is a billed completion taken from the quota it measures: four a day.
+DEFAULT_PROBE_MINUTES = 360.0
+
+
+def _reset_in(value: str) -> timedelta | None:
+    """`x-ratelimit-reset-requests` is seconds, or a duration such as `1h2m3s`."""
+    try:
+        return timedelta(seconds=float(value))
+    except ValueError:
+        pass
+    try:
+        return parse_duration(value)
+    except ValueError:
+        return None
+
+
+def parse_rate_headers(headers: dict[str, str], limit: float, observed_at: datetime) -> PartialUsage:
+    """Remaining daily requests from `x-ratelimit-*-requests`; the tier limit fills in a missing limit header."""
+    remaining = headers.get("x-ratelimit-remaining-requests")
+    if remaining is None:
+        return PartialUsage(messages=[msg("unexpected_response", provider="GitHub Models", detail=": no rate-limit headers")])
+    limit = float(headers.get("x-ratelimit-limit-requests") or limit)
+    left = max(0.0, float(remaining))
+    reset = _reset_in(headers.get("x-ratelimit-reset-requests", ""))
+    return PartialUsage(
+        session_used_pct=Percent.ratio(limit - left, limit),
+        session_reset_at=observed_at + reset if reset is not None else None,
+        details={
+            "session_window": "daily",
+            "requests_remaining": int(left),
+            "requests_limit": int(limit),
+        },
+        as_of=observed_at,
+    )
+
+
+class GitHubModelsAdapter(ProviderAdapter):
+    """GitHub Models free-tier requests per day for one model tier.
+
+    The limits only show up in the inference API's response headers, so
+    the only way to read them is a one-token completion for `model`. That
+    request counts against the same daily quota, so probing is opt-in:
+    with `probe = true` it runs at most every `probe_minutes` (default 6
+    hours, i.e. 4 of the day's requests) and the cached headers are reused
+    in between. Set `tier` to match the model: low, high, or embedding.
+    """
+
+    name = ProviderName.GITHUB_MODELS
+
+    def __init__(self, cache_path: Path | None = None, fetch: FetchHeaders | None = None, now: datetime | None = None) -> None:
+        self.cache_path = cache_path or cache_dir() / "github_models.json"
+        self.fetch = fetch or fetch_headers
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def _cached(self, model: str, now: datetime, max_age: timedelta) -> tuple[dict[str, str], datetime] | None:
+        try:
+            raw = json.loads(self.cache_path.read_text())
+            at = datetime.fromisoformat(raw["at"])
+        except (OSError, ValueError, KeyError, TypeError):
+            return None
+        if raw.get("model") != model or not timedelta(0) <= now - at <= max_age:
+            return None
+        return dict(raw.get("headers", {})), at
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        tier = option_str(cfg, "tier", "low")
+        if tier not in DAILY_REQUESTS:
+            return PartialUsage(messages=[msg("unknown_tier", tier=tier, choices=", ".join(DAILY_REQUESTS))])
+        model = option_str(cfg, "model", DEFAULT_MODEL)
+        now = self.now or datetime.now()
+        probe_minutes = option_float(cfg, "probe_minutes", DEFAULT_PROBE_MINUTES)
+
+        cached = self._cached(model, now, timedelta(minutes=probe_minutes))
+        if cached is None:
+            if cfg.options.get("probe") is not True:
+                return PartialUsage(messages=[msg("probe_disabled", provider="GitHub Models", hint="providers.github_models.probe = true")])
+            key_env = option_str(cfg, "token_env", "GITHUB_TOKEN")
+            token = os.environ.get(key_env)
+            if not token:
+                return PartialUsage(messages=[msg("credentials_missing", provider="GitHub Models", hint=f"{key_env} to a token with models:read")])
+            base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+            body = json.dumps({"model": model, "max_tokens": 1, "messages": [{"role": "user", "content": "hi"}]}).encode()
+            try:
+                headers = self.fetch(f"{base}/inference/chat/completions", {"Authorization": f"Bearer {token}"}, body)
+            except ProviderError as exc:
+                return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+            cached = headers, now
+            try:
+                self.cache_path.parent.mkdir(parents=True, exist_ok=True)
+                self.cache_path.write_text(json.dumps({"at": now.isoformat(), "model": model, "headers": headers}))
+            except OSError:
+                pass  # probe again next time rather than fail the row
+
+        headers, observed_at = cached
+        try:
+            partial = parse_rate_headers(headers, DAILY_REQUESTS[tier], observed_at)
+        except ValueError as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="GitHub Models", detail=f": {exc}")])
+        if partial.details is not None:
+            partial.details.update({"model": model, "tier": tier})
+        return partial
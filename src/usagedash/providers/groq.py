@@ -0,0 +1,86 @@
+from __future__ import annotations
+
+import json
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.paths import xdg_state_home
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage, option_str
+
+
+def log_entry_time(entry: dict) -> datetime | None:
+    stamp = entry.get("created") or entry.get("timestamp")
+    try:
+        if isinstance(stamp, (int, float)):
+            return datetime.fromtimestamp(stamp, timezone.utc).replace(tzinfo=None)
+        parsed = datetime.fromisoformat(str(stamp).replace("Z", "+00:00"))
+    except (TypeError, ValueError, OverflowError):
+        return None
+    if parsed.tzinfo is not None:
+        parsed = parsed.astimezone(timezone.utc).replace(tzinfo=None)
+    return parsed
+
+
+def _entry_tokens(entry: dict) -> float:
+    usage = entry.get("usage") if isinstance(entry.get("usage"), dict) else entry
+    total = usage.get("total_tokens")
+    if isinstance(total, (int, float)):
+        return float(total)
+    return float(usage.get("prompt_tokens") or 0) + float(usage.get("completion_tokens") or 0)
+
+
+class GroqAdapter(ProviderAdapter):
+    """Groq daily token use, tallied from a local JSONL request log.
+
+    Groq's free tier limits tokens per day and resets at midnight UTC, but
+    exposes no usage endpoint; each log line is a chat completion response
+    (or any object with `created`/`timestamp` and `usage.total_tokens`).
+    Point `log_path` at the log your client writes and set
+    `daily_token_limit` for the model tier you use.
+    """
+
+    name = ProviderName.GROQ
+
+    def __init__(self, now: datetime | None = None) -> None:
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        path = Path(option_str(cfg, "log_path", str(xdg_state_home() / "groq/requests.jsonl"))).expanduser()
+        if not path.exists():
+            return PartialUsage(messages=[msg("missing_source_file", path=path)])
+
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        day_start = datetime(now.year, now.month, now.day)
+        tokens = 0.0
+        requests = 0
+        with path.open(errors="ignore") as fh:
+            for line in fh:
+                try:
+                    entry = json.loads(line)
+                except json.JSONDecodeError:
+                    continue
+                if not isinstance(entry, dict):
+                    continue
+                at = log_entry_time(entry)
+                if at is None or at < day_start:
+                    continue
+                tokens += _entry_tokens(entry)
+                requests += 1
+
+        limit = cfg.options.get("daily_token_limit")
+        pct = Percent.ratio(tokens, float(limit) if limit else None)
+        messages = [] if limit else [msg("limit_missing", option="daily_token_limit")]
+        return PartialUsage(
+            session_used_pct=pct,
+            session_reset_at=day_start + timedelta(days=1),
+            details={"session_window": "daily", "tokens_today": tokens, "requests_today": requests},
+            messages=messages,
+        )
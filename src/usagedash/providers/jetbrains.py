@@ -0,0 +1,105 @@
+from __future__ import annotations
+
+import json
+import os
+import sys
+import xml.etree.ElementTree as ET
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.paths import home_dir, xdg_config_home
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage, option_str
+from usagedash.providers.custom import parse_when
+
+QUOTA_FILE = "options/AIAssistantQuotaManager2.xml"
+
+
+def jetbrains_config_root() -> Path:
+    """Where JetBrains IDEs keep one config directory per product and version."""
+    if sys.platform == "darwin":
+        return home_dir() / "Library/Application Support/JetBrains"
+    if os.name == "nt":
+        return Path(os.environ.get("APPDATA", home_dir() / "AppData/Roaming")) / "JetBrains"
+    return xdg_config_home() / "JetBrains"
+
+
+class JetBrainsAdapter(ProviderAdapter):
+    """JetBrains AI Assistant cloud quota, as last cached by an IDE.
+
+    Every IDE (`IntelliJIdea2025.2`, `PyCharm2025.1`, ...) writes its own
+    copy of the quota state; the most recently written one wins, or set
+    `config_dir` to pin one. The quota is monthly, so it fills the weekly
+    slot.
+    """
+
+    name = ProviderName.JETBRAINS
+
+    def __init__(self, root: Path | None = None) -> None:
+        self.root = root or jetbrains_config_root()
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self._parse(cfg), cfg)
+
+    def _quota_file(self, cfg: ProviderConfig) -> Path | None:
+        pinned = option_str(cfg, "config_dir")
+        if pinned:
+            path = Path(pinned).expanduser() / QUOTA_FILE
+            return path if path.exists() else None
+        found = list(self.root.glob(f"*/{QUOTA_FILE}")) if self.root.is_dir() else []
+        return max(found, key=lambda p: p.stat().st_mtime) if found else None
+
+    def _parse(self, cfg: ProviderConfig) -> PartialUsage:
+        path = self._quota_file(cfg)
+        if path is None:
+            return PartialUsage(messages=[msg("missing_source_file", path=self.root / "*" / QUOTA_FILE)])
+        try:
+            options = _options(path)
+        except (OSError, ET.ParseError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="JetBrains", detail=f": {exc}")])
+        partial = parse_quota(options)
+        partial.as_of = datetime.fromtimestamp(path.stat().st_mtime)
+        partial.details = {**(partial.details or {}), "ide": path.parent.parent.name}
+        return partial
+
+
+def _options(path: Path) -> dict[str, object]:
+    """`<option name=... value=...>` pairs, with JSON values decoded."""
+    out: dict[str, object] = {}
+    for option in ET.parse(path).getroot().iter("option"):
+        name, value = option.get("name"), option.get("value")
+        if not name or value is None:
+            continue
+        try:
+            out[name] = json.loads(value)
+        except json.JSONDecodeError:
+            out[name] = value
+    return out
+
+
+def _float(value: object) -> float | None:
+    try:
+        return float(value) if value is not None and not isinstance(value, bool) else None
+    except (TypeError, ValueError):
+        return None
+
+
+def parse_quota(options: dict[str, object]) -> PartialUsage:
+    quota = options.get("quotaInfo")
+    if not isinstance(quota, dict):
+        return PartialUsage(messages=[msg("unexpected_response", provider="JetBrains", detail=": no quotaInfo")])
+    current = _float(quota.get("current"))
+    maximum = _float(quota.get("maximum"))
+    pct = Percent.ratio(current, maximum)
+
+    refill = options.get("nextRefill")
+    reset_at = parse_when(refill.get("next")) if isinstance(refill, dict) else None
+    return PartialUsage(
+        weekly_used_pct=pct,
+        weekly_reset_at=reset_at or parse_when(quota.get("until")),
+        details={"long_window": "monthly", "quota_used": current, "quota_limit": maximum, "quota_type": quota.get("type")},
+    )
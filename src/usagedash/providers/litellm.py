@@ -0,0 +1,92 @@
+from __future__ import annotations
+
+import os
+from dataclasses import replace
+from datetime import datetime, timezone
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import FetchJson, PartialUsage, ProviderAdapter, ProviderError, fetch_json, merge_usage, option_str
+
+DEFAULT_API_BASE = "http://localhost:4000"
+
+
+def _utc(value: object) -> datetime | None:
+    if not isinstance(value, str) or not value:
+        return None
+    try:
+        parsed = datetime.fromisoformat(value.replace("Z", "+00:00"))
+    except ValueError:
+        return None
+    if parsed.tzinfo is not None:
+        parsed = parsed.astimezone(timezone.utc).replace(tzinfo=None)
+    return parsed
+
+
+def parse_key_info(raw: object) -> PartialUsage:
+    """Spend against `max_budget` from the proxy's `/key/info` response."""
+    if not isinstance(raw, dict) or not isinstance(raw.get("info"), dict):
+        raise ValueError("expected an object with info")
+    info = raw["info"]
+    spend = float(info.get("spend") or 0.0)
+    budget = float(info["max_budget"]) if info.get("max_budget") else None
+    messages = [msg("spent", amount=f"${spend:,.2f}")]
+    if not budget:
+        messages.append(msg("no_max_budget"))
+    return PartialUsage(
+        weekly_used_pct=Percent.ratio(spend, budget),
+        weekly_reset_at=_utc(info.get("budget_reset_at")),
+        details={
+            "long_window": "budget",
+            "spend": round(spend, 4),
+            "max_budget": budget,
+            "budget_duration": info.get("budget_duration"),
+            "key_alias": info.get("key_alias") or "",
+        },
+        messages=messages,
+    )
+
+
+class LiteLLMAdapter(ProviderAdapter):
+    """Spend per virtual key on a LiteLLM proxy, against each key's budget.
+
+    Every environment variable in `key_envs` (default `LITELLM_API_KEY`)
+    holds one key and becomes one row, labeled with the key's alias. The
+    proxy tracks spend itself, so this only reads `/key/info` on
+    `api_base`; the budget and its reset come from the key's settings.
+    """
+
+    name = ProviderName.LITELLM
+
+    def __init__(self, fetch: FetchJson | None = None) -> None:
+        self.fetch = fetch or fetch_json
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def collect_all(self, cfg: ProviderConfig) -> list[ProviderSnapshot]:
+        envs = [str(e) for e in cfg.options.get("key_envs") or []]
+        if len(envs) < 2 or cfg.parser_mode == "manual":
+            return [self.collect(cfg)]
+        rows: list[ProviderSnapshot] = []
+        for env in envs:
+            snap = self.collect(replace(cfg, options={**cfg.options, "key_envs": [env]}))
+            snap.account = str(snap.details.get("key_alias") or env)
+            rows.append(snap)
+        return rows
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        envs = [str(e) for e in cfg.options.get("key_envs") or ["LITELLM_API_KEY"]]
+        key = os.environ.get(envs[0])
+        if not key:
+            return PartialUsage(messages=[msg("credentials_missing", provider="LiteLLM", hint=envs[0])])
+        base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        try:
+            return parse_key_info(self.fetch(f"{base}/key/info", {"Authorization": f"Bearer {key}"}))
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (KeyError, TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="LiteLLM", detail=f": {exc}")])
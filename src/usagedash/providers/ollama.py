@@ -0,0 +1,120 @@
+from __future__ import annotations
+
+import json
+import re
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import ProviderName, ProviderSnapshot, SourceKind, StatusKind
+from usagedash.paths import home_dir
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_float,
+    option_str,
+)
+
+DEFAULT_API_BASE = "http://127.0.0.1:11434"
+# [GIN] 2026/10/17 - 09:14:03 | 200 | 2.1s | 127.0.0.1 | POST "/api/chat"
+GIN_RE = re.compile(r'\[GIN\] (\d{4}/\d{2}/\d{2} - \d{2}:\d{2}:\d{2}) \| +\d+ \|.*POST +"/api/(?:chat|generate)"')
+
+
+class OllamaAdapter(ProviderAdapter):
+    """Local Ollama activity as an informational row: there is no quota to fill.
+
+    Loaded models come from `/api/ps`, request counts from the server log,
+    and token counts from an optional JSONL log of responses (`response_log`,
+    using their `eval_count`/`prompt_eval_count`). Everything covers the
+    last `session_hours` (default 5).
+    """
+
+    name = ProviderName.OLLAMA
+
+    def __init__(self, fetch: FetchJson | None = None, now: datetime | None = None) -> None:
+        self.fetch = fetch or fetch_json
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        now = self.now or datetime.now()
+        since = now - timedelta(hours=option_float(cfg, "session_hours", 5.0))
+        base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        try:
+            raw = self.fetch(f"{base}/api/ps", {})
+        except ProviderError as exc:
+            return merge_usage(self.name, PartialUsage(messages=[msg("fetch_failed", error=exc)]), cfg)
+        models = raw.get("models", []) if isinstance(raw, dict) else []
+        loaded = [str(m.get("name") or m.get("model")) for m in models if isinstance(m, dict)]
+        vram = sum(int(m.get("size_vram") or 0) for m in models if isinstance(m, dict))
+
+        log = Path(option_str(cfg, "server_log", str(home_dir() / ".ollama/logs/server.log"))).expanduser()
+        requests = count_requests(log, since)
+        response_log = option_str(cfg, "response_log")
+        generated, prompt = count_tokens(Path(response_log).expanduser(), since) if response_log else (None, None)
+
+        snap = merge_usage(self.name, None, cfg)
+        snap.status = StatusKind.OK
+        snap.source = SourceKind.PARSED
+        snap.details = {
+            "informational": True,
+            "loaded_models": loaded,
+            "vram_bytes": vram,
+            "session_requests": requests,
+            "session_generated_tokens": generated,
+            "session_prompt_tokens": prompt,
+        }
+        notes = [
+            msg("models_loaded", count=len(loaded), models=", ".join(loaded), vram=f"{vram / 2**30:.1f}")
+            if loaded
+            else msg("no_models_loaded")
+        ]
+        if requests is not None:
+            notes.append(msg("session_requests", requests=requests))
+        if generated is not None:
+            notes.append(msg("tokens_generated", tokens=f"{generated:,}"))
+        snap.diagnostics = notes
+        snap.messages = [n.render() for n in notes]
+        return snap
+
+
+def count_requests(log: Path, since: datetime) -> int | None:
+    """Chat/generate requests in the server log since `since`, or None without a log."""
+    try:
+        text = log.read_text(errors="ignore")
+    except OSError:
+        return None
+    count = 0
+    for m in GIN_RE.finditer(text):
+        if datetime.strptime(m.group(1), "%Y/%m/%d - %H:%M:%S") >= since:
+            count += 1
+    return count
+
+
+def count_tokens(path: Path, since: datetime) -> tuple[int | None, int | None]:
+    """(generated, prompt) tokens from final responses logged since `since`."""
+    generated = prompt = 0
+    try:
+        fh = path.open(errors="ignore")
+    except OSError:
+        return None, None
+    with fh:
+        for line in fh:
+            try:
+                entry = json.loads(line)
+                at = datetime.fromisoformat(str(entry["created_at"]).replace("Z", "+00:00"))
+            except (json.JSONDecodeError, KeyError, TypeError, ValueError):
+                continue
+            if at.tzinfo is not None:
+                at = at.astimezone().replace(tzinfo=None)
+            if at < since or not entry.get("done", True):
+                continue
+            generated += int(entry.get("eval_count") or 0)
+            prompt += int(entry.get("prompt_eval_count") or 0)
+    return generated, prompt
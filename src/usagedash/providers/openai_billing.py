@@ -0,0 +1,73 @@
+from __future__ import annotations
+
+import os
+from datetime import date, datetime, timezone
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import ProviderName, ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_float,
+    option_str,
+)
+from usagedash.providers.billing import month_start, spend_usage
+
+DEFAULT_API_BASE = "https://api.openai.com"
+DEFAULT_KEY_ENV = "OPENAI_ADMIN_KEY"
+
+
+class OpenAIBillingAdapter(ProviderAdapter):
+    """Month-to-date OpenAI API spend from the organization costs endpoint.
+
+    Needs an admin key (`api_key_env`, default OPENAI_ADMIN_KEY) and reports
+    against `monthly_budget` in USD.
+    """
+
+    name = ProviderName.OPENAI
+
+    def __init__(self, fetch: FetchJson | None = None, now: datetime | None = None) -> None:
+        self.fetch = fetch or fetch_json
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self._parse(cfg), cfg)
+
+    def _parse(self, cfg: ProviderConfig) -> PartialUsage:
+        key_env = option_str(cfg, "api_key_env", DEFAULT_KEY_ENV)
+        key = os.environ.get(key_env)
+        if not key:
+            return PartialUsage(messages=[msg("credentials_missing", provider="OpenAI", hint=f"{key_env} to an admin key")])
+
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        start = int(month_start(now).replace(tzinfo=timezone.utc).timestamp())
+        base = option_str(cfg, "api_base", DEFAULT_API_BASE).rstrip("/")
+        url = f"{base}/v1/organization/costs?start_time={start}&bucket_width=1d&limit=31"
+        daily: dict[date, float] = {}
+        page: str | None = None
+        try:
+            while True:
+                raw = self.fetch(url + (f"&page={page}" if page else ""), {"Authorization": f"Bearer {key}"})
+                if not isinstance(raw, dict):
+                    return PartialUsage(messages=[msg("unexpected_response", provider="OpenAI costs", detail="")])
+                for bucket in raw.get("data", []):
+                    day = datetime.fromtimestamp(int(bucket["start_time"]), timezone.utc).date()
+                    for result in bucket.get("results", []):
+                        amount = (result.get("amount") or {}).get("value") or 0.0
+                        daily[day] = daily.get(day, 0.0) + float(amount)
+                page = raw.get("next_page") if raw.get("has_more") else None
+                if not page:
+                    break
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (KeyError, TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="OpenAI costs", detail=f": {exc}")])
+
+        return spend_usage(daily, option_float(cfg, "monthly_budget") or None, now)
@@ -0,0 +1,49 @@
+from __future__ import annotations
+
+from usagedash.config import ProviderConfig, provider_from_dict
+from usagedash.messages import msg
+from usagedash.models import ProviderName, ProviderSnapshot
+from usagedash.plugins import list_provider_plugins
+from usagedash.providers.base import OptionError, PartialUsage, ProviderAdapter, merge_usage, option_float
+from usagedash.providers.custom import DEFAULT_TIMEOUT_SECONDS, run_command
+
+
+class PluginAdapter(ProviderAdapter):
+    """External providers: one row per `usagedash-provider-<name>` on PATH.
+
+    The protocol: usagedash runs the executable with no arguments and reads
+    one JSON object from stdout with any of `session_used_pct`,
+    `session_reset_at`, `weekly_used_pct`, `weekly_reset_at` (ISO 8601 or
+    Unix seconds), `details` and `messages`. A non-zero exit marks the row
+    as an error with the last stderr line. Nothing runs until
+    `[providers.plugin] enabled = true`; `[providers.plugin.<name>]` then
+    takes the usual `enabled`, `manual`, `parser_mode` and `display_name`
+    keys plus `timeout`.
+    """
+
+    name = ProviderName.PLUGIN
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        rows = self.collect_all(cfg)
+        return rows[0] if rows else merge_usage(self.name, None, cfg)
+
+    def collect_all(self, cfg: ProviderConfig) -> list[ProviderSnapshot]:
+        rows: list[ProviderSnapshot] = []
+        for plugin, path in sorted(list_provider_plugins().items()):
+            raw = cfg.options.get(plugin)
+            entry = provider_from_dict(raw if isinstance(raw, dict) else {})
+            if not entry.enabled:
+                continue
+            partial = None
+            if not self.run_commands:
+                partial = PartialUsage(messages=[msg("command_skipped", command=path)])
+            elif entry.parser_mode != "manual":
+                try:
+                    partial = run_command([path], option_float(entry, "timeout", DEFAULT_TIMEOUT_SECONDS))
+                except OptionError as exc:
+                    partial = PartialUsage(messages=[exc.diagnostic(f"plugin.{plugin}")])
+            snap = merge_usage(self.name, partial, entry)
+            snap.account = plugin
+            snap.display_name = entry.display_name
+            rows.append(snap)
+        return rows
@@ -0,0 +1,61 @@
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+
+@dataclass(frozen=True)
+class ModelPrice:
+    """USD per million tokens. Cache writes are the 5-minute TTL rate."""
+
+    input: float
+    output: float
+    cache_write: float
+    cache_read: float
+
+
+# Substring of the model id -> price, most specific first. Estimates only:
+# override or extend with `[providers.claude.pricing."<substring>"]`.
+DEFAULT_PRICES: list[tuple[str, ModelPrice]] = [
+    ("opus-4-5", ModelPrice(5.0, 25.0, 6.25, 0.50)),
+    ("opus", ModelPrice(15.0, 75.0, 18.75, 1.50)),
+    ("sonnet", ModelPrice(3.0, 15.0, 3.75, 0.30)),
+    ("haiku-4-5", ModelPrice(1.0, 5.0, 1.25, 0.10)),
+    ("3-5-haiku", ModelPrice(0.80, 4.0, 1.0, 0.08)),
+    ("haiku", ModelPrice(0.25, 1.25, 0.30, 0.03)),
+]
+
+
+def price_table(overrides: object = None) -> list[tuple[str, ModelPrice]]:
+    """DEFAULT_PRICES with `overrides` (substring -> {input, output, ...}) checked first."""
+    table: list[tuple[str, ModelPrice]] = []
+    if isinstance(overrides, dict):
+        for key, raw in overrides.items():
+            if not isinstance(raw, dict):
+                continue
+            inp = float(raw.get("input", 0.0))
+            table.append((str(key), ModelPrice(
+                input=inp,
+                output=float(raw.get("output", 0.0)),
+                cache_write=float(raw.get("cache_write", inp * 1.25)),
+                cache_read=float(raw.get("cache_read", inp * 0.1)),
+            )))
+    return table + DEFAULT_PRICES
+
+
+def price_for(model: str, table: list[tuple[str, ModelPrice]]) -> ModelPrice | None:
+    lowered = model.lower()
+    return next((price for key, price in table if key in lowered), None)
+
+
+def usage_cost(usage: dict, price: ModelPrice) -> float:
+    """Estimated USD for one Anthropic `usage` object."""
+    def n(key: str) -> float:
+        value = usage.get(key)
+        return float(value) if isinstance(value, (int, float)) else 0.0
+
+    return (
+        n("input_tokens") * price.input
+        + n("output_tokens") * price.output
+        + n("cache_creation_input_tokens") * price.cache_write
+        + n("cache_read_input_tokens") * price.cache_read
+    ) / 1_000_000
@@ -0,0 +1,165 @@
+from __future__ import annotations
+
+import os
+from dataclasses import dataclass, field
+from datetime import date, datetime, timezone
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import ProviderSnapshot
+from usagedash.providers.base import (
+    FetchJson,
+    PartialUsage,
+    ProviderAdapter,
+    ProviderError,
+    fetch_json,
+    merge_usage,
+    option_float,
+    option_str,
+)
+from usagedash.providers.billing import month_start, spend_usage
+
+
+@dataclass
+class DailyUsage:
+    cost: dict[date, float] = field(default_factory=dict)
+    tokens: dict[date, float] = field(default_factory=dict)
+
+    def add(self, day: date, cost: float = 0.0, tokens: float = 0.0) -> None:
+        if cost:
+            self.cost[day] = self.cost.get(day, 0.0) + cost
+        if tokens:
+            self.tokens[day] = self.tokens.get(day, 0.0) + tokens
+
+
+def _number(entry: dict, keys: tuple[str, ...]) -> float:
+    for key in keys:
+        value = entry.get(key)
+        if isinstance(value, (int, float, str)) and value != "":
+            try:
+                return float(value)
+            except ValueError:
+                continue
+    return 0.0
+
+
+def parse_daily_entries(raw: object) -> DailyUsage:
+    """The common shape: a list (or `data` list) of per-day entries with a date
+    and any of `cost`/`amount` and `tokens`/`total_tokens`, or input/output tokens.
+    Entries may repeat a day once per `model`."""
+    entries = raw.get("data", []) if isinstance(raw, dict) else raw
+    usage = DailyUsage()
+    if not isinstance(entries, list):
+        raise ValueError("expected a list of daily usage entries")
+    for entry in entries:
+        if not isinstance(entry, dict):
+            continue
+        stamp = entry.get("date") or entry.get("day") or entry.get("start_time")
+        if isinstance(stamp, (int, float)):
+            day = datetime.fromtimestamp(stamp, timezone.utc).date()
+        else:
+            day = datetime.fromisoformat(str(stamp).replace("Z", "+00:00")).date()
+        tokens = _number(entry, ("tokens", "total_tokens"))
+        if not tokens:
+            tokens = _number(entry, ("input_tokens", "prompt_tokens")) + _number(
+                entry, ("output_tokens", "completion_tokens")
+            )
+        usage.add(day, cost=_number(entry, ("cost", "amount", "total_cost")), tokens=tokens)
+    return usage
+
+
+class UsageApiAdapter(ProviderAdapter):
+    """Base for API-key providers that report month-to-date usage over HTTP.
+
+    Subclasses set the endpoint defaults and, when the response differs from
+    the common daily-entries shape, override `parse_usage`. Every default can
+    be overridden per install with `api_base`, `usage_path`, `balance_path`,
+    and `api_key_env`. With a balance path the remaining credit is reported too.
+    """
+
+    title: str
+    default_api_base: str
+    default_key_env: str
+    # Formatted with `start` and `end` (ISO dates) for the current month.
+    default_usage_path: str
+    # Formatted like the usage path minus the dates; empty when there is none.
+    default_balance_path = ""
+    currency = "usd"
+
+    def __init__(self, fetch: FetchJson | None = None, now: datetime | None = None) -> None:
+        self.fetch = fetch or fetch_json
+        self.now = now
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self.parse(cfg), cfg)
+
+    def headers(self, key: str) -> dict[str, str]:
+        return {"Authorization": f"Bearer {key}"}
+
+    def parse_usage(self, raw: object) -> DailyUsage:
+        return parse_daily_entries(raw)
+
+    def parse_balance(self, raw: object) -> float:
+        """Remaining credit from the balance endpoint, in `currency` units."""
+        if not isinstance(raw, dict):
+            raise ValueError("expected an object")
+        for key in ("balance", "remaining", "credit_balance", "total_balance"):
+            if key in raw:
+                return float(raw[key])
+        raise KeyError("balance")
+
+    def path_params(self, cfg: ProviderConfig) -> dict[str, str]:
+        """Extra fields for formatting `usage_path` beyond `start` and `end`."""
+        return {}
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        key_env = option_str(cfg, "api_key_env", self.default_key_env)
+        key = os.environ.get(key_env)
+        if not key:
+            return PartialUsage(messages=[msg("credentials_missing", provider=self.title, hint=key_env)])
+
+        now = self.now or datetime.now(timezone.utc).replace(tzinfo=None)
+        base = option_str(cfg, "api_base", self.default_api_base).rstrip("/")
+        path = option_str(cfg, "usage_path", self.default_usage_path)
+        url = base + path.format(
+            start=month_start(now).date().isoformat(), end=now.date().isoformat(), **self.path_params(cfg)
+        )
+        try:
+            usage = self.parse_usage(self.fetch(url, self.headers(key)))
+        except ProviderError as exc:
+            return PartialUsage(messages=[msg("fetch_failed", error=exc)])
+        except (KeyError, TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider=self.title, detail=f": {exc}")])
+
+        token_cap = option_float(cfg, "monthly_token_cap") or None
+        budget = option_float(cfg, "monthly_budget") or None
+        currency = option_str(cfg, "currency", self.currency)
+        partial = spend_usage(
+            usage.cost,
+            budget,
+            now,
+            currency=currency,
+            daily_tokens=usage.tokens,
+            token_cap=token_cap,
+        )
+        balance_path = option_str(cfg, "balance_path", self.default_balance_path)
+        if balance_path:
+            self._add_balance(partial, base + balance_path.format(**self.path_params(cfg)), key, currency)
+        return partial
+
+    def _add_balance(self, partial: PartialUsage, url: str, key: str, currency: str) -> None:
+        messages = partial.messages if partial.messages is not None else []
+        try:
+            balance = self.parse_balance(self.fetch(url, self.headers(key)))
+        except ProviderError as exc:
+            messages.append(msg("fetch_failed", error=exc))
+            return
+        except (KeyError, TypeError, ValueError) as exc:
+            messages.append(msg("unexpected_response", provider=self.title, detail=f": {exc}"))
+            return
+        symbol = "$" if currency.lower() == "usd" else f"{currency.upper()} "
+        details = partial.details if partial.details is not None else {}
+        details["remaining_credits"] = round(balance, 2)
+        messages.insert(0, msg("credits_remaining", amount=f"{symbol}{balance:,.2f}"))
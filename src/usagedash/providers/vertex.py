@@ -0,0 +1,12 @@
+from __future__ import annotations
+
+from usagedash.models import ProviderName
+from usagedash.providers.cloud_quota import CloudQuotaAdapter
+
+
+class VertexAdapter(CloudQuotaAdapter):
+    """Vertex AI per-minute and per-day model quotas for a Google Cloud project."""
+
+    name = ProviderName.VERTEX
+    title = "Vertex AI"
+    service = "aiplatform.googleapis.com"
@@ -0,0 +1,35 @@
+from __future__ import annotations
+
+import os
+import sqlite3
+import sys
+from pathlib import Path
+
+from usagedash.paths import home_dir, xdg_config_home
+
+
+def state_db_path(app: str) -> Path:
+    """The global `state.vscdb` of a VS Code fork (`Cursor`, `Windsurf`, ...)."""
+    if sys.platform == "darwin":
+        base = home_dir() / "Library/Application Support"
+    elif os.name == "nt":
+        base = Path(os.environ.get("APPDATA", home_dir() / "AppData/Roaming"))
+    else:
+        base = xdg_config_home()
+    return base / app / "User/globalStorage/state.vscdb"
+
+
+def read_item(db: Path, key: str) -> str | None:
+    """One `ItemTable` value, or None when the database or key is missing."""
+    if not db.exists():
+        return None
+    try:
+        # Read-only so a running editor never sees its database locked by us.
+        conn = sqlite3.connect(f"file:{db}?mode=ro", uri=True)
+        try:
+            row = conn.execute("SELECT value FROM ItemTable WHERE key = ?", (key,)).fetchone()
+        finally:
+            conn.close()
+    except sqlite3.Error:
+        return None
+    return str(row[0]) if row and row[0] else None
@@ -0,0 +1,74 @@
+from __future__ import annotations
+
+import json
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import Percent, ProviderName, ProviderSnapshot
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage
+from usagedash.providers.vscdb import read_item, state_db_path
+
+PLAN_KEY = "windsurf.settings.cachedPlanInfo"
+
+
+class WindsurfAdapter(ProviderAdapter):
+    """Windsurf premium prompt credits for the current billing cycle.
+
+    Read from the plan info the editor caches in its `state.vscdb`, so it is
+    only as fresh as the editor's last check. Credits fill the weekly slot,
+    labeled monthly, and reset at the cycle end the editor recorded.
+    """
+
+    name = ProviderName.WINDSURF
+
+    def __init__(self, state_db: Path | None = None) -> None:
+        self.state_db = state_db or state_db_path("Windsurf")
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        if cfg.parser_mode == "manual":
+            return merge_usage(self.name, None, cfg)
+        return merge_usage(self.name, self._parse(), cfg)
+
+    def _parse(self) -> PartialUsage:
+        raw = read_item(self.state_db, PLAN_KEY)
+        if raw is None:
+            return PartialUsage(messages=[msg("missing_source_file", path=self.state_db)])
+        try:
+            plan = json.loads(raw)
+            return parse_windsurf_plan(plan)
+        except (json.JSONDecodeError, AttributeError, TypeError, ValueError) as exc:
+            return PartialUsage(messages=[msg("unexpected_response", provider="Windsurf", detail=f": {exc}")])
+
+
+def _ms(value: object) -> datetime | None:
+    if not isinstance(value, (int, float, str)) or value == "":
+        return None
+    return datetime.fromtimestamp(float(value) / 1000.0)
+
+
+def parse_windsurf_plan(plan: dict) -> PartialUsage:
+    usage = plan.get("usage") or {}
+    total = float(usage.get("messages") or 0)
+    used = float(usage.get("usedMessages") or 0)
+    flex_left = float(usage.get("flexCredits") or 0) - float(usage.get("usedFlexCredits") or 0)
+    pct = Percent.ratio(used, total) if total > 0 else None
+    messages = []
+    # Counts are stored in hundredths of a credit.
+    if total > 0:
+        messages.append(msg("prompt_credits_used", used=f"{used / 100:,.0f}", total=f"{total / 100:,.0f}"))
+    if flex_left > 0:
+        messages.append(msg("addon_credits_left", amount=f"{flex_left / 100:,.0f}"))
+    return PartialUsage(
+        weekly_used_pct=pct,
+        weekly_reset_at=_ms(plan.get("endTimestamp")),
+        details={
+            "long_window": "monthly",
+            "plan": plan.get("planName"),
+            "prompt_credits_used": used / 100,
+            "prompt_credits_total": total / 100,
+        },
+        messages=messages,
+        as_of=_ms(plan.get("lastUpdated")),
+    )
@@ -0,0 +1,34 @@
+from __future__ import annotations
+
+from usagedash.config import ProviderConfig
+from usagedash.messages import msg
+from usagedash.models import ProviderName
+from usagedash.providers.base import PartialUsage, option_str
+from usagedash.providers.usage_api import UsageApiAdapter
+
+
+class XaiAdapter(UsageApiAdapter):
+    """xAI month-to-date spend plus remaining prepaid credits.
+
+    Both come from the management API, which needs a management key and the
+    team id (`team_id` option) rather than the inference key.
+    """
+
+    name = ProviderName.XAI
+    title = "xAI"
+    default_api_base = "https://management-api.x.ai"
+    default_key_env = "XAI_MANAGEMENT_KEY"
+    default_usage_path = "/v1/billing/teams/{team}/usage?start_date={start}&end_date={end}"
+    default_balance_path = "/v1/billing/teams/{team}/prepaid/balance"
+
+    def path_params(self, cfg: ProviderConfig) -> dict[str, str]:
+        return {"team": option_str(cfg, "team_id", "")}
+
+    def parse_balance(self, raw: object) -> float:
+        # Cents, negative while credit remains.
+        return -float(raw["total"]["val"]) / 100.0  # type: ignore[index]
+
+    def parse(self, cfg: ProviderConfig) -> PartialUsage:
+        if not option_str(cfg, "team_id", ""):
+            return PartialUsage(messages=[msg("credentials_missing", provider=self.title, hint="providers.xai.team_id")])
+        return super().parse(cfg)
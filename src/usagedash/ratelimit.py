@@ -0,0 +1,91 @@
+from __future__ import annotations
+
+import json
+import time
+from collections.abc import Iterator
+from contextlib import contextmanager
+from contextvars import ContextVar
+from dataclasses import dataclass
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.durations import parse_duration
+from usagedash.locking import file_lock
+
+
+def parse_budget(spec: str) -> tuple[float, float]:
+    """Parse `"<calls>/<period>"` (e.g. `30/1h`) into (capacity, refill per second)."""
+    count, sep, period = spec.partition("/")
+    if not sep:
+        raise ValueError(f"invalid rate limit {spec!r} (expected e.g. 30/1h)")
+    capacity = float(count)
+    seconds = parse_duration(period).total_seconds()
+    if capacity <= 0 or seconds <= 0:
+        raise ValueError(f"invalid rate limit {spec!r}")
+    return capacity, capacity / seconds
+
+
+def ratelimit_state_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).parent / "ratelimit.json"
+
+
+def acquire(cfg: Config, key: str, now: float | None = None) -> bool:
+    """Take one token from the bucket for endpoint `key`; False means skip the call.
+
+    Buckets live on disk under a file lock so watch loops, the tray, and
+    one-off CLI invocations all draw from the same budget. Read-only runs
+    (dry runs, --in-memory) neither charge nor persist them.
+    """
+    spec = cfg.rate_limits.get(key) or cfg.rate_limits.get("default")
+    if not spec or cfg.general.read_only:
+        return True
+    capacity, refill = parse_budget(spec)
+    now = time.time() if now is None else now
+
+    path = ratelimit_state_path(cfg)
+    with file_lock(path.with_name(path.name + ".lock")):
+        try:
+            state = json.loads(path.read_text())
+        except (OSError, json.JSONDecodeError):
+            state = {}
+        bucket = state.get(key) or {"tokens": capacity, "updated": now}
+        elapsed = max(0.0, now - float(bucket["updated"]))
+        tokens = min(capacity, float(bucket["tokens"]) + elapsed * refill)
+        allowed = tokens >= 1.0
+        if allowed:
+            tokens -= 1.0
+        state[key] = {"tokens": tokens, "updated": now}
+        path.write_text(json.dumps(state))
+    return allowed
+
+
+@dataclass
+class RateGate:
+    """The budget outbound requests draw from while one provider collects."""
+
+    cfg: Config
+    key: str
+    denied: int = 0
+
+
+_gate: ContextVar[RateGate | None] = ContextVar("rate_gate", default=None)
+
+
+@contextmanager
+def rate_limited(cfg: Config, key: str) -> Iterator[RateGate]:
+    """Charge every request made through the shared HTTP helpers to bucket `key`."""
+    gate = RateGate(cfg, key)
+    token = _gate.set(gate)
+    try:
+        yield gate
+    finally:
+        _gate.reset(token)
+
+
+def allow_request() -> str | None:
+    """Take a token for the request about to go out; the bucket's key when it is empty."""
+    gate = _gate.get()
+    if gate is None or acquire(gate.cfg, gate.key):
+        return None
+    gate.denied += 1
+    return gate.key
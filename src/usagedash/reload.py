@@ -0,0 +1,71 @@
+from __future__ import annotations
+
+import signal
+import threading
+from collections.abc import Callable
+from pathlib import Path
+
+from usagedash.config import CONFIG_PATH, Config, load_config
+from usagedash.oplog import log_event
+
+
+class ConfigWatcher:
+    """The running config, reloaded when config.toml changes or on request.
+
+    Long-running loops call `poll()` once per cycle. A changed file is
+    loaded and passed through `prepare` (env overrides, command-line flags);
+    if either fails the old config stays in effect and the error is logged,
+    so a typo never takes a running dashboard or server down. The listen
+    address of `serve` is fixed at start and not reloaded.
+    """
+
+    def __init__(self, cfg: Config, path: Path = CONFIG_PATH, prepare: Callable[[Config], None] | None = None) -> None:
+        self.current = cfg
+        self.path = path
+        self.prepare = prepare
+        self.last_error: str | None = None
+        self._lock = threading.RLock()
+        self._requested = threading.Event()
+        self._stamp = self._stat()
+
+    def _stat(self) -> tuple[int, int] | None:
+        try:
+            st = self.path.stat()
+        except OSError:
+            return None
+        return st.st_mtime_ns, st.st_size
+
+    def request(self) -> None:
+        """Reload on the next `poll()` even if the file looks unchanged."""
+        self._requested.set()
+
+    def poll(self) -> Config:
+        with self._lock:
+            stamp = self._stat()
+            if stamp == self._stamp and not self._requested.is_set():
+                return self.current
+            self._requested.clear()
+            self.reload()
+            return self.current
+
+    def reload(self) -> bool:
+        """Load and validate now; True when the new config took effect."""
+        with self._lock:
+            self._stamp = self._stat()
+            try:
+                cfg = load_config(self.path, create=False)
+                if self.prepare is not None:
+                    self.prepare(cfg)
+            except (OSError, ValueError, TypeError, KeyError) as exc:  # TOMLDecodeError is a ValueError
+                self.last_error = f"{type(exc).__name__}: {exc}"
+                log_event(self.current, "config_reload_failed", f"{self.path}: {exc}; keeping the previous config", "warning")
+                return False
+            self.current = cfg
+            self.last_error = None
+            log_event(cfg, "config_reloaded", str(self.path))
+            return True
+
+    def install_sighup(self) -> None:
+        """`kill -HUP` requests a reload, as with most daemons; no-op on Windows."""
+        if hasattr(signal, "SIGHUP") and threading.current_thread() is threading.main_thread():
+            signal.signal(signal.SIGHUP, lambda *_: self.request())
@@ -0,0 +1,71 @@
+from __future__ import annotations
+
+from dataclasses import dataclass
+
+from usagedash.events import UsageEvent
+from usagedash.providers.pricing import ModelPrice, price_for, price_table, usage_cost
+
+UNTAGGED = "(untagged)"
+
+
+@dataclass
+class ReportRow:
+    key: str
+    events: int = 0
+    input_tokens: int = 0
+    output_tokens: int = 0
+    # Estimated from list prices; turns of unpriced models add nothing.
+    cost_usd: float = 0.0
+
+    @property
+    def tokens(self) -> int:
+        return self.input_tokens + self.output_tokens
+
+
+def event_cost(event: UsageEvent, prices: list[tuple[str, ModelPrice]]) -> float:
+    """Estimated USD for one turn, or 0 when its model has no known price."""
+    price = price_for(event.model, prices)
+    if price is None:
+        return 0.0
+    return usage_cost({
+        "input_tokens": event.input_tokens,
+        "output_tokens": event.output_tokens,
+        "cache_creation_input_tokens": event.cache_creation_tokens,
+        "cache_read_input_tokens": event.cache_read_tokens,
+    }, price)
+
+
+def group_keys(event: UsageEvent, group_by: str, tag: str | None = None) -> list[str]:
+    """The report groups `event` counts under; a tagged event may be in several."""
+    if group_by == "project":
+        return [event.project]
+    if group_by == "model":
+        return [event.model]
+    if group_by == "provider":
+        return [event.provider]
+    if group_by == "tag":
+        if tag is not None:
+            return [f"{tag}={event.tags[tag]}" if tag in event.tags else UNTAGGED]
+        # Without a specific key an event counts once under each of its tags.
+        return [f"{k}={v}" for k, v in sorted(event.tags.items())] or [UNTAGGED]
+    raise ValueError(f"unknown grouping: {group_by}")
+
+
+def group_events(
+    events: list[UsageEvent],
+    group_by: str,
+    tag: str | None = None,
+    prices: list[tuple[str, ModelPrice]] | None = None,
+) -> list[ReportRow]:
+    """Totals per group; `prices` defaults to the built-in table (see `providers.claude.pricing`)."""
+    prices = prices if prices is not None else price_table()
+    rows: dict[str, ReportRow] = {}
+    for event in events:
+        cost = event_cost(event, prices)
+        for key in group_keys(event, group_by, tag):
+            row = rows.setdefault(key, ReportRow(key=key))
+            row.events += 1
+            row.input_tokens += event.input_tokens
+            row.output_tokens += event.output_tokens
+            row.cost_usd += cost
+    return sorted(rows.values(), key=lambda r: r.tokens, reverse=True)
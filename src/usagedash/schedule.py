@@ -0,0 +1,72 @@
+from __future__ import annotations
+
+import shlex
+from datetime import datetime, timedelta
+
+from usagedash.models import UsageSnapshot
+
+
+def upcoming_resets(
+    snapshot: UsageSnapshot,
+    providers: list[str] | None = None,
+    windows: tuple[str, ...] = ("session", "weekly"),
+    now: datetime | None = None,
+) -> list[tuple[str, str, datetime]]:
+    """Future reset instants from the snapshot, sorted, one per provider/window."""
+    now = now or datetime.now()
+    out: list[tuple[str, str, datetime]] = []
+    for p in snapshot.providers:
+        if providers and p.provider.value not in providers:
+            continue
+        for window, reset_at in (("session", p.session_reset_at), ("weekly", p.weekly_reset_at)):
+            if window in windows and reset_at is not None and reset_at > now:
+                out.append((p.provider.value, window, reset_at))
+    out.sort(key=lambda r: r[2])
+    return out
+
+
+def render_systemd(name: str, command: str, times: list[datetime]) -> dict[str, str]:
+    """Return `{filename: contents}` for a oneshot service plus its timer."""
+    service = (
+        "[Unit]\n"
+        f"Description={name} (scheduled by usagedash around quota resets)\n"
+        "\n"
+        "[Service]\n"
+        "Type=oneshot\n"
+        # systemd expands %-specifiers in ExecStart; %% is a literal percent.
+        f"ExecStart=/bin/sh -c {shlex.quote(command).replace('%', '%%')}\n"
+    )
+    calendars = "".join(f"OnCalendar={t:%Y-%m-%d %H:%M:%S}\n" for t in times)
+    timer = (
+        "[Unit]\n"
+        f"Description=Run {name} right after usage limits reset\n"
+        "\n"
+        "[Timer]\n"
+        f"{calendars}"
+        "Persistent=true\n"
+        "\n"
+        "[Install]\n"
+        "WantedBy=timers.target\n"
+    )
+    return {f"{name}.service": service, f"{name}.timer": timer}
+
+
+def render_cron(command: str, times: list[datetime]) -> str:
+    # cron has no year field; regenerate after each reset so entries don't repeat next year.
+    # An unescaped % ends the command in crontab (the rest becomes stdin).
+    command = command.replace("%", "\\%")
+    return "".join(f"{t.minute} {t.hour} {t.day} {t.month} * {command}\n" for t in times)
+
+
+def schedule_times(
+    resets: list[tuple[str, str, datetime]],
+    delay: timedelta,
+) -> list[datetime]:
+    seen: set[datetime] = set()
+    times: list[datetime] = []
+    for _, _, reset_at in resets:
+        t = (reset_at + delay).replace(microsecond=0)
+        if t not in seen:
+            seen.add(t)
+            times.append(t)
+    return times
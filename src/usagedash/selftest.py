@@ -0,0 +1,178 @@
+from __future__ import annotations
+
+import copy
+import os
+import tempfile
+from collections.abc import Iterator
+from contextlib import contextmanager
+from dataclasses import dataclass
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+from usagedash.config import AlertRule, Config, ProviderConfig, StoreConfig
+from usagedash.heartbeat import last_success
+from usagedash.models import ProviderName, ProviderSnapshot, UsageSnapshot
+from usagedash.providers import ADAPTERS
+from usagedash.providers.base import PartialUsage, ProviderAdapter, merge_usage
+from usagedash.sinks import SinkError, deliver
+from usagedash.snapshot import read_snapshot, snapshot_to_json
+from usagedash.store import open_store
+
+
+@dataclass
+class StageResult:
+    stage: str
+    ok: bool
+    detail: str
+
+
+class MockAdapter(ProviderAdapter):
+    """Canned usage that goes through the same merge path as real adapters."""
+
+    name = ProviderName.CODEX
+
+    def collect(self, cfg: ProviderConfig) -> ProviderSnapshot:
+        now = datetime.now(timezone.utc).replace(tzinfo=None)
+        partial = PartialUsage(
+            session_used_pct=42.0,
+            session_reset_at=now + timedelta(hours=2),
+            weekly_used_pct=85.0,
+            weekly_reset_at=now + timedelta(days=3),
+            details={"selftest": True},
+            messages=[],
+        )
+        return merge_usage(self.name, partial, cfg)
+
+
+def run_selftest(cfg: Config, sinks: bool = True) -> list[StageResult]:
+    """Run one real collection cycle against a mock provider in a throwaway state dir.
+
+    The cycle goes through `pipeline.run_cycle` and every bus subscriber, so
+    a broken stage fails here the way it would in the daemon. Nothing under
+    the real state dir, history store or log sinks is written. Configured
+    alert sinks get one test message each unless `sinks` is False.
+    """
+    from usagedash.pipeline import BUS, run_cycle
+
+    results: list[StageResult] = []
+
+    def stage(name: str, fn) -> object:
+        try:
+            detail = fn()
+        except Exception as exc:  # report every stage, whatever breaks
+            results.append(StageResult(name, False, f"{type(exc).__name__}: {exc}"))
+            return None
+        results.append(StageResult(name, True, str(detail or "")))
+        return detail
+
+    with tempfile.TemporaryDirectory(prefix="usagedash-selftest-") as tmp:
+        test_cfg = copy.deepcopy(cfg)
+        test_cfg.general.state_file = str(Path(tmp) / "latest.json")
+        test_cfg.general.windows_state_path = str(Path(tmp) / "mirror" / "latest.json")
+        test_cfg.general.read_only = False
+        test_cfg.store = StoreConfig(fsync=cfg.store.fsync)
+        test_cfg.flags.dir = ""
+        test_cfg.heartbeat.ping_url = ""
+        test_cfg.log.sinks = []
+        test_cfg.alerts = [AlertRule(name="selftest", provider="codex", warn_pct=80.0)]
+        test_cfg.maintenance = []
+        for provider in test_cfg.providers.values():
+            provider.enabled = False
+        test_cfg.providers["codex"] = ProviderConfig()
+        holder: dict[str, UsageSnapshot] = {}
+        before = BUS.stats()
+
+        def collect() -> str:
+            with _mock_adapters():
+                holder["snapshot"] = run_cycle(test_cfg)
+            if not BUS.drain(5.0):
+                raise TimeoutError("queued subscribers did not finish")
+            provider = holder["snapshot"].providers[0]
+            return f"{provider.status.value}, session {provider.session_used_pct:.0f}%"
+
+        def bus() -> str:
+            after = BUS.stats()
+            failed = [n for n, s in after.items() if s["failed"] > before.get(n, {}).get("failed", 0)]
+            if failed:
+                raise RuntimeError(f"subscribers failed: {', '.join(failed)}")
+            return ", ".join(after)
+
+        def state() -> str:
+            back = read_snapshot(test_cfg.general.state_file)
+            if snapshot_to_json(back) != snapshot_to_json(holder["snapshot"]):
+                raise ValueError("state file does not round-trip")
+            return f"{test_cfg.general.state_format} state file round-trips"
+
+        def mirror() -> str:
+            back = read_snapshot(test_cfg.general.windows_state_path)
+            fields = test_cfg.mirror.fields or None
+            if snapshot_to_json(back, fields=fields) != snapshot_to_json(holder["snapshot"], fields=fields):
+                raise ValueError("mirror differs from state file")
+            return _check_configured_mirror(cfg)
+
+        def history() -> str:
+            with open_store(test_cfg) as store:
+                count = len(store.snapshots())
+            if count != 1:
+                raise ValueError(f"expected 1 history row, found {count}")
+            return "sqlite append and read"
+
+        def alerts() -> str:
+            with open_store(test_cfg) as store:
+                fired = [a for a in store.alerts() if a.get("rule") == "selftest"]
+            if len(fired) != 1:
+                raise ValueError(f"expected 1 alert event, got {len(fired)}")
+            return f"{fired[0]['level']} at {fired[0]['value']:.0f}%"
+
+        def heartbeat() -> str:
+            at = last_success(test_cfg)
+            if at is None:
+                raise ValueError("no heartbeat written")
+            return f"last success {at.isoformat(timespec='seconds')}"
+
+        stage("collect", collect)
+        if "snapshot" in holder:
+            stage("bus", bus)
+            stage("state", state)
+            stage("mirror", mirror)
+            stage("history", history)
+            stage("alerts", alerts)
+            stage("heartbeat", heartbeat)
+
+    if sinks:
+        for sink in sorted({s for r in cfg.alerts for s in (r.sink, r.critical_sink) if s}):
+            def send(sink: str = sink) -> str:
+                try:
+                    deliver(sink, "usagedash selftest: ignore this message", {"selftest": True})
+                except SinkError as exc:
+                    raise RuntimeError(str(exc)) from exc
+                return "delivered"
+
+            stage(f"sink {sink}", send)
+    return results
+
+
+@contextmanager
+def _mock_adapters() -> Iterator[None]:
+    """Collect only MockAdapter, as codex, for the duration."""
+    saved = dict(ADAPTERS)
+    ADAPTERS.clear()
+    ADAPTERS["codex"] = MockAdapter
+    try:
+        yield
+    finally:
+        ADAPTERS.clear()
+        ADAPTERS.update(saved)
+
+
+def _check_configured_mirror(cfg: Config) -> str:
+    """The real mirror's directory must be writable; an existing file must parse."""
+    path = Path(cfg.general.windows_state_path)
+    if path.exists():
+        read_snapshot(path)
+        return f"{path} parses"
+    if not path.parent.is_dir():
+        return f"{path.parent} does not exist; mirror skipped"
+    if not os.access(path.parent, os.W_OK):
+        raise PermissionError(f"{path.parent} is not writable")
+    return f"{path.parent} writable"
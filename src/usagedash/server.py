@@ -0,0 +1,282 @@
+from __future__ import annotations
+
+import hmac
+import json
+import os
+import secrets
+import signal
+import threading
+from collections import OrderedDict
+from datetime import datetime, timedelta
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+from urllib.parse import parse_qs
+
+from usagedash.bus import render_bus_metrics
+from usagedash.capabilities import capabilities
+from usagedash.config import Config
+from usagedash.compression import compress, negotiate_encoding
+from usagedash.delta import DeltaLog
+from usagedash.federation import federated_metrics
+from usagedash.heartbeat import last_success
+from usagedash.oplog import log_event
+from usagedash.models import UsageSnapshot
+from usagedash.pause import pause, resume
+from usagedash.pipeline import BUS, refresh_interval, run_cycle
+from usagedash.protocol import SUPPORTED_VERSIONS, Message, ProtocolError, decode, encode, error, negotiate
+from usagedash.reload import ConfigWatcher
+from usagedash.snapshot import snapshot_to_json
+
+# Sent with every request after `hello`; names the negotiated session.
+SESSION_HEADER = "X-UsageDash-Session"
+# Sessions kept per server; the oldest is forgotten past this.
+MAX_SESSIONS = 64
+# Requests that change what the daemon does need the token in ipc.token.
+PRIVILEGED = frozenset({"pause", "resume", "reload"})
+
+
+def ipc_token_path(cfg: Config) -> Path:
+    return Path(cfg.general.state_file).expanduser().parent / "ipc.token"
+
+
+def ensure_ipc_token(cfg: Config) -> str:
+    """The token clients present for PRIVILEGED requests, created owner-only on first start.
+
+    Kept across restarts so scripts that read it once keep working.
+    """
+    path = ipc_token_path(cfg)
+    token = read_ipc_token(cfg)
+    if token:
+        return token
+    token = secrets.token_hex(16)
+    path.parent.mkdir(parents=True, exist_ok=True)
+    fd = os.open(path, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+    with os.fdopen(fd, "w") as fh:
+        fh.write(token + "\n")
+    return token
+
+
+def read_ipc_token(cfg: Config) -> str | None:
+    try:
+        return ipc_token_path(cfg).read_text().strip() or None
+    except OSError:
+        return None
+
+
+class _State:
+    def __init__(self, token: str | None = None) -> None:
+        self.lock = threading.Lock()
+        self.snapshot: UsageSnapshot | None = None
+        self.last_success: datetime | None = None
+        self.last_error: str | None = None
+        self.deltas = DeltaLog()
+        self.token = token
+        # session id -> negotiated protocol version
+        self.sessions: OrderedDict[str, int] = OrderedDict()
+
+
+def _collect_loop(watcher: ConfigWatcher, state: _State, stop: threading.Event) -> None:
+    cfg = watcher.current
+    while not stop.is_set():
+        try:
+            cfg = watcher.poll()
+            snap = run_cycle(cfg)
+            state.deltas.record(snap)
+            with state.lock:
+                state.snapshot = snap
+                state.last_success = datetime.now()
+                state.last_error = None
+        except Exception as exc:  # keep serving the last good snapshot
+            with state.lock:
+                state.last_error = f"{type(exc).__name__}: {exc}"
+            log_event(cfg, "collect_failed", state.last_error, "error")
+        stop.wait(refresh_interval(cfg))
+
+
+def _handler(watcher: ConfigWatcher, state: _State) -> type[BaseHTTPRequestHandler]:
+    class Handler(BaseHTTPRequestHandler):
+        def do_GET(self) -> None:
+            cfg = watcher.current
+            stale_after = timedelta(minutes=cfg.heartbeat.stale_minutes)
+            path, _, query = self.path.partition("?")
+            with state.lock:
+                snap = state.snapshot
+                last = state.last_success or last_success(cfg)
+                collect_error = state.last_error
+            if path == "/healthz":
+                healthy = last is not None and datetime.now() - last <= stale_after
+                body = {
+                    "status": "ok" if healthy else "stale",
+                    "last_success": last.isoformat() if last else None,
+                    "last_error": collect_error,
+                }
+                self._send(200 if healthy else 503, "application/json", json.dumps(body))
+                return
+            if path == "/capabilities":
+                self._send(200, "application/json", json.dumps(capabilities(cfg)))
+                return
+            if snap is None:
+                self._send(503, "text/plain", "no snapshot collected yet\n")
+                return
+            if path == "/metrics":
+                body = federated_metrics(cfg, snap) + render_bus_metrics(BUS)
+                self._send(200, "text/plain; version=0.0.4", body)
+                return
+            if path in ("/", "/snapshot"):
+                since = parse_qs(query).get("since", [""])[0]
+                if since:
+                    self._send_delta(since)
+                    return
+                self._send(200, "application/json", snapshot_to_json(snap, pretty=cfg.general.pretty_state))
+                return
+            self._send(404, "text/plain", "not found\n")
+
+        def do_POST(self) -> None:
+            if self.path.split("?", 1)[0] != "/ipc":
+                self._send(404, "text/plain", "not found\n")
+                return
+            try:
+                length = int(self.headers.get("Content-Length") or 0)
+                if length < 0:
+                    raise ValueError(length)
+            except ValueError:
+                self._send(400, "application/json", encode(error("invalid Content-Length")))
+                return
+            try:
+                request = decode(self.rfile.read(length))
+            except ProtocolError as exc:
+                self._send(400, "application/json", encode(error(str(exc))))
+                return
+            code, reply = _handle_ipc(
+                watcher, state, request, self.headers.get(SESSION_HEADER), self.headers.get("Authorization")
+            )
+            self._send(code, "application/json", encode(reply))
+
+        def _send_delta(self, since: str) -> None:
+            # Unknown or expired bases get the full snapshot, tagged with its seq.
+            try:
+                body = state.deltas.since(int(since))
+            except ValueError:
+                body = None
+            if body is None:
+                body = state.deltas.full()
+            if body is None:
+                self._send(503, "text/plain", "no snapshot collected yet\n")
+                return
+            self._send(200, "application/json", json.dumps(body, separators=(",", ":")))
+
+        def _send(self, code: int, content_type: str, body: str) -> None:
+            data = body.encode("utf-8")
+            encoding = negotiate_encoding(self.headers.get("Accept-Encoding"), len(data))
+            if encoding is not None:
+                data = compress(data, encoding)
+            self.send_response(code)
+            self.send_header("Content-Type", content_type)
+            if encoding is not None:
+                self.send_header("Content-Encoding", encoding)
+                self.send_header("Vary", "Accept-Encoding")
+            self.send_header("Content-Length", str(len(data)))
+            self.end_headers()
+            self.wfile.write(data)
+
+        def log_message(self, format: str, *args: object) -> None:
+            # Probes hit /healthz every few seconds; keep container logs quiet.
+            pass
+
+    return Handler
+
+
+def _handle_ipc(
+    watcher: ConfigWatcher,
+    state: _State,
+    request: Message,
+    session: str | None = None,
+    authorization: str | None = None,
+) -> tuple[int, Message]:
+    """Answer one protocol message with an HTTP status; replies use the request's version.
+
+    Every request but `hello` names the session `hello` opened and must use
+    the version negotiated there. PRIVILEGED requests also need
+    `Authorization: Bearer <ipc.token>`: anything on the machine can reach a
+    local port, but only the user can read the token file.
+    """
+    cfg = watcher.current
+    v = request.version
+    if request.type == "hello":
+        try:
+            version = negotiate([int(x) for x in request.payload.get("versions", [v])])
+        except (ProtocolError, TypeError, ValueError) as exc:
+            return 400, error(str(exc), v)
+        session = secrets.token_hex(8)
+        with state.lock:
+            state.sessions[session] = version
+            while len(state.sessions) > MAX_SESSIONS:
+                state.sessions.popitem(last=False)
+        return 200, Message(type="welcome", version=version, payload={
+            "version": version,
+            "session": session,
+            "supported": list(SUPPORTED_VERSIONS),
+            "capabilities": capabilities(cfg),
+        })
+    with state.lock:
+        negotiated = state.sessions.get(session or "")
+    if negotiated is None:
+        return 400, error("no session: send hello first", v)
+    if negotiated != v:
+        return 400, error(f"session negotiated protocol v{negotiated}, request uses v{v}", v)
+    if request.type in PRIVILEGED:
+        if cfg.general.read_only:
+            return 400, error(f"{request.type} is disabled in read-only mode", v)
+        scheme, _, presented = (authorization or "").partition(" ")
+        if state.token is None or scheme != "Bearer" or not hmac.compare_digest(presented, state.token):
+            return 401, error(f"{request.type} needs the token from {ipc_token_path(cfg)}", v)
+    if request.type == "get_snapshot":
+        with state.lock:
+            snap = state.snapshot
+        if snap is None:
+            return 400, error("no snapshot collected yet", v)
+        return 200, Message(type="snapshot", version=v, payload={"snapshot": json.loads(snapshot_to_json(snap))})
+    if request.type == "reload":
+        if not watcher.reload():
+            return 400, error(f"config not reloaded: {watcher.last_error}", v)
+        return 200, Message(type="ok", version=v, payload={"reloaded": True})
+    if request.type in ("pause", "resume"):
+        if request.type == "resume":
+            return 200, Message(type="ok", version=v, payload={"was_paused": resume(cfg)})
+        until = request.payload.get("until")
+        try:
+            paused = pause(cfg, datetime.fromisoformat(until) if until else None)
+        except (TypeError, ValueError) as exc:
+            return 400, error(f"invalid until: {exc}", v)
+        return 200, Message(type="ok", version=v, payload={"until": paused.until})
+    return 400, error(f"{request.type} is not a request", v)
+
+
+def parse_bind(bind: str) -> tuple[str, int]:
+    host, sep, port = bind.rpartition(":")
+    if not sep:
+        return "0.0.0.0", int(bind)
+    return host or "0.0.0.0", int(port)
+
+
+def serve(watcher: ConfigWatcher, bind: str) -> None:
+    """Collect in the background and serve /healthz, /metrics, /snapshot, and POST /ipc."""
+    cfg = watcher.current
+    state = _State(None if cfg.general.read_only else ensure_ipc_token(cfg))
+    stop = threading.Event()
+    collector = threading.Thread(target=_collect_loop, args=(watcher, state, stop), daemon=True)
+    collector.start()
+    server = ThreadingHTTPServer(parse_bind(bind), _handler(watcher, state))
+    # Containers stop with SIGTERM; shut down cleanly instead of dying mid-write.
+    signal.signal(signal.SIGTERM, lambda *_: threading.Thread(target=server.shutdown).start())
+    log_event(cfg, "start", f"serving on {bind}")
+    try:
+        server.serve_forever()
+    except KeyboardInterrupt:
+        pass
+    finally:
+        stop.set()
+        server.server_close()
+        # Give an in-flight collection a moment to finish writing state.
+        collector.join(timeout=5)
+        log_event(cfg, "stop", "server stopped")
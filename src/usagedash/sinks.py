@@ -0,0 +1,58 @@
+from __future__ import annotations
+
+import json
+import os
+import subprocess
+import sys
+import urllib.request
+
+
+class SinkError(RuntimeError):
+    pass
+
+
+def deliver(sink: str, message: str, payload: dict[str, object] | None = None) -> None:
+    """Send `message` to a sink spec: `stderr`, `command:<shell cmd>`, or `webhook:<url>`."""
+    kind, _, target = sink.partition(":")
+    body = dict(payload or {})
+    body.setdefault("text", message)
+
+    if kind == "stderr":
+        print(message, file=sys.stderr)
+        return
+
+    if kind == "command":
+        if not target:
+            raise SinkError("command sink requires a command")
+        env = dict(os.environ, USAGEDASH_ALERT_MESSAGE=message)
+        proc = subprocess.run(
+            target,
+            shell=True,
+            input=json.dumps(body),
+            text=True,
+            env=env,
+            capture_output=True,
+            timeout=30,
+        )
+        if proc.returncode != 0:
+            raise SinkError(f"command exited {proc.returncode}: {proc.stderr.strip()}")
+        return
+
+    if kind == "webhook":
+        if not target:
+            raise SinkError("webhook sink requires a url")
+        req = urllib.request.Request(
+            target,
+            data=json.dumps(body).encode("utf-8"),
+            headers={"Content-Type": "application/json"},
+            method="POST",
+        )
+        try:
+            with urllib.request.urlopen(req, timeout=10) as resp:
+                if resp.status >= 400:
+                    raise SinkError(f"webhook returned HTTP {resp.status}")
+        except OSError as exc:
+            raise SinkError(f"webhook failed: {exc}") from exc
+        return
+
+    raise SinkError(f"unknown sink: {sink}")
@@ -2,25 +2,139 @@ from __future__ import annotations
 
 from dataclasses import asdict
 from datetime import datetime, timezone
+from enum import Enum
 from pathlib import Path
 import json
 
-from usagedash.config import Config
-from usagedash.models import UsageSnapshot
-from usagedash.providers import ClaudeAdapter, CodexAdapter, GeminiAdapter
+from usagedash.atomic import write_atomic
+from usagedash.compression import compress, decompress
+from usagedash.config import Config, active_maintenance
+from usagedash.messages import Diagnostic, msg
+from usagedash.models import SNAPSHOT_SCHEMA_VERSION, ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.processes import running_agents
+from usagedash.providers import ADAPTERS
+from usagedash.providers.base import OptionError
+from usagedash.ratelimit import rate_limited
 
 
-def build_snapshot(cfg: Config) -> UsageSnapshot:
+def build_snapshot(cfg: Config, run_commands: bool = True) -> UsageSnapshot:
     providers = []
+    previous = _previous_rows(cfg)
 
-    if cfg.providers["codex"].enabled:
-        providers.append(CodexAdapter().collect(cfg.providers["codex"]))
-    if cfg.providers["claude"].enabled:
-        providers.append(ClaudeAdapter().collect(cfg.providers["claude"]))
-    if cfg.providers["gemini"].enabled:
-        providers.append(GeminiAdapter().collect(cfg.providers["gemini"]))
+    # By configured order, then id, so output never depends on which providers
+    # are enabled or the order adapters are registered in. Rows of one
+    # provider keep the adapter's order (accounts as configured).
+    ordered = sorted(ADAPTERS.items(), key=lambda item: (cfg.providers[item[0]].order, item[0]))
+    for name, adapter in ordered:
+        if not cfg.providers[name].enabled:
+            continue
+        window = active_maintenance(cfg, name)
+        if window is not None:
+            last = next((p for p in reversed(previous.values()) if p.provider.value == name), None)
+            providers.append(_in_maintenance(name, window.name, last))
+            continue
+        instance = adapter()
+        instance.state_dir = Path(cfg.general.state_file).expanduser().parent
+        instance.read_only = cfg.general.read_only
+        instance.run_commands = run_commands
+        with rate_limited(cfg, name) as gate:
+            try:
+                rows = instance.collect_all(cfg.providers[name])
+            except OptionError as exc:
+                rows = [_collect_failed(name, exc.diagnostic(name))]
+            except Exception as exc:
+                # One adapter's bug or surprise payload must not cost every
+                # other provider its row, the state file and history.
+                detail = f": {type(exc).__name__}: {exc}"
+                rows = [_collect_failed(name, msg("unexpected_response", provider=name, detail=detail))]
+        kept = [p for p in previous.values() if p.provider.value == name]
+        providers.extend(_rate_limited(kept, gate.key) if gate.denied and kept else rows)
 
-    return UsageSnapshot(generated_at=datetime.now(timezone.utc).replace(tzinfo=None), providers=providers)
+    if cfg.general.detect_processes:
+        counts = running_agents(cfg.general.detect_windows_processes)
+        for p in providers:
+            if p.provider.value in counts:
+                p.details = {**p.details, "running": counts[p.provider.value]}
+
+    now = datetime.now(timezone.utc).replace(tzinfo=None)
+    for p in providers:
+        # Rows may name themselves, e.g. custom providers; config wins when set.
+        p.display_name = cfg.providers[p.provider.value].display_name or p.display_name
+        carry_message_history(p, previous.get(p.key), now)
+    return UsageSnapshot(generated_at=now, providers=providers)
+
+
+def _previous_rows(cfg: Config) -> dict[str, ProviderSnapshot]:
+    try:
+        snap = read_snapshot(cfg.general.state_file)
+    except (OSError, ValueError, KeyError):
+        return {}
+    return {p.key: p for p in snap.providers}
+
+
+def carry_message_history(row: ProviderSnapshot, last: ProviderSnapshot | None, now: datetime) -> None:
+    """Date `row`'s diagnostics, continuing the count of ones `last` already had."""
+    seen = {d.identity: d for d in last.diagnostics} if last is not None else {}
+    for d in row.diagnostics:
+        before = seen.get(d.identity)
+        if before is not None and before.first_seen is not None:
+            d.first_seen, d.count, d.logged_at = before.first_seen, before.count + 1, before.logged_at
+        else:
+            d.first_seen, d.count = now, 1
+        d.last_seen = now
+
+
+def _in_maintenance(name: str, window: str, last: ProviderSnapshot | None) -> ProviderSnapshot:
+    """Carry the last collected values through a maintenance window without collecting."""
+    note = msg("maintenance", window=window)
+    if last is None:
+        return ProviderSnapshot(
+            provider=ProviderName(name),
+            status=StatusKind.PARTIAL,
+            messages=[note.render()],
+            diagnostics=[note],
+            details={"maintenance": window},
+        )
+    kept = [d for d in last.diagnostics if d.code != "maintenance"]
+    last.diagnostics = [*kept, note]
+    last.messages = [d.render() for d in last.diagnostics]
+    last.details = {**last.details, "maintenance": window}
+    return last
+
+
+def _collect_failed(name: str, diagnostic: Diagnostic) -> ProviderSnapshot:
+    return ProviderSnapshot(
+        provider=ProviderName(name),
+        status=StatusKind.ERROR,
+        messages=[diagnostic.render()],
+        diagnostics=[diagnostic],
+    )
+
+
+def _rate_limited(last: list[ProviderSnapshot], key: str) -> list[ProviderSnapshot]:
+    """Keep the last collected rows when the request budget ran out mid-collection."""
+    for row in last:
+        if not any(d.code == "rate_limited" for d in row.diagnostics):
+            row.diagnostics = [*row.diagnostics, msg("rate_limited", key=key)]
+        row.messages = [d.render() for d in row.diagnostics]
+    return last
+
+
+# Old serialized provider field name -> current name. Renaming a
+# ProviderSnapshot field adds the old name here so snapshots written by
+# earlier releases (state files, history, federation peers) still load; the
+# fixtures in tests/fixtures/snapshots fail the build when one is missing.
+PROVIDER_FIELD_ALIASES: dict[str, str] = {}
+
+
+def _serialized(snapshot: UsageSnapshot) -> dict:
+    data = asdict(snapshot)
+    for provider in data["providers"]:
+        # Adapters add details in whatever order their code runs; sort the keys
+        # so exported snapshots diff cleanly. Nested maps may be ranked on
+        # purpose and keep their order.
+        provider["details"] = dict(sorted(provider["details"].items()))
+    return {"schema_version": SNAPSHOT_SCHEMA_VERSION, **data}
 
 
 def _json_default(obj):
@@ -29,28 +143,121 @@ def _json_default(obj):
     raise TypeError(f"not serializable: {type(obj)!r}")
 
 
-def snapshot_to_json(snapshot: UsageSnapshot) -> str:
-    return json.dumps(asdict(snapshot), default=_json_default, indent=2)
+def snapshot_to_json(snapshot: UsageSnapshot, pretty: bool = True, fields: list[str] | None = None) -> str:
+    """JSON for the snapshot; `fields` reduces each provider as `filter_fields` does."""
+    data = _serialized(snapshot)
+    if fields:
+        data["providers"] = [filter_fields(p, fields) for p in data["providers"]]
+    if pretty:
+        return json.dumps(data, default=_json_default, indent=2)
+    return json.dumps(data, default=_json_default, separators=(",", ":"))
+
+
+# Kept in every reduced provider so readers can still tell rows apart.
+ALWAYS_KEPT = ("provider", "status")
+
+
+def filter_fields(provider: dict, fields: list[str]) -> dict:
+    """Keep only `fields` of a serialized provider; `details.<key>` keeps one detail."""
+    out = {k: provider[k] for k in ALWAYS_KEPT if k in provider}
+    details: dict[str, object] = {}
+    for name in fields:
+        if name.startswith("details."):
+            key = name.removeprefix("details.")
+            if key in provider.get("details", {}):
+                details[key] = provider["details"][key]
+        elif name in provider:
+            out[name] = provider[name]
+    if details:
+        out["details"] = {**out.get("details", {}), **details}
+    return out
+
+
+def _plain(obj):
+    if isinstance(obj, Enum):
+        return obj.value
+    if isinstance(obj, datetime):
+        return obj.isoformat()
+    if isinstance(obj, float):
+        return float(obj)  # Percent, which encoders keyed on exact types may not know
+    if isinstance(obj, dict):
+        return {k: _plain(v) for k, v in obj.items()}
+    if isinstance(obj, list):
+        return [_plain(v) for v in obj]
+    return obj
+
+
+def snapshot_to_cbor(snapshot: UsageSnapshot) -> bytes | None:
+    """CBOR encoding of the snapshot, or None when the optional cbor2 package is missing."""
+    try:
+        import cbor2  # type: ignore[import-not-found]
+    except ImportError:
+        return None
+    return cbor2.dumps(_plain(_serialized(snapshot)))
 
 
 def write_snapshot_files(cfg: Config, snapshot: UsageSnapshot) -> None:
-    body = snapshot_to_json(snapshot)
+    """Write the state file and its mirror, compact unless `general.pretty_state` is set.
+
+    These are rewritten every refresh, so the default keeps IO and mirror
+    traffic down; `usagedash snapshot` stays pretty for humans. With
+    `general.state_format = "cbor"` the internal state file is binary; the
+    mirror is always JSON because other tools read it, and carries only
+    `mirror.fields` when that is set, compressed per `mirror.compression`.
+    """
+    body = snapshot_to_json(snapshot, pretty=cfg.general.pretty_state)
 
-    state_file = Path(cfg.general.state_file)
-    state_file.parent.mkdir(parents=True, exist_ok=True)
-    state_file.write_text(body)
+    binary = snapshot_to_cbor(snapshot) if cfg.general.state_format == "cbor" else None
+    write_atomic(Path(cfg.general.state_file), binary if binary is not None else body, cfg.store.fsync)
 
     mirror = Path(cfg.general.windows_state_path)
-    mirror.parent.mkdir(parents=True, exist_ok=True)
-    mirror.write_text(body)
+    if cfg.mirror.fields:
+        body = snapshot_to_json(snapshot, pretty=cfg.general.pretty_state, fields=cfg.mirror.fields)
+    if cfg.mirror.compression != "none":
+        write_atomic(mirror, compress(body.encode("utf-8"), cfg.mirror.compression), cfg.store.fsync)
+        return
+    write_atomic(mirror, body, cfg.store.fsync)
 
 
 def read_snapshot(path: str | Path) -> UsageSnapshot:
-    raw = json.loads(Path(path).read_text())
+    """Read a state file in either encoding, compressed or not; JSON always starts with `{`."""
+    data = decompress(Path(path).read_bytes())
+    if data[:1] in (b"{", b" ", b"\n"):
+        return snapshot_from_dict(json.loads(data))
+    try:
+        import cbor2  # type: ignore[import-not-found]
+    except ImportError as exc:
+        raise ValueError(f"{path} is CBOR encoded; install usagedash[cbor] to read it") from exc
+    try:
+        return snapshot_from_dict(cbor2.loads(data))
+    except cbor2.CBORDecodeError as exc:
+        raise ValueError(f"{path}: {exc}") from exc
+
+
+def _optional_time(value: object) -> datetime | None:
+    return datetime.fromisoformat(value) if isinstance(value, str) and value else None
+
+
+def _upgrade_provider(item: dict) -> dict:
+    renamed = {PROVIDER_FIELD_ALIASES[k]: v for k, v in item.items() if k in PROVIDER_FIELD_ALIASES}
+    return {**item, **renamed} if renamed else item
+
+
+def snapshot_from_dict(raw: dict) -> UsageSnapshot:
+    """Load a snapshot serialized by this or any earlier release.
+
+    A newer schema may have removed or redefined fields, so it is refused
+    rather than read through the alias table as if it were current.
+    """
+    version = raw.get("schema_version", 1)
+    if not isinstance(version, int) or isinstance(version, bool) or version > SNAPSHOT_SCHEMA_VERSION:
+        raise ValueError(
+            f"snapshot schema v{version} is newer than this release reads (v{SNAPSHOT_SCHEMA_VERSION}); upgrade usagedash"
+        )
     providers = []
     from usagedash.models import ProviderSnapshot, ProviderName, StatusKind, SourceKind
 
-    for item in raw["providers"]:
+    for item in map(_upgrade_provider, raw["providers"]):
         providers.append(
             ProviderSnapshot(
                 provider=ProviderName(item["provider"]),
@@ -59,11 +266,30 @@ def read_snapshot(path: str | Path) -> UsageSnapshot:
                 session_reset_at=datetime.fromisoformat(item["session_reset_at"]) if item.get("session_reset_at") else None,
                 weekly_used_pct=item.get("weekly_used_pct"),
                 weekly_reset_at=datetime.fromisoformat(item["weekly_reset_at"]) if item.get("weekly_reset_at") else None,
-                source=SourceKind(item["source"]),
+                source=SourceKind(item.get("source", "manual")),
                 messages=item.get("messages", []),
                 details=item.get("details", {}),
-                updated_at=datetime.fromisoformat(item["updated_at"]),
+                updated_at=datetime.fromisoformat(item.get("updated_at") or raw["generated_at"]),
+                display_name=item.get("display_name", ""),
+                account=item.get("account", ""),
+                diagnostics=[
+                    Diagnostic(
+                        code=str(d["code"]),
+                        params={k: str(v) for k, v in d.get("params", {}).items()},
+                        severity=str(d.get("severity", "")),
+                        first_seen=_optional_time(d.get("first_seen")),
+                        last_seen=_optional_time(d.get("last_seen")),
+                        count=int(d.get("count", 1)),
+                        logged_at=_optional_time(d.get("logged_at")),
+                    )
+                    for d in item.get("diagnostics", [])
+                ],
             )
         )
 
-    return UsageSnapshot(generated_at=datetime.fromisoformat(raw["generated_at"]), providers=providers)
+    return UsageSnapshot(
+        generated_at=datetime.fromisoformat(raw["generated_at"]),
+        providers=providers,
+        paused=bool(raw.get("paused", False)),
+        paused_until=datetime.fromisoformat(raw["paused_until"]) if raw.get("paused_until") else None,
+    )
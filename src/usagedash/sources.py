@@ -0,0 +1,60 @@
+from __future__ import annotations
+
+from collections.abc import Callable
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.models import UsageSnapshot
+from usagedash.oplog import log_event
+from usagedash.paths import home_dir
+from usagedash.providers.codex import codex_home
+
+# Data directories of the agents whose transcripts are read locally, resolved
+# again every cycle so a changed HOME or CODEX_HOME is followed.
+SOURCE_ROOTS: dict[str, Callable[[], Path]] = {
+    "claude": lambda: home_dir() / ".claude",
+    "codex": codex_home,
+}
+
+
+def _identity(path: Path) -> tuple[int, int] | None:
+    try:
+        st = path.stat()
+    except OSError:
+        return None
+    return st.st_dev, st.st_ino
+
+
+class SourceWatchdog:
+    """Logs once when an agent's data directory disappears or comes back.
+
+    A reinstall or `rm -rf ~/.claude` leaves the row reporting a missing
+    file each cycle; that is the row's state, so the event log only gets
+    the transition, plus one event when the directory is back (or was
+    recreated in between). Adapters re-read their paths every cycle, so
+    the row recovers on the next collection without a restart.
+    """
+
+    def __init__(self, roots: dict[str, Callable[[], Path]] | None = None) -> None:
+        self.roots = roots if roots is not None else SOURCE_ROOTS
+        self._seen: dict[str, tuple[Path, tuple[int, int] | None]] = {}
+
+    def check(self, cfg: Config, snapshot: UsageSnapshot | None = None) -> None:
+        """Compare each enabled provider's directory with the one seen last cycle."""
+        for name, resolve in self.roots.items():
+            if name not in cfg.providers or not cfg.providers[name].enabled:
+                self._seen.pop(name, None)
+                continue
+            path = resolve()
+            identity = _identity(path)
+            previous = self._seen.get(name)
+            self._seen[name] = (path, identity)
+            if previous is None or previous[0] != path:
+                continue  # first look at this path: nothing to compare with
+            before = previous[1]
+            if before is not None and identity is None:
+                log_event(cfg, "source_missing", f"{name}: {path} disappeared; waiting for it to come back", "warning")
+            elif before is None and identity is not None:
+                log_event(cfg, "source_restored", f"{name}: {path} is back")
+            elif before is not None and identity != before:
+                log_event(cfg, "source_restored", f"{name}: {path} was recreated")
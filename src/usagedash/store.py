@@ -0,0 +1,343 @@
+from __future__ import annotations
+
+import json
+import socket
+import sqlite3
+from abc import ABC, abstractmethod
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import Config
+from usagedash.models import UsageSnapshot
+from usagedash.snapshot import snapshot_from_dict, snapshot_to_json
+
+ALERT_FIELDS = ("fired_at", "rule", "level", "provider", "window", "value", "sink", "delivered", "error")
+# "window" is reserved in Postgres, so column names are always quoted there.
+_PG_ALERT_COLUMNS = ", ".join(f'"{k}"' for k in ALERT_FIELDS)
+
+
+class Store(ABC):
+    """Persistence for snapshot history and fired alerts.
+
+    Snapshot bounds compare against the UTC `generated_at`; alert bounds
+    against the local `fired_at`, matching how each is recorded.
+    """
+
+    @abstractmethod
+    def append_snapshot(self, snapshot: UsageSnapshot) -> None:
+        raise NotImplementedError
+
+    @abstractmethod
+    def snapshots(self, since: datetime | None = None, until: datetime | None = None) -> list[UsageSnapshot]:
+        raise NotImplementedError
+
+    @abstractmethod
+    def last_snapshot_at(self) -> datetime | None:
+        raise NotImplementedError
+
+    @abstractmethod
+    def append_alert(self, record: dict) -> None:
+        raise NotImplementedError
+
+    @abstractmethod
+    def alerts(self, since: datetime | None = None) -> list[dict]:
+        raise NotImplementedError
+
+    @abstractmethod
+    def prune(self, before: datetime) -> int:
+        """Delete snapshots generated before `before`; returns the number removed."""
+        raise NotImplementedError
+
+    def close(self) -> None:
+        pass
+
+    def __enter__(self) -> Store:
+        return self
+
+    def __exit__(self, *exc: object) -> None:
+        self.close()
+
+
+class MemoryStore(Store):
+    def __init__(self) -> None:
+        self._snapshots: list[UsageSnapshot] = []
+        self._alerts: list[dict] = []
+
+    def append_snapshot(self, snapshot: UsageSnapshot) -> None:
+        self._snapshots.append(snapshot)
+
+    def snapshots(self, since: datetime | None = None, until: datetime | None = None) -> list[UsageSnapshot]:
+        return [
+            s for s in self._snapshots
+            if (since is None or s.generated_at >= since) and (until is None or s.generated_at <= until)
+        ]
+
+    def last_snapshot_at(self) -> datetime | None:
+        return self._snapshots[-1].generated_at if self._snapshots else None
+
+    def append_alert(self, record: dict) -> None:
+        self._alerts.append(dict(record))
+
+    def alerts(self, since: datetime | None = None) -> list[dict]:
+        return [
+            dict(r) for r in self._alerts
+            if since is None or datetime.fromisoformat(r["fired_at"]) >= since
+        ]
+
+    def prune(self, before: datetime) -> int:
+        kept = [s for s in self._snapshots if s.generated_at >= before]
+        removed = len(self._snapshots) - len(kept)
+        self._snapshots = kept
+        return removed
+
+
+_SQLITE_SYNC = {"always": "FULL", "normal": "NORMAL", "off": "OFF"}
+
+
+class SqliteStore(Store):
+    def __init__(self, path: str | Path, read_only: bool = False, fsync: str = "normal") -> None:
+        if read_only:
+            self._conn = sqlite3.connect(f"{Path(path).resolve().as_uri()}?mode=ro", uri=True, timeout=10)
+            return
+        if str(path) != ":memory:":
+            Path(path).parent.mkdir(parents=True, exist_ok=True)
+        self._conn = sqlite3.connect(str(path), timeout=10)
+        # Write-ahead log: a torn write from a power cut is rolled back on the
+        # next open instead of corrupting history. synchronous=FULL fsyncs
+        # every commit; NORMAL only at checkpoints.
+        self._conn.execute("PRAGMA journal_mode=WAL")
+        self._conn.execute(f"PRAGMA synchronous={_SQLITE_SYNC.get(fsync, 'NORMAL')}")
+        self._conn.executescript(
+            """
+            CREATE TABLE IF NOT EXISTS snapshots (
+                generated_at TEXT NOT NULL,
+                body TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS snapshots_generated_at ON snapshots (generated_at);
+            CREATE TABLE IF NOT EXISTS alerts (
+                fired_at TEXT NOT NULL,
+                rule TEXT NOT NULL,
+                level TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                window TEXT NOT NULL,
+                value REAL NOT NULL,
+                sink TEXT NOT NULL,
+                delivered INTEGER NOT NULL,
+                error TEXT
+            );
+            CREATE INDEX IF NOT EXISTS alerts_fired_at ON alerts (fired_at);
+            """
+        )
+
+    def append_snapshot(self, snapshot: UsageSnapshot) -> None:
+        body = snapshot_to_json(snapshot, pretty=False)
+        with self._conn:
+            self._conn.execute(
+                "INSERT INTO snapshots (generated_at, body) VALUES (?, ?)",
+                (snapshot.generated_at.isoformat(), body),
+            )
+
+    def snapshots(self, since: datetime | None = None, until: datetime | None = None) -> list[UsageSnapshot]:
+        query = "SELECT body FROM snapshots WHERE 1=1"
+        params: list[str] = []
+        if since is not None:
+            query += " AND generated_at >= ?"
+            params.append(since.isoformat())
+        if until is not None:
+            query += " AND generated_at <= ?"
+            params.append(until.isoformat())
+        out: list[UsageSnapshot] = []
+        for (body,) in self._conn.execute(query + " ORDER BY generated_at", params):
+            try:
+                out.append(snapshot_from_dict(json.loads(body)))
+            except (json.JSONDecodeError, KeyError, TypeError, ValueError):
+                continue
+        return out
+
+    def last_snapshot_at(self) -> datetime | None:
+        row = self._conn.execute("SELECT MAX(generated_at) FROM snapshots").fetchone()
+        return datetime.fromisoformat(row[0]) if row and row[0] else None
+
+    def append_alert(self, record: dict) -> None:
+        values = [record.get(k) for k in ALERT_FIELDS]
+        with self._conn:
+            self._conn.execute(
+                f"INSERT INTO alerts ({', '.join(ALERT_FIELDS)}) VALUES ({', '.join('?' * len(ALERT_FIELDS))})",
+                values,
+            )
+
+    def alerts(self, since: datetime | None = None) -> list[dict]:
+        query = f"SELECT {', '.join(ALERT_FIELDS)} FROM alerts"
+        params: list[str] = []
+        if since is not None:
+            query += " WHERE fired_at >= ?"
+            params.append(since.isoformat())
+        rows = self._conn.execute(query + " ORDER BY fired_at", params)
+        out = [dict(zip(ALERT_FIELDS, row)) for row in rows]
+        for r in out:
+            r["delivered"] = bool(r["delivered"])
+        return out
+
+    def prune(self, before: datetime) -> int:
+        with self._conn:
+            cur = self._conn.execute("DELETE FROM snapshots WHERE generated_at < ?", (before.isoformat(),))
+        return cur.rowcount
+
+    def close(self) -> None:
+        self._conn.close()
+
+
+class PostgresStore(Store):
+    """Shared store for a team aggregation server; many hosts may write concurrently.
+
+    Requires the optional `postgres` extra (psycopg 3). Each snapshot is kept
+    whole plus one normalized row per provider, indexed for the per-host and
+    per-provider queries the combined dashboard runs.
+    """
+
+    def __init__(self, url: str, host: str | None = None) -> None:
+        try:
+            import psycopg  # type: ignore[import-not-found]
+        except ImportError as exc:
+            raise RuntimeError("postgres store requires: pip install 'usagedash[postgres]'") from exc
+        self.host = host or socket.gethostname()
+        self._conn = psycopg.connect(url, autocommit=True)
+        with self._conn.cursor() as cur:
+            cur.execute(
+                """
+                CREATE TABLE IF NOT EXISTS snapshots (
+                    id BIGSERIAL PRIMARY KEY,
+                    host TEXT NOT NULL,
+                    generated_at TIMESTAMP NOT NULL,
+                    body JSONB NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS snapshots_host_generated_at ON snapshots (host, generated_at);
+                CREATE TABLE IF NOT EXISTS provider_usage (
+                    snapshot_id BIGINT NOT NULL REFERENCES snapshots (id) ON DELETE CASCADE,
+                    host TEXT NOT NULL,
+                    provider TEXT NOT NULL,
+                    generated_at TIMESTAMP NOT NULL,
+                    status TEXT NOT NULL,
+                    session_used_pct DOUBLE PRECISION,
+                    weekly_used_pct DOUBLE PRECISION
+                );
+                CREATE INDEX IF NOT EXISTS provider_usage_lookup
+                    ON provider_usage (provider, host, generated_at);
+                CREATE TABLE IF NOT EXISTS alerts (
+                    host TEXT NOT NULL,
+                    fired_at TIMESTAMP NOT NULL,
+                    rule TEXT NOT NULL,
+                    level TEXT NOT NULL,
+                    provider TEXT NOT NULL,
+                    "window" TEXT NOT NULL,
+                    value DOUBLE PRECISION NOT NULL,
+                    sink TEXT NOT NULL,
+                    delivered BOOLEAN NOT NULL,
+                    error TEXT
+                );
+                CREATE INDEX IF NOT EXISTS alerts_host_fired_at ON alerts (host, fired_at);
+                """
+            )
+
+    def append_snapshot(self, snapshot: UsageSnapshot) -> None:
+        body = snapshot_to_json(snapshot, pretty=False)
+        with self._conn.transaction(), self._conn.cursor() as cur:
+            cur.execute(
+                "INSERT INTO snapshots (host, generated_at, body) VALUES (%s, %s, %s) RETURNING id",
+                (self.host, snapshot.generated_at, body),
+            )
+            snapshot_id = cur.fetchone()[0]
+            for p in snapshot.providers:
+                cur.execute(
+                    "INSERT INTO provider_usage (snapshot_id, host, provider, generated_at, status,"
+                    " session_used_pct, weekly_used_pct) VALUES (%s, %s, %s, %s, %s, %s, %s)",
+                    (
+                        snapshot_id,
+                        self.host,
+                        p.provider.value,
+                        snapshot.generated_at,
+                        p.status.value,
+                        p.session_used_pct,
+                        p.weekly_used_pct,
+                    ),
+                )
+
+    def snapshots(self, since: datetime | None = None, until: datetime | None = None) -> list[UsageSnapshot]:
+        query = "SELECT body FROM snapshots WHERE host = %s"
+        params: list[object] = [self.host]
+        if since is not None:
+            query += " AND generated_at >= %s"
+            params.append(since)
+        if until is not None:
+            query += " AND generated_at <= %s"
+            params.append(until)
+        with self._conn.cursor() as cur:
+            cur.execute(query + " ORDER BY generated_at", params)
+            rows = cur.fetchall()
+        out: list[UsageSnapshot] = []
+        for (body,) in rows:
+            try:
+                out.append(snapshot_from_dict(body if isinstance(body, dict) else json.loads(body)))
+            except (json.JSONDecodeError, KeyError, TypeError, ValueError):
+                continue
+        return out
+
+    def last_snapshot_at(self) -> datetime | None:
+        with self._conn.cursor() as cur:
+            cur.execute("SELECT MAX(generated_at) FROM snapshots WHERE host = %s", (self.host,))
+            row = cur.fetchone()
+        return row[0] if row else None
+
+    def append_alert(self, record: dict) -> None:
+        values = [self.host] + [record.get(k) for k in ALERT_FIELDS]
+        with self._conn.cursor() as cur:
+            cur.execute(
+                f"INSERT INTO alerts (host, {_PG_ALERT_COLUMNS}) VALUES ({', '.join(['%s'] * len(values))})",
+                values,
+            )
+
+    def alerts(self, since: datetime | None = None) -> list[dict]:
+        query = f"SELECT {_PG_ALERT_COLUMNS} FROM alerts WHERE host = %s"
+        params: list[object] = [self.host]
+        if since is not None:
+            query += " AND fired_at >= %s"
+            params.append(since)
+        with self._conn.cursor() as cur:
+            cur.execute(query + " ORDER BY fired_at", params)
+            rows = cur.fetchall()
+        out = [dict(zip(ALERT_FIELDS, row)) for row in rows]
+        for r in out:
+            r["fired_at"] = r["fired_at"].isoformat()
+        return out
+
+    def prune(self, before: datetime) -> int:
+        # Retention applies fleet-wide: whichever host prunes cleans up for all.
+        with self._conn.cursor() as cur:
+            cur.execute("DELETE FROM snapshots WHERE generated_at < %s", (before,))
+            return cur.rowcount
+
+    def close(self) -> None:
+        self._conn.close()
+
+
+def store_path(cfg: Config) -> Path:
+    if cfg.store.path:
+        return Path(cfg.store.path).expanduser()
+    return Path(cfg.general.state_file).parent / "usagedash.db"
+
+
+def open_store(cfg: Config) -> Store:
+    backend = cfg.store.backend
+    if backend == "sqlite":
+        path = store_path(cfg)
+        if cfg.general.read_only:
+            # Never create the database; an absent one just means no history yet.
+            return SqliteStore(path, read_only=True) if path.exists() else MemoryStore()
+        return SqliteStore(path, fsync=cfg.store.fsync)
+    if backend == "memory":
+        return MemoryStore()
+    if backend == "postgres":
+        if not cfg.store.url:
+            raise ValueError("store.url is required for the postgres backend")
+        return PostgresStore(cfg.store.url)
+    raise ValueError(f"unknown store backend: {backend}")
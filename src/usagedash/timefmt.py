@@ -0,0 +1,78 @@
+from __future__ import annotations
+
+import locale
+from datetime import datetime
+
+CLOCK_CHOICES = ("auto", "24h", "12h")
+
+_use_12h = False
+
+
+def configure(locale_name: str = "auto", clock: str = "auto") -> None:
+    """Apply `general.locale` / `general.clock` to every time rendered afterwards.
+
+    `auto` locale follows LC_TIME / LANG from the environment; `auto` clock
+    follows whatever the active locale's time format uses.
+    """
+    global _use_12h
+    try:
+        locale.setlocale(locale.LC_TIME, "" if locale_name in ("", "auto") else locale_name)
+    except locale.Error:
+        locale.setlocale(locale.LC_TIME, "C")
+
+    if clock == "12h":
+        _use_12h = True
+    elif clock == "24h":
+        _use_12h = False
+    else:
+        _use_12h = _locale_prefers_12h()
+
+
+def _locale_prefers_12h() -> bool:
+    try:
+        fmt = locale.nl_langinfo(locale.T_FMT)
+    except (AttributeError, ValueError):
+        # nl_langinfo is unavailable on Windows.
+        return False
+    return any(token in fmt for token in ("%I", "%l", "%r", "%p"))
+
+
+def _time_pattern(seconds: bool = False) -> str:
+    if _use_12h:
+        return "%I:%M:%S %p" if seconds else "%I:%M %p"
+    return "%H:%M:%S" if seconds else "%H:%M"
+
+
+def format_reset(dt: datetime | str | None) -> str:
+    if dt is None:
+        return "-"
+    if isinstance(dt, str):
+        try:
+            dt = datetime.fromisoformat(dt)
+        except ValueError:
+            return dt
+    return dt.strftime(f"%a %b %d  {_time_pattern()}")
+
+
+def format_clock(dt: datetime) -> str:
+    return dt.strftime(_time_pattern(seconds=True))
+
+
+def format_since(dt: datetime, now: datetime | None = None) -> str:
+    """A clock time, prefixed with the weekday when it is not today."""
+    now = now or datetime.now()
+    if dt.date() == now.date():
+        return dt.strftime(_time_pattern())
+    return dt.strftime(f"%a {_time_pattern()}")
+
+
+def format_duration(total_seconds: float) -> str:
+    total = max(0, int(total_seconds))
+    days, rem = divmod(total, 86400)
+    hours, rem = divmod(rem, 3600)
+    minutes = rem // 60
+    if days > 0:
+        return f"{days}d {hours}h"
+    if hours > 0:
+        return f"{hours}h {minutes}m"
+    return f"{minutes}m"
@@ -1,10 +1,111 @@
 from __future__ import annotations
 
+import os
+import sys
+import time
 from pathlib import Path
 
+from usagedash.timefmt import format_duration
 from usagedash.snapshot import read_snapshot
 
 
+class SummaryCache:
+    """`summary_line` that only re-reads the state file after it changes.
+
+    The tray lives for weeks, so reading and parsing an unchanged snapshot
+    every poll is pure churn; a stat is enough to tell.
+    """
+
+    def __init__(self, state_file: str) -> None:
+        self.path = Path(state_file)
+        self._stamp: tuple[int, int] | None = None
+        self._line = ""
+        self.reads = 0
+
+    def line(self) -> str:
+        try:
+            st = self.path.stat()
+        except OSError:
+            self._stamp = None
+            return "UsageDash: snapshot missing"
+        stamp = (st.st_mtime_ns, st.st_size)
+        if stamp != self._stamp:
+            try:
+                self._line = summary_line(str(self.path))
+            except (OSError, ValueError, KeyError):
+                # Caught mid-write; keep the previous line and retry next time.
+                return self._line or "UsageDash: snapshot unreadable"
+            self._stamp = stamp
+            self.reads += 1
+        return self._line
+
+
+def wait_for_change(directory: Path, timeout: float) -> None:
+    """Block until something in `directory` is written, or `timeout` seconds pass.
+
+    Uses a change notification handle on Windows; elsewhere (including a
+    WSL-side tray) it simply sleeps.
+    """
+    if sys.platform == "win32" and directory.is_dir():
+        import ctypes
+
+        kernel32 = ctypes.windll.kernel32  # type: ignore[attr-defined]
+        kernel32.FindFirstChangeNotificationW.restype = ctypes.c_void_p
+        handle = kernel32.FindFirstChangeNotificationW(str(directory), False, 0x10)  # LAST_WRITE
+        if handle and handle != ctypes.c_void_p(-1).value:
+            try:
+                kernel32.WaitForSingleObject(ctypes.c_void_p(handle), int(timeout * 1000))
+            finally:
+                kernel32.FindCloseChangeNotification(ctypes.c_void_p(handle))
+            return
+    time.sleep(timeout)
+
+
+def process_memory_bytes() -> int | None:
+    """Current working set on Windows, peak RSS elsewhere; None if unknown."""
+    if sys.platform == "win32":
+        import ctypes
+        from ctypes import wintypes
+
+        class Counters(ctypes.Structure):
+            _fields_ = [
+                ("cb", wintypes.DWORD),
+                ("PageFaultCount", wintypes.DWORD),
+                ("PeakWorkingSetSize", ctypes.c_size_t),
+                ("WorkingSetSize", ctypes.c_size_t),
+                ("QuotaPeakPagedPoolUsage", ctypes.c_size_t),
+                ("QuotaPagedPoolUsage", ctypes.c_size_t),
+                ("QuotaPeakNonPagedPoolUsage", ctypes.c_size_t),
+                ("QuotaNonPagedPoolUsage", ctypes.c_size_t),
+                ("PagefileUsage", ctypes.c_size_t),
+                ("PeakPagefileUsage", ctypes.c_size_t),
+            ]
+
+        counters = Counters()
+        counters.cb = ctypes.sizeof(Counters)
+        process = ctypes.windll.kernel32.GetCurrentProcess()  # type: ignore[attr-defined]
+        if ctypes.windll.psapi.GetProcessMemoryInfo(process, ctypes.byref(counters), counters.cb):  # type: ignore[attr-defined]
+            return int(counters.WorkingSetSize)
+        return None
+    try:
+        import resource
+    except ImportError:
+        return None
+    peak = resource.getrusage(resource.RUSAGE_SELF).ru_maxrss
+    # Linux reports KiB, macOS bytes.
+    return int(peak) if sys.platform == "darwin" else int(peak) * 1024
+
+
+def about_text(started: float, cache: SummaryCache | None = None) -> str:
+    parts = [f"UsageDash tray, pid {os.getpid()}", f"up {format_duration(time.monotonic() - started)}"]
+    memory = process_memory_bytes()
+    if memory is not None:
+        parts.append(f"{memory / 1_048_576:.1f} MiB")
+    if cache is not None:
+        parts.append(f"{cache.reads} reads")
+    return ", ".join(parts)
+
+
 def summary_line(state_file: str) -> str:
     path = Path(state_file)
     if not path.exists():
@@ -13,9 +114,12 @@ def summary_line(state_file: str) -> str:
     snap = read_snapshot(path)
     parts: list[str] = []
     for p in snap.providers:
-        s = f"{p.provider.value}:S{_fmt(p.session_used_pct)} W{_fmt(p.weekly_used_pct)}"
+        s = f"{p.label}:S{_fmt(p.session_used_pct)} W{_fmt(p.weekly_used_pct)}"
+        if p.activity:
+            s += f" ({p.activity})"
         parts.append(s)
-    return " | ".join(parts) if parts else "UsageDash: no providers"
+    line = " | ".join(parts) if parts else "UsageDash: no providers"
+    return f"UsageDash paused | {line}" if snap.paused else line
 
 
 def _fmt(value: float | None) -> str:
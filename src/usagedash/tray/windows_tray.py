@@ -2,11 +2,13 @@ from __future__ import annotations
 
 import threading
 import time
+from pathlib import Path
+
 from PIL import Image, ImageDraw
 import pystray  # type: ignore[import-untyped]
 
 from usagedash.config import load_config
-from usagedash.tray.bridge import summary_line
+from usagedash.tray.bridge import SummaryCache, about_text, wait_for_change
 
 
 def _create_icon() -> Image.Image:
@@ -20,14 +22,22 @@ def _create_icon() -> Image.Image:
 
 def run_tray() -> None:
     cfg = load_config()
+    started = time.monotonic()
+    cache = SummaryCache(cfg.general.windows_state_path)
     icon = pystray.Icon("usagedash", _create_icon(), "UsageDash", menu=pystray.Menu(
+        pystray.MenuItem(lambda item: about_text(started, cache), None, enabled=False),
         pystray.MenuItem("Quit", lambda icon, item: icon.stop()),
     ))
 
     def refresh_loop() -> None:
+        # poll_seconds only bounds the wait: a write to the state directory
+        # wakes the loop early, and an unchanged file is never re-read.
+        state_dir = Path(cfg.general.windows_state_path).parent
         while icon.visible:
-            icon.title = summary_line(cfg.general.windows_state_path)
-            time.sleep(max(5, cfg.tray.poll_seconds))
+            line = cache.line()
+            if icon.title != line:
+                icon.title = line
+            wait_for_change(state_dir, max(5, cfg.tray.poll_seconds))
 
     t = threading.Thread(target=refresh_loop, daemon=True)
     t.start()
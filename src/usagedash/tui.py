@@ -0,0 +1,162 @@
+from __future__ import annotations
+
+from datetime import datetime, timedelta, timezone
+
+from textual import work
+from textual.app import App, ComposeResult
+from textual.binding import Binding
+from textual.containers import Vertical, VerticalScroll
+from textual.timer import Timer
+from textual.widgets import Footer, Header, Sparkline
+
+from usagedash.api import history_records
+from usagedash.config import Config
+from usagedash.formats import paused_line
+from usagedash.models import ProviderSnapshot, UsageSnapshot
+from usagedash.oplog import log_event
+from usagedash.pipeline import refresh_interval, run_cycle
+from usagedash.reload import ConfigWatcher
+from usagedash.timefmt import format_clock
+from usagedash.ui import APP_CSS
+from usagedash.ui.widgets import ProviderCard
+
+SPARKLINE_HOURS = 24
+
+
+def row_key(provider: str, account: str) -> str:
+    return f"{provider} ({account})" if account else provider
+
+
+def usage_series(cfg: Config, hours: int = SPARKLINE_HOURS) -> dict[str, list[float]]:
+    """Recorded usage per row over the last `hours`, session usage where a row has it."""
+    since = datetime.now(timezone.utc).replace(tzinfo=None) - timedelta(hours=hours)
+    series: dict[str, list[float]] = {}
+    for r in history_records(cfg, since=since):
+        value = r["session_used_pct"] if r["session_used_pct"] is not None else r["weekly_used_pct"]
+        if isinstance(value, (int, float)):
+            series.setdefault(row_key(str(r["provider"]), str(r["account"])), []).append(float(value))
+    return series
+
+
+def next_only(keys: list[str], only: str | None) -> str | None:
+    """The row `p` shows next: all, then each row in turn, then all again."""
+    if not keys:
+        return None
+    if only is None:
+        return keys[0]
+    at = keys.index(only) + 1 if only in keys else len(keys)
+    return keys[at] if at < len(keys) else None
+
+
+class ProviderRow(Vertical):
+    DEFAULT_CSS = """
+    ProviderRow { height: auto; }
+    ProviderRow Sparkline { margin: 0 4 1 4; height: 2; }
+    """
+
+    def __init__(self, title: str) -> None:
+        super().__init__()
+        self.card = ProviderCard(title)
+        self.spark = Sparkline([], summary_function=max)
+
+    def compose(self) -> ComposeResult:
+        yield self.card
+        yield self.spark
+
+
+class UsageApp(App):
+    """Full-screen dashboard: a card per provider with a sparkline of the last day.
+
+    Collects on the configured refresh interval in a worker thread so the
+    screen stays responsive; `r` collects now and `p` steps through showing
+    one provider at a time.
+    """
+
+    CSS = APP_CSS
+    TITLE = "usagedash"
+    BINDINGS = [
+        Binding("q", "quit", "Quit"),
+        Binding("r", "refresh", "Refresh"),
+        Binding("p", "toggle_providers", "Providers"),
+    ]
+
+    def __init__(self, watcher: ConfigWatcher) -> None:
+        super().__init__()
+        self.watcher = watcher
+        self.rows: dict[str, ProviderRow] = {}
+        # None shows every provider, otherwise the key of the one shown.
+        self.only: str | None = None
+        self.timer: Timer | None = None
+
+    def compose(self) -> ComposeResult:
+        yield Header()
+        yield VerticalScroll(id="providers")
+        yield Footer()
+
+    def on_mount(self) -> None:
+        self.action_refresh()
+
+    def action_refresh(self) -> None:
+        self._collect()
+
+    def action_toggle_providers(self) -> None:
+        if not self.rows:
+            return
+        self.only = next_only(list(self.rows), self.only)
+        self._apply_filter()
+
+    def _apply_filter(self) -> None:
+        for key, row in self.rows.items():
+            row.display = self.only is None or key == self.only
+        self.sub_title = self.only or "all providers"
+
+    @work(thread=True, exclusive=True)
+    def _collect(self) -> None:
+        cfg = self.watcher.current
+        try:
+            cfg = self.watcher.poll()
+            snapshot = run_cycle(cfg)
+            series = usage_series(cfg)
+        except Exception as exc:  # keep the last screen and try again next interval
+            error = f"{type(exc).__name__}: {exc}"
+            log_event(cfg, "collect_failed", error, "error")
+            self.call_from_thread(self._failed, error, refresh_interval(cfg))
+            return
+        self.call_from_thread(self._show, snapshot, series, refresh_interval(cfg))
+
+    def _schedule(self, interval: float) -> None:
+        # One pending collection at a time, however often `r` is pressed.
+        if self.timer is not None:
+            self.timer.stop()
+        self.timer = self.set_timer(interval, self.action_refresh)
+
+    def _failed(self, error: str, interval: float) -> None:
+        self._schedule(interval)
+        self.title = f"usagedash  {format_clock(datetime.now())}  |  collect failed: {error}"
+
+    def _show(self, snapshot: UsageSnapshot, series: dict[str, list[float]], interval: float) -> None:
+        container = self.query_one("#providers", VerticalScroll)
+        seen: set[str] = set()
+        for snap in snapshot.providers:
+            key = self._key(snap)
+            seen.add(key)
+            row = self.rows.get(key)
+            if row is None:
+                row = self.rows[key] = ProviderRow(snap.label)
+                container.mount(row)
+            row.card.render_provider(snap)
+            row.spark.data = series.get(key, [])
+        for key in [k for k in self.rows if k not in seen]:
+            self.rows.pop(key).remove()
+        self._apply_filter()
+        self._schedule(interval)
+        paused = paused_line(snapshot)
+        self.title = f"usagedash  {format_clock(datetime.now())}" + (f"  |  {paused}" if paused else "")
+
+    @staticmethod
+    def _key(snap: ProviderSnapshot) -> str:
+        return row_key(snap.provider.value, snap.account)
+
+
+def run_tui(watcher: ConfigWatcher) -> None:
+    UsageApp(watcher).run()
@@ -7,7 +7,8 @@ from rich.table import Table
 from rich.text import Text
 from textual.widgets import Static
 
-from usagedash.models import ProviderSnapshot
+from usagedash.models import Percent, ProviderSnapshot, ResetAt
+from usagedash.timefmt import format_clock, format_duration, format_reset
 
 
 def _bar_color(pct: float) -> str:
@@ -22,7 +23,7 @@ def _bar(value: float | None, width: int = 30) -> Text:
     if value is None:
         return Text("  ── no data ──", style="dim")
     shown = max(0.0, value)
-    bar_pct = min(100.0, shown)
+    bar_pct = Percent(shown).bar
     filled = int(round((bar_pct / 100.0) * width))
     empty = width - filled
     color = _bar_color(shown)
@@ -62,20 +63,18 @@ def _fmt_time_remaining(runout_iso: str | None) -> Text:
     total_seconds = remaining.total_seconds()
     if total_seconds <= 0:
         return Text("  EXHAUSTED", style="bold red")
-    hours = int(total_seconds // 3600)
-    minutes = int((total_seconds % 3600) // 60)
-    if hours > 0:
-        txt = f"  {hours}h {minutes}m remaining"
-    else:
-        txt = f"  {minutes}m remaining"
+    txt = f"  {format_duration(total_seconds)} remaining"
     color = "red" if total_seconds < 1800 else "yellow" if total_seconds < 7200 else "green"
     return Text(txt, style=color)
 
 
-def _fmt_reset(dt: datetime | None) -> str:
-    if dt is None:
-        return "-"
-    return dt.strftime("%b %d  %H:%M")
+def _reset_text(at: ResetAt | None) -> Text:
+    """The reset time with a countdown, e.g. `Mon Mar 02  14:00  (in 3h 20m)`."""
+    if at is None:
+        return Text(f"  {format_reset(None)}", style="bright_white")
+    text = Text(f"  {at.display()}", style="bright_white")
+    text.append(f"  ({at.humanize()})", style="dim")
+    return text
 
 
 def _model_name(raw: str) -> str:
@@ -124,26 +123,44 @@ class ProviderCard(Static):
         status_text = Text()
         status_text.append(f"  ● {snap.status.value.upper()}", style=f"bold {status_color}")
         status_text.append(f"    source: {snap.source.value}", style="dim")
+        running = (snap.details or {}).get("running")
+        if running:
+            status_text.append(f"    ▶ running ({running})", style="bold green")
         table.add_row("Status", status_text)
+        if snap.activity:
+            active = snap.activity == "active"
+            table.add_row("Activity", Text(f"  {snap.activity}", style="bold green" if active else "dim"))
 
         # ── Session usage ──
         table.add_row("", Text())
-        session_label = Text("Session", style="bold cyan")
+        session_window = str((snap.details or {}).get("session_window", "session")).capitalize()
+        session_label = Text(session_window, style="bold cyan")
         table.add_row(session_label, _bar(snap.session_used_pct))
         table.add_row(
             Text("  resets", style="dim"),
-            Text(f"  {_fmt_reset(snap.session_reset_at)}", style="bright_white"),
+            _reset_text(snap.session_reset_at),
         )
 
         # ── Weekly usage ──
         table.add_row("", Text())
-        weekly_label = Text("Weekly", style="bold magenta")
+        long_window = str((snap.details or {}).get("long_window", "weekly")).capitalize()
+        weekly_label = Text(long_window, style="bold magenta")
         table.add_row(weekly_label, _bar(snap.weekly_used_pct))
         table.add_row(
             Text("  resets", style="dim"),
-            Text(f"  {_fmt_reset(snap.weekly_reset_at)}", style="bright_white"),
+            _reset_text(snap.weekly_reset_at),
         )
 
+        # ── Context window ──
+        context = (snap.details or {}).get("context")
+        if isinstance(context, dict) and context.get("window_tokens"):
+            table.add_row("", Text())
+            table.add_row(Text("Context", style="bold yellow"), _bar(context.get("used_pct")))
+            table.add_row(
+                Text("  tokens", style="dim"),
+                Text(f"  {_fmt_num(context.get('used_tokens'))} / {_fmt_num(context.get('window_tokens'))}", style="bright_white"),
+            )
+
         # ── Codex details ──
         codex_dyn = (snap.details or {}).get("codex_limits", {})
         if isinstance(codex_dyn, dict) and codex_dyn and snap.provider.value == "codex":
@@ -195,6 +212,12 @@ class ProviderCard(Static):
                 Text("Runout", style="bold blue"),
                 _fmt_time_remaining(dyn.get("predicted_tokens_runout_at")),
             )
+            costs = (snap.details or {}).get("costs")
+            if isinstance(costs, dict):
+                table.add_row(
+                    Text("Cost", style="bold blue"),
+                    Text(f"  ${costs.get('today_usd', 0):,.2f} today  ${costs.get('week_usd', 0):,.2f} 7d  (est.)", style="bright_white"),
+                )
 
             model_dist = dyn.get("model_distribution", {})
             if isinstance(model_dist, dict) and model_dist:
@@ -209,14 +232,14 @@ class ProviderCard(Static):
         # ── Notes ──
         if snap.messages:
             table.add_row("", Text())
-            notes = " | ".join(snap.messages)
+            notes = " | ".join(snap.rendered_messages(detail=False))
             table.add_row(Text("Notes", style="dim"), Text(f"  {notes}", style="dim italic"))
 
         border = {"ok": "#2be38f", "partial": "#f2c94c", "error": "#ff5e6c"}.get(snap.status.value, "#7184d6")
         self.update(Panel(
             table,
-            title=f"[bold bright_white] {self.title.upper()} [/]",
-            subtitle=f"[dim]updated {snap.updated_at.strftime('%H:%M:%S')}[/]" if snap.updated_at else None,
+            title=f"[bold bright_white] {snap.label if snap.display_name or snap.account else self.title.upper()} [/]",
+            subtitle=f"[dim]updated {format_clock(snap.updated_at)}[/]" if snap.updated_at else None,
             border_style=border,
             padding=(1, 2),
         ))
@@ -0,0 +1,165 @@
+import signal
+import sys
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash import killswitch, pipeline
+from usagedash.alerts import (
+    acknowledge,
+    evaluate_alerts,
+    load_alert_state,
+    process_alerts,
+    save_alert_state,
+)
+from usagedash.config import AlertRule, load_config
+from usagedash.durations import parse_duration
+from usagedash.heartbeat import beat, check_heartbeat, last_success
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.store import MemoryStore
+
+
+def _snap(weekly: float) -> UsageSnapshot:
+    return UsageSnapshot(
+        generated_at=datetime(2026, 2, 16),
+        providers=[ProviderSnapshot(provider=ProviderName.CLAUDE, status=StatusKind.OK, weekly_used_pct=weekly)],
+    )
+
+
+def test_alert_cooldown_escalation_and_ack(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.alerts = [
+        AlertRule(name="w", provider="claude", cooldown_minutes=30, sink="stderr", critical_sink="webhook:http://x")
+    ]
+    t0 = datetime(2026, 2, 16, 12, 0)
+    state: dict = {}
+
+    fired = evaluate_alerts(cfg, _snap(82.0), state, now=t0)
+    assert [(e.level, e.sink) for e in fired] == [("warn", "stderr")]
+
+    # Inside the cooldown nothing repeats; after it, the warning repeats.
+    assert evaluate_alerts(cfg, _snap(83.0), state, now=t0 + timedelta(minutes=10)) == []
+    assert len(evaluate_alerts(cfg, _snap(83.0), state, now=t0 + timedelta(minutes=31))) == 1
+
+    # Escalation bypasses the cooldown and routes to the critical sink.
+    fired = evaluate_alerts(cfg, _snap(96.0), state, now=t0 + timedelta(minutes=32))
+    assert [(e.level, e.sink) for e in fired] == [("critical", "webhook:http://x")]
+
+    save_alert_state(cfg, state)
+    assert acknowledge(cfg) == ["w"]
+    state = load_alert_state(cfg)
+    assert evaluate_alerts(cfg, _snap(97.0), state, now=t0 + timedelta(hours=5)) == []
+
+    # Clearing resets the ack so the next crossing fires again.
+    evaluate_alerts(cfg, _snap(10.0), state, now=t0 + timedelta(hours=6))
+    assert len(evaluate_alerts(cfg, _snap(85.0), state, now=t0 + timedelta(hours=7))) == 1
+
+
+def test_fired_alerts_are_recorded_with_delivery_result(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.alerts = [AlertRule(name="w", provider="claude", sink="webhook:")]
+
+    store = MemoryStore()
+    process_alerts(cfg, store, _snap(90.0))
+
+    records = store.alerts(since=datetime.now() - parse_duration("7d"))
+    assert len(records) == 1
+    assert records[0]["rule"] == "w"
+    assert records[0]["delivered"] is False
+    assert "requires a url" in records[0]["error"]
+
+
+def test_critical_actions_need_the_provider_kill_switch_and_are_audited(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    marker = tmp_path / "ran"
+    action = f"command:{sys.executable} -c \"open({str(marker)!r}, 'w')\""
+    cfg.alerts = [AlertRule(name="budget", provider="claude", action=action)]
+
+    store = MemoryStore()
+    process_alerts(cfg, store, _snap(99.0))
+    assert not marker.exists()
+    refused = store.alerts()[-1]
+    assert (refused["sink"], refused["delivered"]) == (f"action:{action}", False)
+    assert refused["error"] == "kill_switch is not enabled for claude"
+
+    cfg.providers["claude"].options["kill_switch"] = True
+    save_alert_state(cfg, {})
+    process_alerts(cfg, store, _snap(99.0))
+    assert marker.exists()
+    assert store.alerts()[-1]["delivered"] is True
+
+
+def test_stopped_agents_are_remembered_and_resumed(tmp_path: Path, monkeypatch) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.providers["claude"].options["kill_switch"] = True
+    sent: list[tuple[int, int]] = []
+    monkeypatch.setattr(killswitch, "agent_pids", lambda provider: [4242])
+    monkeypatch.setattr(killswitch.os, "kill", lambda pid, sig: sent.append((pid, sig)))
+
+    done, error = killswitch.take_action(cfg, AlertRule(name="budget", provider="claude", action="stop"))
+    assert (done, error) == ("stop pid 4242", None)
+    assert killswitch.load_stopped(cfg) == {"claude": [4242]}
+
+    assert killswitch.resume_agents(cfg) == [4242]
+    assert sent == [(4242, signal.SIGSTOP), (4242, signal.SIGCONT)]
+    assert killswitch.load_stopped(cfg) == {}
+
+
+def test_heartbeat_fires_once_per_outage(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.heartbeat.stale_minutes = 15
+    t0 = datetime(2026, 2, 16, 12, 0)
+    store = MemoryStore()
+
+    beat(cfg, now=t0)
+    assert check_heartbeat(cfg, store, now=t0 + timedelta(minutes=10)) is None
+    assert check_heartbeat(cfg, store, now=t0 + timedelta(minutes=20)) is not None
+    assert check_heartbeat(cfg, store, now=t0 + timedelta(minutes=25)) is None
+
+    beat(cfg, now=t0 + timedelta(minutes=30))
+    assert check_heartbeat(cfg, store, now=t0 + timedelta(minutes=31)) is None
+    assert check_heartbeat(cfg, store, now=t0 + timedelta(minutes=50)) is not None
+
+
+def test_heartbeat_needs_a_provider_that_collected_ok(tmp_path: Path, monkeypatch) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    status = StatusKind.ERROR
+    monkeypatch.setattr(pipeline, "build_snapshot", lambda cfg: UsageSnapshot(
+        generated_at=datetime.utcnow(),
+        providers=[
+            ProviderSnapshot(provider=ProviderName.CLAUDE, status=StatusKind.ERROR),
+            ProviderSnapshot(provider=ProviderName.CODEX, status=status),
+        ],
+    ))
+
+    pipeline.run_cycle(cfg)
+    assert last_success(cfg) is None
+    assert check_heartbeat(cfg, MemoryStore()) is not None
+
+    cfg.heartbeat.require_ok = False
+    pipeline.run_cycle(cfg)
+    assert last_success(cfg) is not None
+
+    cfg.heartbeat.require_ok = True
+    (tmp_path / "heartbeat.json").unlink()
+    status = StatusKind.OK
+    pipeline.run_cycle(cfg)
+    assert last_success(cfg) is not None
+
+
+def test_only_while_running_rules_wait_for_the_agent_process(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.alerts = [AlertRule(name="runaway", provider="claude", warn_pct=95.0, critical_pct=None, only_while_running=True)]
+    snapshot = _snap(97.0)
+    state: dict = {}
+
+    assert evaluate_alerts(cfg, snapshot, state) == []
+
+    snapshot.providers[0].details = {"running": 1}
+    fired = evaluate_alerts(cfg, snapshot, state)
+    assert [e.message for e in fired] == ["[WARN] claude weekly usage at 97.0% (runaway) while agent is running"]
@@ -0,0 +1,36 @@
+import threading
+from pathlib import Path
+
+from usagedash.atomic import write_atomic
+
+
+def test_concurrent_writers_never_share_a_temp_file(tmp_path: Path) -> None:
+    path = tmp_path / "latest.json"
+    errors: list[Exception] = []
+
+    def writer(payload: str) -> None:
+        try:
+            for _ in range(200):
+                write_atomic(path, payload)
+        except Exception as exc:
+            errors.append(exc)
+
+    threads = [threading.Thread(target=writer, args=(c * 4096,)) for c in "ab"]
+    for t in threads:
+        t.start()
+    for t in threads:
+        t.join()
+
+    assert errors == []
+    assert path.read_text() in ("a" * 4096, "b" * 4096)
+    assert [p.name for p in tmp_path.iterdir()] == ["latest.json"]
+
+
+def test_replacing_keeps_the_existing_mode(tmp_path: Path) -> None:
+    path = tmp_path / "latest.json"
+    write_atomic(path, "{}")
+    assert path.stat().st_mode & 0o777 == 0o644
+
+    path.chmod(0o640)
+    write_atomic(path, "{}", fsync="always")
+    assert path.stat().st_mode & 0o777 == 0o640
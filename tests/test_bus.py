@@ -0,0 +1,91 @@
+import threading
+import time
+from datetime import datetime
+from pathlib import Path
+
+import pytest
+
+from usagedash import pipeline
+from usagedash.api import history_records
+from usagedash.bus import Bus, render_bus_metrics
+from usagedash.config import BUS_SUBSCRIBERS, load_config
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+
+
+def test_config_knows_every_optional_pipeline_subscriber() -> None:
+    assert pipeline.BUS.names == ["state", *BUS_SUBSCRIBERS]
+
+
+def test_failing_subscriber_does_not_stop_the_others(tmp_path: Path, capsys) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    snap = UsageSnapshot(generated_at=datetime(2026, 3, 1, 12, 0), providers=[])
+    seen: list[str] = []
+
+    def broken(cfg, snapshot) -> None:
+        raise OSError("disk full")
+
+    bus = Bus()
+    bus.subscribe("broken", broken)
+    bus.subscribe("first", lambda c, s: seen.append("first"))
+    unsubscribe = bus.subscribe("second", lambda c, s: seen.append("second"))
+
+    assert bus.publish(cfg, snap) == ["broken"]
+    assert seen == ["first", "second"]
+    # No [log] sinks configured: the failure still reaches stderr.
+    assert capsys.readouterr().err == "usagedash[error] sink_failed: broken: OSError: disk full\n"
+
+    unsubscribe()
+    bus.subscribe("state", broken, required=True)
+    with pytest.raises(OSError):
+        bus.publish(cfg, snap)
+    assert seen == ["first", "second", "first"]
+    assert bus.names == ["broken", "first", "state"]
+
+
+def test_queued_subscriber_drops_oldest_and_reports_metrics(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.bus.queued = ["webhook"]
+    cfg.bus.max_queue = 2
+    started, release = threading.Event(), threading.Event()
+    handled: list[datetime] = []
+
+    def hanging(cfg, snapshot) -> None:
+        started.set()
+        release.wait(5)
+        handled.append(snapshot.generated_at)
+
+    bus = Bus()
+    bus.subscribe("webhook", hanging)
+    snaps = [UsageSnapshot(generated_at=datetime(2026, 3, 1, 12, m), providers=[]) for m in range(5)]
+    bus.publish(cfg, snaps[0])
+    assert started.wait(5)  # the worker picked up the first snapshot and hangs on it
+    for snap in snaps[1:]:
+        assert bus.publish(cfg, snap) == []
+
+    assert bus.stats()["webhook"] == {"queue_depth": 2, "delivered": 0, "failed": 0, "dropped": 2}
+    assert 'usagedash_bus_dropped_total{subscriber="webhook"} 2' in render_bus_metrics(bus)
+
+    release.set()
+    assert bus.drain(5)
+    assert handled == [snaps[0].generated_at, snaps[3].generated_at, snaps[4].generated_at]
+
+
+def test_queued_alerts_do_not_hold_up_history(tmp_path: Path, monkeypatch) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.bus.queued = ["alerts"]
+    release = threading.Event()
+    monkeypatch.setattr(pipeline, "process_alerts", lambda cfg, store, snapshot: release.wait(5))
+    snap = UsageSnapshot(
+        generated_at=datetime.utcnow(),
+        providers=[ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, weekly_used_pct=10.0)],
+    )
+
+    assert pipeline.BUS.names == ["state", "flags", "store", "alerts", "sources"]
+    started = time.monotonic()
+    assert pipeline.BUS.publish(cfg, snap) == []
+    assert time.monotonic() - started < 2  # the webhook hangs on its own thread
+    assert [r["weekly_used_pct"] for r in history_records(cfg)] == [10.0]
+
+    release.set()
+    assert pipeline.BUS.drain(5)
@@ -1,5 +1,10 @@
+import json
+import os
 import subprocess
 import sys
+from datetime import datetime
+
+import pytest
 
 
 def test_cli_health_runs() -> None:
@@ -11,3 +16,175 @@ def test_cli_health_runs() -> None:
     )
     assert proc.returncode == 0
     assert "config" in proc.stdout
+
+
+def test_cli_capabilities_lists_providers() -> None:
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--read-only", "capabilities", "--format", "json"],
+        check=False,
+        capture_output=True,
+        text=True,
+    )
+    assert proc.returncode == 0
+    caps = json.loads(proc.stdout)
+    assert "codex" in caps["providers"]
+    assert caps["schemas"]["snapshot"] >= 1
+
+
+def test_check_diffs_a_candidate_config_and_collects_without_writing(tmp_path) -> None:
+    from usagedash.config import PROVIDER_DEFAULTS
+
+    lines = ["[general]", "refresh_seconds = 15", f'state_file = "{tmp_path / "state.json"}"', ""]
+    for name in PROVIDER_DEFAULTS:
+        lines += [f"[providers.{name}]", f"enabled = {'true' if name == 'custom' else 'false'}", ""]
+    lines += ["[providers.custom.local]", "parser_mode = \"manual\"", "manual = { weekly_used_pct = 12.0 }"]
+    candidate = tmp_path / "new.toml"
+    candidate.write_text("\n".join(lines) + "\n")
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--config", str(candidate), "check"],
+        check=False,
+        capture_output=True,
+        text=True,
+        # An empty config dir: the active config is the defaults.
+        env={**os.environ, "USAGEDASH_CONFIG_DIR": str(tmp_path / "active")},
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    assert "~ general.refresh_seconds: 2.0 -> 15.0" in proc.stdout
+    assert "~ providers.codex.enabled: True -> False" in proc.stdout
+    assert "weekly=12%" in proc.stdout
+    assert not (tmp_path / "state.json").exists()
+
+
+def test_query_prints_one_provider_field(tmp_path) -> None:
+    config = tmp_path / "config.toml"
+    config.write_text('[providers.groq]\nparser_mode = "manual"\nmanual = { session_used_pct = 41.5 }\n')
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--config", str(config), "query", "groq", "--field", "session_used_pct"],
+        check=False,
+        capture_output=True,
+        text=True,
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    assert proc.stdout.strip() == "41.5"
+
+
+def test_history_lists_recorded_rows_for_one_provider(tmp_path) -> None:
+    from datetime import datetime, timedelta
+
+    from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+    from usagedash.store import SqliteStore
+
+    now = datetime.utcnow()
+    with SqliteStore(tmp_path / "usagedash.db") as store:
+        for hours, pct in ((30 * 24, 5.0), (2, 33.0), (1, 47.5)):
+            store.append_snapshot(UsageSnapshot(generated_at=now - timedelta(hours=hours), providers=[
+                ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, weekly_used_pct=pct),
+                ProviderSnapshot(provider=ProviderName.CLAUDE, status=StatusKind.OK, weekly_used_pct=99.0),
+            ]))
+    config = tmp_path / "config.toml"
+    config.write_text(f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n')
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--config", str(config), "history", "--provider", "codex", "--since", "1d"],
+        check=False,
+        capture_output=True,
+        text=True,
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    assert "33.0%" in proc.stdout and "47.5%" in proc.stdout
+    assert "5.0%" not in proc.stdout
+    assert "99.0%" not in proc.stdout
+
+
+def test_panel_json_output_is_the_filtered_snapshot(tmp_path) -> None:
+    config = tmp_path / "config.toml"
+    config.write_text(
+        f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n'
+        '[providers.groq]\nenabled = true\nparser_mode = "manual"\nmanual = { session_used_pct = 12.5 }\n'
+    )
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--read-only", "--config", str(config), "panel", "--provider", "groq", "--format", "json"],
+        check=False,
+        capture_output=True,
+        text=True,
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    data = json.loads(proc.stdout)
+    assert [p["provider"] for p in data["providers"]] == ["groq"]
+    assert data["providers"][0]["session_used_pct"] == 12.5
+
+
+@pytest.mark.parametrize("command", ["snapshot", "export"])
+def test_snapshot_writes_a_prometheus_textfile(tmp_path, command: str) -> None:
+    config = tmp_path / "config.toml"
+    config.write_text(
+        f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n'
+        '[store]\nfsync = "off"\n'
+        '[providers.groq]\nenabled = true\nparser_mode = "manual"\n'
+        'manual = { session_used_pct = 12.5, session_reset_at = "2026-10-17T12:34:56" }\n'
+    )
+    out = tmp_path / "usagedash.prom"
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--read-only", "--config", str(config), command, "--format", "prometheus", "--output", str(out)],
+        check=False,
+        capture_output=True,
+        text=True,
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    assert proc.stdout == ""
+    lines = out.read_text().splitlines()
+    assert 'usagedash_session_percent_used{provider="groq"} 12.5' in lines
+    reset = int(datetime(2026, 10, 17, 12, 34, 56).timestamp())
+    assert f'usagedash_session_reset_timestamp_seconds{{provider="groq"}} {reset}' in lines
+
+
+def test_panel_shows_a_malformed_project_budget_as_a_warning(tmp_path) -> None:
+    config = tmp_path / "config.toml"
+    config.write_text(
+        f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n'
+        '[providers.groq]\nenabled = true\nparser_mode = "manual"\nmanual = { session_used_pct = 12.5 }\n'
+    )
+    project = tmp_path / "acme"
+    project.mkdir()
+    (project / ".usagedash.toml").write_text("[budget\nname = acme\n")
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--read-only", "--config", str(config), "panel", "--provider", "groq", "--format", "plain"],
+        check=False,
+        capture_output=True,
+        text=True,
+        cwd=project,
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    assert "groq session used 12 percent" in proc.stdout
+    assert "ignoring malformed .usagedash.toml" in proc.stdout
+
+
+def test_in_memory_mode_writes_no_files(tmp_path) -> None:
+    config = tmp_path / "config.toml"
+    config.write_text(
+        f'[general]\nstate_file = "{tmp_path / "state" / "latest.json"}"\n'
+        f'windows_state_path = "{tmp_path / "mirror" / "latest.json"}"\n'
+        '[providers.groq]\nenabled = true\nparser_mode = "manual"\nmanual = { session_used_pct = 12.5 }\n'
+    )
+
+    proc = subprocess.run(
+        [sys.executable, "-m", "usagedash", "--in-memory", "--config", str(config), "panel", "--format", "json"],
+        check=False,
+        capture_output=True,
+        text=True,
+    )
+
+    assert proc.returncode == 0, proc.stderr
+    assert json.loads(proc.stdout)["providers"]
+    assert sorted(p.name for p in tmp_path.iterdir()) == ["config.toml"]
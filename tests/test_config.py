@@ -0,0 +1,117 @@
+import importlib.util
+from pathlib import Path
+
+import pytest
+
+from usagedash.api import collect_one
+from usagedash.config import ConfigError, apply_env_overrides, load_config, save_config
+from usagedash.paths import config_dir, state_dir
+from usagedash.pipeline import MIN_REFRESH_SECONDS, refresh_interval
+
+
+def test_env_overrides_reach_nested_sections(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+
+    applied = apply_env_overrides(cfg, {
+        "USAGEDASH_GENERAL__STATE_FILE": "/data/latest.json",
+        "USAGEDASH_GENERAL__REFRESH_SECONDS": "30",
+        "USAGEDASH_PROVIDERS__CLAUDE__ENABLED": "false",
+        "USAGEDASH_PROVIDERS__CODEX__MANUAL__WEEKLY_USED_PCT": "12.5",
+        "USAGEDASH_UNRELATED": "ignored",
+    })
+
+    assert sorted(applied) == [
+        "general.refresh_seconds",
+        "general.state_file",
+        "providers.claude.enabled",
+        "providers.codex.manual.weekly_used_pct",
+    ]
+    assert cfg.general.state_file == "/data/latest.json"
+    assert cfg.general.refresh_seconds == 30
+    assert cfg.providers["claude"].enabled is False
+    assert cfg.providers["codex"].manual.weekly_used_pct == 12.5
+    assert not (tmp_path / "config.toml").exists()
+
+    with pytest.raises(ValueError, match="unknown config key"):
+        apply_env_overrides(cfg, {"USAGEDASH_GENERAL__NOPE": "1"})
+
+
+def test_refresh_accepts_duration_strings_and_jitter(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[general]\nrefresh_seconds = "500ms"\nrefresh_jitter_seconds = "2s"\n')
+
+    cfg = load_config(path)
+
+    assert cfg.general.refresh_seconds == 0.5
+    assert cfg.general.refresh_jitter_seconds == 2.0
+    for _ in range(50):
+        assert MIN_REFRESH_SECONDS <= refresh_interval(cfg) <= 2.5
+
+
+def test_unwritable_config_dir_falls_back_to_defaults(tmp_path: Path) -> None:
+    # A file where the directory should be fails like a read-only home, even as root.
+    blocked = tmp_path / "usagedash"
+    blocked.write_text("")
+
+    cfg = load_config(blocked / "config.toml")
+
+    assert cfg.general.refresh_seconds == 2.0
+    with pytest.raises(ConfigError, match="USAGEDASH_CONFIG_DIR"):
+        save_config(cfg, blocked / "config.toml")
+
+
+def test_dir_overrides_come_from_env(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("USAGEDASH_CONFIG_DIR", str(tmp_path / "cfg"))
+    monkeypatch.setenv("USAGEDASH_STATE_DIR", str(tmp_path / "state"))
+
+    assert config_dir() == tmp_path / "cfg"
+    assert state_dir() == tmp_path / "state"
+
+
+def test_collect_one_skips_the_pipeline_and_honors_display_name(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.providers["groq"].parser_mode = "manual"
+    cfg.providers["groq"].manual.weekly_used_pct = 9.0
+    cfg.providers["groq"].display_name = "Groq free"
+
+    row = collect_one("groq", cfg)
+
+    assert row.weekly_used_pct == 9.0
+    assert row.label == "Groq free"
+
+
+def test_cbor_state_format_needs_cbor2(tmp_path: Path, monkeypatch) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[general]\nstate_format = "cbor"\n')
+    monkeypatch.setattr(importlib.util, "find_spec", lambda name: None)
+
+    with pytest.raises(ValueError, match="needs the cbor2 package"):
+        load_config(path, create=False)
+
+
+def test_unknown_fsync_policy_is_rejected(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[store]\nfsync = "alwyas"\n')
+
+    with pytest.raises(ValueError, match="store.fsync must be one of always, normal, off"):
+        load_config(path, create=False)
+
+
+def test_unknown_mirror_fields_are_rejected(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[mirror]\nfields = ["weekly_used_pct", "details.plan"]\n')
+    assert load_config(path, create=False).mirror.fields == ["weekly_used_pct", "details.plan"]
+
+    path.write_text('[mirror]\nfields = ["weekly_used_percent"]\n')
+    with pytest.raises(ValueError, match="mirror.fields: unknown field 'weekly_used_percent'"):
+        load_config(path, create=False)
+
+
+def test_unknown_bus_queued_names_are_rejected(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[bus]\nqueued = ["alerts", "store"]\n')
+    assert load_config(path, create=False).bus.queued == ["alerts", "store"]
+
+    path.write_text('[bus]\nqueued = ["alert"]\n')
+    with pytest.raises(ValueError, match="bus.queued: unknown subscriber 'alert'"):
+        load_config(path, create=False)
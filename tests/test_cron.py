@@ -0,0 +1,19 @@
+from datetime import datetime
+
+import pytest
+
+from usagedash.cron import parse_cron
+
+
+def test_cron_matches_ranges_steps_and_weekdays() -> None:
+    nightly = parse_cron("* 0-6 * * *")
+    assert nightly.matches(datetime(2026, 3, 2, 3, 15))
+    assert not nightly.matches(datetime(2026, 3, 2, 7, 0))
+
+    weekend_quarters = parse_cron("*/15 * * * 6,7")
+    assert weekend_quarters.matches(datetime(2026, 3, 1, 10, 45))  # Sunday
+    assert not weekend_quarters.matches(datetime(2026, 3, 1, 10, 46))
+    assert not weekend_quarters.matches(datetime(2026, 3, 2, 10, 45))  # Monday
+
+    with pytest.raises(ValueError):
+        parse_cron("* 25 * * *")
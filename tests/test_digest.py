@@ -0,0 +1,44 @@
+import json
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.digest import build_digest
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.providers.pricing import price_table
+from usagedash.store import MemoryStore
+
+
+def test_digest_reports_peak_before_each_weekly_reset(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("HOME", str(tmp_path))
+    store = MemoryStore()
+    t0 = datetime.utcnow() - timedelta(days=3)
+    for i, (w, s) in enumerate([(40.0, 10.0), (91.0, 70.0), (3.0, 5.0), (22.0, 12.0)]):
+        store.append_snapshot(UsageSnapshot(
+            generated_at=t0 + timedelta(hours=i),
+            providers=[ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, weekly_used_pct=w, session_used_pct=s)],
+        ))
+
+    body = build_digest(store)
+
+    assert "CODEX" in body
+    assert "peak session usage: 70.0%" in body
+    assert "closest to weekly limit before reset: 91.0%, 22.0%" in body
+
+
+def test_digest_prices_claude_tokens_over_the_requested_days(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("HOME", str(tmp_path))
+    transcripts = tmp_path / ".claude" / "projects" / "-acme"
+    transcripts.mkdir(parents=True)
+    (transcripts / "s.jsonl").write_text(json.dumps({
+        "type": "assistant",
+        "uuid": "a",
+        "cwd": str(tmp_path / "acme"),
+        "timestamp": (datetime.now() - timedelta(hours=1)).isoformat(),
+        "message": {"id": "a", "role": "assistant", "model": "claude-sonnet-4-5",
+                    "usage": {"input_tokens": 1_000_000, "output_tokens": 0}},
+    }) + "\n")
+
+    body = build_digest(MemoryStore(), days=3, prices=price_table({"sonnet": {"input": 2.0}}))
+
+    assert "Claude tokens in the last 3 days: 1,000,000 (est. $2.00)" in body
+    assert f"  {tmp_path / 'acme'}: 1,000,000 (est. $2.00)" in body
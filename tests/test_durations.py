@@ -0,0 +1,21 @@
+from datetime import timedelta
+
+import pytest
+
+from usagedash.durations import format_seconds, parse_duration, seconds_value
+
+
+def test_parse_duration_accepts_compound_units() -> None:
+    assert parse_duration("7d") == timedelta(days=7)
+    assert parse_duration("1h30m") == timedelta(hours=1, minutes=30)
+    assert parse_duration("500ms") == timedelta(milliseconds=500)
+
+    with pytest.raises(ValueError):
+        parse_duration("7 days")
+
+
+def test_seconds_value_round_trips_through_format_seconds() -> None:
+    assert seconds_value(2) == 2.0
+    assert seconds_value("2m") == 120.0
+    assert format_seconds(seconds_value("500ms")) == "500ms"
+    assert format_seconds(90) == "1m30s"
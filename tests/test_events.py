@@ -0,0 +1,23 @@
+import io
+from datetime import datetime
+
+import pytest
+
+from usagedash.events import UsageEvent, select_columns, write_csv
+
+
+def test_csv_export_prunes_columns() -> None:
+    events = [
+        UsageEvent(timestamp=datetime(2026, 2, 16, 12, 0), provider="claude", project="/w/acme",
+                   model="claude-opus-4-6", input_tokens=10, output_tokens=5, tags={"client": "acme"}),
+    ]
+    out = io.StringIO()
+
+    write_csv(events, select_columns("timestamp,model,output_tokens,tags"), out)
+
+    assert out.getvalue().splitlines() == [
+        "timestamp,model,output_tokens,tags",
+        '2026-02-16T12:00:00,claude-opus-4-6,5,"{""client"": ""acme""}"',
+    ]
+    with pytest.raises(ValueError, match="unknown column"):
+        select_columns("timestamp,cost")
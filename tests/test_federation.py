@@ -0,0 +1,90 @@
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.compression import compress, decompress, negotiate_encoding
+from usagedash.config import load_config
+from usagedash.delta import DeltaLog, FollowState
+from usagedash.federation import discover_targets
+from usagedash.formats import render_prometheus_many
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.snapshot import snapshot_from_dict
+
+
+def _snap(pct: float) -> UsageSnapshot:
+    return UsageSnapshot(
+        generated_at=datetime(2026, 2, 16, 12, 0),
+        providers=[ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, weekly_used_pct=pct)],
+    )
+
+
+def test_federated_metrics_share_families_and_label_instances() -> None:
+    body = render_prometheus_many([(_snap(10.0), {"instance": "a"}), (_snap(20.0), {"instance": "b"})])
+
+    assert body.count("# TYPE usagedash_weekly_percent_used gauge") == 1
+    assert 'usagedash_weekly_percent_used{provider="codex",instance="a"} 10' in body
+    assert 'usagedash_weekly_percent_used{provider="codex",instance="b"} 20' in body
+
+
+def test_discover_targets_dedupes_static_and_dns(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.federation.targets = ["http://127.0.0.1:9184/", "http://127.0.0.1:9184"]
+    cfg.federation.dns_name = "localhost:9185"
+
+    targets = discover_targets(cfg)
+
+    assert targets[0] == "http://127.0.0.1:9184"
+    assert "http://127.0.0.1:9185" in targets
+    assert len(targets) == len(set(targets))
+
+
+def test_snapshot_responses_negotiate_gzip_for_large_bodies() -> None:
+    assert negotiate_encoding("gzip, deflate", 4096) == "gzip"
+    assert negotiate_encoding("br;q=1.0, gzip;q=0.5", 4096) == "gzip"
+    assert negotiate_encoding("gzip", 100) is None
+    assert negotiate_encoding("identity", 4096) is None
+    body = b'{"providers": []}' * 100
+    assert decompress(compress(body, "gzip")) == body
+    assert decompress(body) == body
+
+
+def test_delta_follower_applies_changes_and_falls_back_on_mismatch() -> None:
+    def snap(pct: float) -> UsageSnapshot:
+        # Real collection times: every cycle stamps generated_at and updated_at anew.
+        return UsageSnapshot(
+            generated_at=datetime.utcnow(),
+            providers=[ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, weekly_used_pct=pct)],
+        )
+
+    log = DeltaLog(keep=2)
+    log.record(snap(10.0))
+    follow = FollowState()
+    follow.accept(log.full())
+    assert follow.seq == 1
+
+    log.record(snap(10.0))
+    assert log.seq == 1 and log.since(1)["changed"] == {}
+
+    log.record(snap(25.0))
+    delta = log.since(1)
+    assert delta["changed"]["codex"]["weekly_used_pct"] == 25.0
+    data = follow.accept(delta)
+    assert data is not None and snapshot_from_dict(data).providers[0].weekly_used_pct == 25.0
+    assert follow.want_delta(full_sync_every=1) is False
+
+    log.record(snap(30.0))
+    log.record(snap(40.0))
+    assert log.since(1) is None
+    assert follow.accept({**log.since(3), "base": 1}) is None
+
+
+def test_delta_from_a_restarted_target_is_refused() -> None:
+    before = DeltaLog()
+    before.record(_snap(10.0))
+    follow = FollowState()
+    follow.accept(before.full())
+
+    after = DeltaLog()  # same sequence numbers, different run
+    after.record(_snap(50.0))
+    assert after.since(1) is not None
+    assert follow.accept(after.since(1)) is None
+    assert follow.accept(after.full())["providers"][0]["weekly_used_pct"] == 50.0
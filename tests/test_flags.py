@@ -0,0 +1,57 @@
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import load_config
+from usagedash.flags import flags_dir, write_flags
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+
+
+def _snap(session: float, weekly: float) -> UsageSnapshot:
+    return UsageSnapshot(
+        generated_at=datetime(2026, 10, 17),
+        providers=[
+            ProviderSnapshot(ProviderName.CLAUDE, StatusKind.OK, session_used_pct=session, weekly_used_pct=weekly),
+            ProviderSnapshot(ProviderName.CODEX, StatusKind.OK, weekly_used_pct=100.0, account="work"),
+        ],
+    )
+
+
+def test_flag_files_follow_threshold_crossings(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    root = flags_dir(cfg)
+
+    write_flags(cfg, _snap(85.0, 100.0))
+    assert sorted(p.name for p in root.iterdir()) == [
+        "claude.session.warn",
+        "claude.weekly.exceeded",
+        "claude.weekly.warn",
+        "codex.work.weekly.exceeded",
+        "codex.work.weekly.warn",
+    ]
+    assert (root / "claude.session.warn").read_text() == "85.0\n"
+
+    (root / "notes.txt").write_text("kept")
+    write_flags(cfg, _snap(10.0, 90.0))
+    assert sorted(p.name for p in root.iterdir()) == [
+        "claude.weekly.warn",
+        "codex.work.weekly.exceeded",
+        "codex.work.weekly.warn",
+        "notes.txt",
+    ]
+    # Unchanged flags keep the value they were raised with.
+    assert (root / "claude.weekly.warn").read_text() == "100.0\n"
+
+
+def test_flag_names_follow_the_provider_window(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    snap = UsageSnapshot(
+        generated_at=datetime(2026, 10, 17),
+        providers=[ProviderSnapshot(
+            ProviderName.COPILOT, StatusKind.OK, weekly_used_pct=85.0, details={"long_window": "monthly"},
+        )],
+    )
+
+    write_flags(cfg, snap)
+    assert sorted(p.name for p in flags_dir(cfg).iterdir()) == ["copilot.monthly.warn"]
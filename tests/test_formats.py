@@ -0,0 +1,83 @@
+from datetime import datetime, timedelta
+
+import pytest
+
+from usagedash.formats import render_html, render_plain, render_summary, render_template
+from usagedash.models import ProviderName, ProviderSnapshot, SourceKind, StatusKind, UsageSnapshot
+
+
+def test_plain_format_emits_one_labeled_line_per_field() -> None:
+    now = datetime(2026, 2, 16, 12, 0)
+    snap = ProviderSnapshot(
+        provider=ProviderName.CLAUDE,
+        status=StatusKind.OK,
+        session_used_pct=12.4,
+        weekly_used_pct=63.0,
+        weekly_reset_at=now + timedelta(days=2, hours=4),
+        source=SourceKind.PARSED,
+        messages=["derived from project logs"],
+    )
+
+    out = render_plain([snap], now=now)
+
+    assert out.splitlines() == [
+        "claude status ok, source parsed",
+        "claude session used 12 percent",
+        "claude weekly used 63 percent, resets in 2 days 4 hours",
+        "claude note: derived from project logs",
+    ]
+
+
+def test_html_format_escapes_messages() -> None:
+    snap = UsageSnapshot(
+        generated_at=datetime(2026, 2, 16, 12, 0),
+        providers=[
+            ProviderSnapshot(
+                provider=ProviderName.CODEX,
+                status=StatusKind.PARTIAL,
+                session_used_pct=42.0,
+                messages=["<missing> history"],
+            )
+        ],
+    )
+
+    page = render_html(snap, refresh_seconds=5)
+
+    assert '<meta http-equiv="refresh" content="5">' in page
+    assert "&lt;missing&gt; history" in page
+    assert 'value="42.0"' in page
+
+
+def test_template_format_substitutes_snapshot_fields() -> None:
+    now = datetime(2026, 2, 16, 12, 0)
+    snap = UsageSnapshot(
+        generated_at=now,
+        providers=[
+            ProviderSnapshot(
+                provider=ProviderName.CLAUDE,
+                status=StatusKind.OK,
+                weekly_used_pct=63.4,
+                weekly_reset_at=now + timedelta(hours=5),
+            )
+        ],
+    )
+
+    out = render_template("{{claude.weekly.percent}}% W ({{ claude.weekly.resets_in }}) codex={{codex.status}}", snap, now=now)
+    assert out == "63% W (5h 0m) codex=-"
+
+    with pytest.raises(ValueError, match="unknown template field"):
+        render_template("{{claude.monthly.percent}}", snap, now=now)
+
+
+def test_summary_format_is_one_line_plus_status_trailer() -> None:
+    providers = [
+        ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, session_used_pct=40.0, weekly_used_pct=12.0),
+        ProviderSnapshot(provider=ProviderName.CLAUDE, status=StatusKind.PARTIAL),
+    ]
+
+    out = render_summary(providers)
+
+    assert out.splitlines() == [
+        "codex ok session=40% weekly=12%; claude partial",
+        "ok=1 partial=1 error=0",
+    ]
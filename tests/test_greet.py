@@ -0,0 +1,43 @@
+import sys
+import time
+from datetime import datetime
+from pathlib import Path
+
+from usagedash import greet as greet_module
+from usagedash.config import load_config
+from usagedash.greet import greet, refresh_command
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.snapshot import write_snapshot_files
+
+
+def test_refresh_command_passes_the_active_config(tmp_path: Path) -> None:
+    config = tmp_path / "work.toml"
+
+    assert refresh_command(config) == [sys.executable, "-m", "usagedash", "--config", str(config), "snapshot"]
+
+
+def test_stale_greeting_refreshes_with_the_same_config(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("HOME", str(tmp_path))
+    config = tmp_path / "work.toml"
+    config.write_text(f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n')
+    cfg = load_config(config)
+    write_snapshot_files(cfg, UsageSnapshot(
+        generated_at=datetime.utcnow(),
+        providers=[ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, session_used_pct=40.0)],
+    ))
+    spawned: list[list[str]] = []
+
+    class FakePopen:
+        def __init__(self, args, **kwargs) -> None:
+            spawned.append(args)
+
+        def poll(self) -> int:
+            return 0
+
+    monkeypatch.setattr(greet_module.subprocess, "Popen", FakePopen)
+    time.sleep(0.01)
+
+    line = greet(cfg, config, max_age_seconds=0.0, budget_seconds=0.1)
+
+    assert spawned == [refresh_command(config)]
+    assert line is not None and "40%" in line.plain
@@ -1,6 +1,11 @@
+import json
+from datetime import datetime, timedelta
+
+from usagedash.messages import configure as configure_messages, msg
 from usagedash.config import ProviderConfig
-from usagedash.models import ProviderName, SourceKind, StatusKind
+from usagedash.models import Percent, ProviderName, ProviderSnapshot, ResetAt, SourceKind, StatusKind, UsageSnapshot
 from usagedash.providers.base import PartialUsage, merge_usage
+from usagedash.snapshot import snapshot_from_dict, snapshot_to_json
 
 
 def test_merge_prefers_parsed_when_present() -> None:
@@ -16,3 +21,67 @@ def test_merge_error_when_no_data() -> None:
     out = merge_usage(ProviderName.CLAUDE, PartialUsage(), cfg)
     assert out.status == StatusKind.ERROR
     assert out.messages
+
+
+def test_diagnostics_render_through_catalog_overrides(tmp_path) -> None:
+    catalog = tmp_path / "de.toml"
+    catalog.write_text('missing_source_file = "{path} fehlt"\n')
+    snap = merge_usage(ProviderName.CODEX, PartialUsage(messages=[msg("missing_source_file", path="/x")]), ProviderConfig())
+
+    assert snap.messages[0] == "missing /x"
+    assert [d.code for d in snap.diagnostics] == ["missing_source_file", "no_usage_metrics"]
+    try:
+        configure_messages(str(catalog))
+        assert snap.rendered_messages()[0] == "/x fehlt"
+    finally:
+        configure_messages("")
+
+
+def test_messages_are_deduped_and_table_shows_most_severe() -> None:
+    partial = PartialUsage(
+        session_used_pct=10.0,
+        messages=[
+            msg("parsed_from", provider="Codex", file="a.jsonl"),
+            msg("reset_missing"),
+            msg("parsed_from", provider="Codex", file="a.jsonl"),
+            msg("parsed_from", provider="Codex", file="b.jsonl"),
+        ],
+    )
+    snap = merge_usage(ProviderName.CODEX, partial, ProviderConfig())
+
+    # Same code with different parameters is a different message.
+    assert [(d.code, d.params.get("file")) for d in snap.diagnostics] == [
+        ("parsed_from", "a.jsonl"), ("reset_missing", None), ("parsed_from", "b.jsonl"),
+    ]
+    assert snap.rendered_messages(detail=False) == ["usage detected but reset timestamps missing"]
+    assert len(snap.rendered_messages()) == 3
+
+
+def test_percent_keeps_overage_and_clamps_only_the_bar() -> None:
+    assert Percent(130.0) == 130.0
+    assert Percent(130.0).bar == 100.0
+    assert Percent(-5.0) == 0.0
+    assert Percent.ratio(1.0, 0) is None
+    assert Percent.ratio(None, 10.0) is None
+    assert Percent.ratio(5.0, 20.0) == 25.0
+    assert Percent.from_left(25.0) == 75.0
+    assert Percent(40.0).left == 60.0
+
+
+def test_snapshot_coerces_usage_types_and_serializes_unchanged() -> None:
+    reset = datetime(2026, 3, 2, 14, 0)
+    snap = ProviderSnapshot(ProviderName.CODEX, StatusKind.OK, session_used_pct=140.0, session_reset_at=reset)
+    assert isinstance(snap.session_used_pct, Percent)
+    assert isinstance(snap.session_reset_at, ResetAt)
+    assert snap.session_reset_at.humanize(reset - timedelta(hours=3, minutes=20)) == "in 3h 20m"
+    assert snap.session_reset_at.humanize(reset + timedelta(minutes=1)) == "due"
+    body = snapshot_to_json(UsageSnapshot(generated_at=reset, providers=[snap]))
+    out = json.loads(body)["providers"][0]
+    assert out["session_used_pct"] == 140.0  # over budget stays visible
+    assert out["session_reset_at"] == reset.isoformat()
+    assert snapshot_from_dict(json.loads(body)).providers[0].session_used_pct == 140.0
+
+    snap.weekly_used_pct = 12.5
+    snap.weekly_reset_at = reset
+    assert isinstance(snap.weekly_used_pct, Percent)
+    assert isinstance(snap.weekly_reset_at, ResetAt)
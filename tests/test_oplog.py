@@ -0,0 +1,39 @@
+from pathlib import Path
+
+import pytest
+
+from usagedash.config import load_config
+from usagedash.oplog import log_event
+
+
+def test_log_event_honors_min_level(tmp_path: Path, capsys) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[log]\nsinks = ["stderr"]\nmin_level = "warning"\n')
+    cfg = load_config(path)
+
+    log_event(cfg, "start", "serving on :9464")
+    log_event(cfg, "collect_failed", "OSError: disk full", "error")
+
+    assert capsys.readouterr().err == "usagedash[error] collect_failed: OSError: disk full\n"
+
+
+def test_log_event_writes_each_event_to_the_stderr_sink(tmp_path: Path, capsys) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[log]\nsinks = ["stderr"]\nident = "usagedash-test"\n')
+    cfg = load_config(path)
+
+    log_event(cfg, "start", "serving on :9464")
+    log_event(cfg, "kill_switch", "stopped codex (pid 42)", "warning")
+
+    assert capsys.readouterr().err == (
+        "usagedash-test[info] start: serving on :9464\n"
+        "usagedash-test[warning] kill_switch: stopped codex (pid 42)\n"
+    )
+
+
+def test_unknown_log_sink_is_rejected_at_load(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[log]\nsinks = ["stderr", "journal"]\n')
+
+    with pytest.raises(ValueError, match="journal"):
+        load_config(path)
@@ -0,0 +1,19 @@
+from pathlib import Path
+
+from usagedash.paths import cache_dir, config_dir, state_dir
+
+
+def test_xdg_base_dirs_are_respected_and_relative_values_ignored(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.delenv("USAGEDASH_CONFIG_DIR", raising=False)
+    monkeypatch.delenv("USAGEDASH_STATE_DIR", raising=False)
+    monkeypatch.delenv("USAGEDASH_CACHE_DIR", raising=False)
+    monkeypatch.setenv("XDG_CONFIG_HOME", str(tmp_path / "cfg"))
+    monkeypatch.setenv("XDG_STATE_HOME", str(tmp_path / "state"))
+    monkeypatch.setenv("XDG_CACHE_HOME", "relative/cache")
+
+    assert config_dir() == tmp_path / "cfg" / "usagedash"
+    assert state_dir() == tmp_path / "state" / "usagedash"
+    assert cache_dir() == Path.home() / ".cache" / "usagedash"
+
+    monkeypatch.setenv("USAGEDASH_STATE_DIR", str(tmp_path / "override"))
+    assert state_dir() == tmp_path / "override"
@@ -0,0 +1,59 @@
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import load_config
+from usagedash.pause import clear_expired, mark_snapshot, pause, pause_path, pause_state, resume
+from usagedash.pipeline import run_cycle
+from usagedash.snapshot import read_snapshot
+
+
+def _cfg(tmp_path: Path):
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "state" / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    return cfg
+
+
+def test_paused_cycle_reuses_last_snapshot_without_collecting(tmp_path: Path) -> None:
+    cfg = _cfg(tmp_path)
+    first = run_cycle(cfg)
+    state_file = Path(cfg.general.state_file)
+    mtime = state_file.stat().st_mtime_ns
+
+    pause(cfg)
+    snap = run_cycle(cfg)
+
+    assert snap.paused and snap.paused_until is None
+    assert snap.generated_at == first.generated_at
+    assert state_file.stat().st_mtime_ns == mtime
+
+    assert resume(cfg)
+    assert not run_cycle(cfg).paused
+    assert not resume(cfg)
+
+
+def test_timed_pause_expires(tmp_path: Path) -> None:
+    cfg = _cfg(tmp_path)
+    now = datetime(2026, 3, 1, 12, 0)
+
+    pause(cfg, until=now + timedelta(hours=1), now=now)
+
+    assert pause_state(cfg, now + timedelta(minutes=59)) is not None
+    assert pause_state(cfg, now + timedelta(hours=1)) is None
+
+
+def test_expired_pause_clears_the_flag_in_the_state_file(tmp_path: Path) -> None:
+    cfg = _cfg(tmp_path)
+    run_cycle(cfg)
+    now = datetime.now()
+    pause(cfg, until=now + timedelta(hours=1), now=now)
+    mark_snapshot(cfg, True, now + timedelta(hours=1))
+    assert read_snapshot(cfg.general.state_file).paused
+
+    assert not clear_expired(cfg, now + timedelta(minutes=59))
+    assert clear_expired(cfg, now + timedelta(hours=1))
+
+    stored = read_snapshot(cfg.general.state_file)
+    assert not stored.paused and stored.paused_until is None
+    assert not pause_path(cfg).exists()
+    assert not clear_expired(cfg, now + timedelta(hours=2))
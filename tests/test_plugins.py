@@ -0,0 +1,80 @@
+import os
+import stat
+from pathlib import Path
+
+from usagedash.config import ProviderConfig, load_config
+from usagedash.dryrun import validation_pass
+from usagedash.plugins import find_subcommand, list_subcommands
+from usagedash.providers.plugin import PluginAdapter
+from usagedash.snapshot import build_snapshot
+
+
+def _executable(path: Path, body: str) -> Path:
+    path.write_text(body)
+    path.chmod(path.stat().st_mode | stat.S_IXUSR)
+    return path
+
+
+def test_external_subcommands_are_discovered_on_path(tmp_path: Path, monkeypatch) -> None:
+    plugin = tmp_path / "usagedash-hello"
+    plugin.write_text("#!/bin/sh\necho hello\n")
+    plugin.chmod(plugin.stat().st_mode | stat.S_IXUSR)
+    (tmp_path / "usagedash-not-executable").write_text("")
+    monkeypatch.setenv("PATH", str(tmp_path) + os.pathsep + os.environ.get("PATH", ""))
+
+    assert find_subcommand("hello") == str(plugin)
+    assert find_subcommand("../hello") is None
+    assert list_subcommands() == {"hello": str(plugin)}
+
+
+def test_provider_plugins_report_rows_and_stay_out_of_subcommands(tmp_path: Path, monkeypatch) -> None:
+    _executable(tmp_path / "usagedash-provider-gateway", '#!/bin/sh\necho \'{"weekly_used_pct": 33, "messages": ["ok"]}\'\n')
+    _executable(tmp_path / "usagedash-provider-broken", "#!/bin/sh\necho 'token expired' >&2\nexit 2\n")
+    _executable(tmp_path / "usagedash-provider-off", "#!/bin/sh\nexit 1\n")
+    monkeypatch.setenv("PATH", str(tmp_path) + os.pathsep + os.environ.get("PATH", ""))
+
+    assert list_subcommands() == {}
+    assert find_subcommand("provider-gateway") is None
+
+    rows = PluginAdapter().collect_all(ProviderConfig(options={"off": {"enabled": False}}))
+
+    assert [r.key for r in rows] == ["plugin:broken", "plugin:gateway"]
+    assert rows[0].messages[0].endswith("failed: token expired")
+    assert rows[1].weekly_used_pct == 33.0
+
+
+def test_provider_plugins_do_not_run_until_enabled(tmp_path: Path, monkeypatch) -> None:
+    marker = tmp_path / "ran"
+    (tmp_path / "bin").mkdir()
+    _executable(tmp_path / "bin" / "usagedash-provider-gateway", f"#!/bin/sh\ntouch {marker}\necho '{{}}'\n")
+    monkeypatch.setenv("PATH", str(tmp_path / "bin") + os.pathsep + os.environ.get("PATH", ""))
+    path = tmp_path / "config.toml"
+    path.write_text(f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n')
+
+    snap = build_snapshot(load_config(path))
+    assert "plugin" not in [p.provider.value for p in snap.providers]
+    assert not marker.exists()
+
+    path.write_text(f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n[providers.plugin]\nenabled = true\n')
+    snap = build_snapshot(load_config(path))
+    assert [p.key for p in snap.providers if p.provider.value == "plugin"] == ["plugin:gateway"]
+    assert marker.exists()
+
+
+def test_dry_runs_do_not_execute_plugins_or_custom_commands(tmp_path: Path, monkeypatch) -> None:
+    marker = tmp_path / "ran"
+    (tmp_path / "bin").mkdir()
+    _executable(tmp_path / "bin" / "usagedash-provider-gateway", f"#!/bin/sh\ntouch {marker}\necho '{{}}'\n")
+    monkeypatch.setenv("PATH", str(tmp_path / "bin") + os.pathsep + os.environ.get("PATH", ""))
+    path = tmp_path / "config.toml"
+    path.write_text(
+        f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n'
+        "[providers.plugin]\nenabled = true\n"
+        f'[providers.custom.team]\ncommand = "touch {marker}"\n'
+    )
+
+    snap = validation_pass(load_config(path))
+
+    assert not marker.exists()
+    skipped = [p.key for p in snap.providers if any(d.code == "command_skipped" for d in p.diagnostics)]
+    assert skipped == ["custom:team", "plugin:gateway"]
@@ -0,0 +1,33 @@
+from pathlib import Path
+
+from usagedash.processes import AGENT_PROCESSES, _proc_names
+
+
+def _proc(root: Path, pid: int, comm: str, *argv: str) -> None:
+    (root / str(pid)).mkdir()
+    (root / str(pid) / "comm").write_text(comm + "\n")
+    (root / str(pid) / "cmdline").write_bytes(b"\0".join(a.encode() for a in argv) + b"\0")
+
+
+def test_proc_scan_sees_node_wrapped_and_native_agents(tmp_path: Path) -> None:
+    _proc(tmp_path, 10, "node", "node", "/usr/lib/node_modules/@openai/codex/bin/codex.js", "exec")
+    _proc(tmp_path, 11, "claude", "/home/me/.local/bin/claude", "--resume")
+    _proc(tmp_path, 12, "bash", "bash")
+    (tmp_path / "self").mkdir()
+
+    names = _proc_names(tmp_path)
+    found = {(pid, p) for p, exes in AGENT_PROCESSES.items() for pid, candidates in names if candidates.intersection(exes)}
+
+    assert found == {(10, "codex"), (11, "claude")}
+
+
+def test_proc_scan_ignores_agent_names_passed_to_other_programs(tmp_path: Path) -> None:
+    _proc(tmp_path, 20, "vim", "vim", "codex")
+    _proc(tmp_path, 21, "less", "/usr/bin/less", "claude")
+    _proc(tmp_path, 22, "bun", "bun", "/opt/gemini/gemini.js")
+
+    names = dict(_proc_names(tmp_path))
+
+    assert not names[20].intersection(AGENT_PROCESSES["codex"])
+    assert not names[21].intersection(AGENT_PROCESSES["claude"])
+    assert names[22].intersection(AGENT_PROCESSES["gemini"])
@@ -0,0 +1,100 @@
+import json
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.api import cost_records
+from usagedash.config import load_config
+from usagedash.events import UsageEvent
+from usagedash.projects import find_project_budget, project_usage, tag_events
+from usagedash.providers.pricing import price_table
+from usagedash.report import group_events
+
+
+def _turn(uid: str, cwd: Path, ts: datetime, tokens: int) -> str:
+    return json.dumps({
+        "type": "assistant",
+        "uuid": uid,
+        "cwd": str(cwd),
+        "timestamp": ts.isoformat(),
+        "message": {"id": uid, "role": "assistant", "model": "claude-sonnet-4-5",
+                    "usage": {"input_tokens": tokens, "output_tokens": 0}},
+    })
+
+
+def test_project_budget_attributes_tokens_by_working_directory(tmp_path: Path) -> None:
+    project = tmp_path / "work" / "acme"
+    (project / "src").mkdir(parents=True)
+    (project / ".usagedash.toml").write_text('[budget]\nname = "acme"\ndaily_tokens = 1000\nweekly_tokens = 5000\n')
+    now = datetime.now().replace(hour=12, minute=0)
+
+    transcripts = tmp_path / "projects" / "-work-acme"
+    transcripts.mkdir(parents=True)
+    (transcripts / "s.jsonl").write_text("\n".join([
+        _turn("a", project, now - timedelta(minutes=5), 600),
+        _turn("b", project / "src", now - timedelta(minutes=1), 600),
+        _turn("c", tmp_path / "other", now - timedelta(minutes=1), 9999),
+        _turn("d", project, now - timedelta(days=3), 1000),
+    ]))
+
+    budget = find_project_budget(project / "src")
+    assert budget is not None and budget.name == "acme"
+
+    usage = project_usage(budget, now=now, projects_path=tmp_path / "projects")
+    assert usage.daily_tokens == 1200
+    assert usage.weekly_tokens == 2200
+    assert usage.exceeded == ["daily"]
+
+
+def test_report_groups_events_by_project_and_config_tags(tmp_path: Path) -> None:
+    project = tmp_path / "acme"
+    project.mkdir()
+    (project / ".usagedash.toml").write_text('[tags]\nclient = "acme"\n')
+    other = tmp_path / "internal"
+    other.mkdir()
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.project_tags = {str(other): {"client": "us"}}
+    now = datetime.now()
+    events = tag_events(cfg, [
+        UsageEvent(timestamp=now, provider="claude", project=str(project), model="m", input_tokens=10),
+        UsageEvent(timestamp=now, provider="claude", project=str(project), model="m", output_tokens=5),
+        UsageEvent(timestamp=now, provider="claude", project=str(other), model="m", input_tokens=7),
+        UsageEvent(timestamp=now, provider="claude", project="-scratch", model="m", input_tokens=1),
+    ])
+
+    rows = group_events(events, "tag", "client")
+    assert [(r.key, r.tokens) for r in rows] == [("client=acme", 15), ("client=us", 7), ("(untagged)", 1)]
+
+
+def test_report_prices_each_group_for_invoicing(tmp_path: Path) -> None:
+    project = tmp_path / "acme"
+    project.mkdir()
+    (project / ".usagedash.toml").write_text('[tags]\nclient = "acme"\n')
+    cfg = load_config(tmp_path / "config.toml")
+    now = datetime.now()
+    events = tag_events(cfg, [
+        UsageEvent(timestamp=now, provider="claude", project=str(project), model="claude-sonnet-4-5", input_tokens=1_000_000),
+        UsageEvent(timestamp=now, provider="claude", project=str(project), model="claude-opus-4-1", output_tokens=100_000),
+        UsageEvent(timestamp=now, provider="claude", project=str(project), model="unknown", input_tokens=500),
+    ])
+
+    [row] = group_events(events, "tag", "client")
+    assert row.key == "client=acme"
+    assert round(row.cost_usd, 2) == 10.50  # $3 input + $7.50 opus output; unknown models are free
+    prices = price_table({"sonnet": {"input": 1.0}})
+    assert round(group_events(events, "tag", "client", prices)[0].cost_usd, 2) == 8.50
+
+
+def test_cost_records_group_by_project_tags_from_usagedash_config(tmp_path: Path, monkeypatch) -> None:
+    project = tmp_path / "acme"
+    project.mkdir()
+    (project / ".usagedash.toml").write_text('[tags]\nclient = "acme"\n')
+    transcripts = tmp_path / ".claude" / "projects" / "-acme"
+    transcripts.mkdir(parents=True)
+    (transcripts / "s.jsonl").write_text(_turn("a", project, datetime.now(), 1_000_000) + "\n")
+    config = tmp_path / "custom.toml"
+    config.write_text('[providers.claude.pricing.sonnet]\ninput = 2.0\n')
+    monkeypatch.setenv("HOME", str(tmp_path))
+    monkeypatch.setenv("USAGEDASH_CONFIG", str(config))
+
+    records = cost_records(datetime.now() - timedelta(days=1), group_by="tag")
+    assert [(r["key"], r["cost_usd"]) for r in records] == [("client=acme", 2.0)]
@@ -0,0 +1,23 @@
+import pytest
+
+from usagedash.protocol import SUPPORTED_VERSIONS, Message, ProtocolError, decode, encode, negotiate
+
+
+def test_messages_round_trip_with_version_tag() -> None:
+    line = encode(Message(type="pause", payload={"until": "2026-10-17T22:00:00"}))
+
+    msg = decode(line)
+
+    assert msg.type == "pause"
+    assert msg.version == SUPPORTED_VERSIONS[-1]
+    assert msg.payload == {"until": "2026-10-17T22:00:00"}
+
+
+def test_negotiation_picks_highest_shared_version() -> None:
+    assert negotiate([1, 99]) == 1
+    with pytest.raises(ProtocolError, match="no common protocol version"):
+        negotiate([99])
+    with pytest.raises(ProtocolError, match="unsupported protocol version"):
+        decode('{"v": 99, "type": "hello"}')
+    with pytest.raises(ProtocolError, match="unknown message type"):
+        decode('{"v": 1, "type": "reboot"}')
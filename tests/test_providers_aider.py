@@ -0,0 +1,83 @@
+import json
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.aider import AiderAdapter
+
+HISTORY = """
+# aider chat started at 2026-10-16 18:00:00
+
+> Tokens: 10k sent, 200 received. Cost: $0.50 message, $0.50 session.
+
+# aider chat started at 2026-10-17 09:00:00
+
+> Tokens: 12k sent, 345 received. Cost: $0.25 message, $0.25 session.
+> Tokens: 14k sent, 100 received. Cost: $0.75 message, $1.00 session.
+"""
+
+
+def test_aider_sums_today_across_repos(tmp_path: Path) -> None:
+    for repo in ("a", "b/nested"):
+        (tmp_path / repo).mkdir(parents=True)
+        (tmp_path / repo / ".aider.chat.history.md").write_text(HISTORY)
+    (tmp_path / "node_modules" / "x").mkdir(parents=True)
+    (tmp_path / "node_modules" / "x" / ".aider.chat.history.md").write_text(HISTORY)
+
+    cfg = ProviderConfig(options={"search_roots": [str(tmp_path)], "daily_budget": 4})
+    adapter = AiderAdapter(now=datetime(2026, 10, 17, 12))
+    adapter.state_dir = tmp_path / "state"
+    snap = adapter.collect(cfg)
+
+    assert snap.details["history_files"] == 2
+    assert snap.details["today_spend"] == 2.0
+    assert snap.session_used_pct == 50.0
+    assert snap.details["last_session_spend"] == 1.0
+
+
+def test_discovered_histories_are_reused_until_the_rescan_interval(tmp_path: Path) -> None:
+    (tmp_path / "a").mkdir()
+    (tmp_path / "a" / ".aider.chat.history.md").write_text(HISTORY)
+    cfg = ProviderConfig(options={"search_roots": [str(tmp_path / "a"), str(tmp_path / "b")], "rescan_hours": 6})
+    now = datetime(2026, 10, 17, 12)
+    adapter = AiderAdapter(now=now)
+    adapter.state_dir = tmp_path / "state"
+    assert adapter.collect(cfg).details["history_files"] == 1
+
+    (tmp_path / "a" / "new").mkdir()
+    (tmp_path / "a" / "new" / ".aider.chat.history.md").write_text(HISTORY)
+    assert adapter.collect(cfg).details["history_files"] == 1
+
+    adapter.now = now + timedelta(hours=7)
+    assert adapter.collect(cfg).details["history_files"] == 2
+
+
+def test_explicit_history_files_skip_the_search(tmp_path: Path) -> None:
+    history = tmp_path / "repo.md"
+    history.write_text(HISTORY)
+    cfg = ProviderConfig(options={"history_files": [str(history)], "search_roots": ["/nonexistent"]})
+    adapter = AiderAdapter(now=datetime(2026, 10, 17, 12))
+    adapter.state_dir = tmp_path / "state"
+
+    assert adapter.collect(cfg).details["today_spend"] == 1.0
+    assert not (tmp_path / "state").exists()
+
+
+def test_analytics_log_books_each_message_to_the_day_it_was_sent(tmp_path: Path) -> None:
+    def event(name: str, sent: datetime, **props: float) -> str:
+        return json.dumps({"event": name, "properties": props, "user_id": "u", "time": int(sent.timestamp())})
+
+    log = tmp_path / "analytics.jsonl"
+    log.write_text("\n".join([
+        event("launched", datetime(2026, 10, 16, 23, 0)),
+        event("message_send", datetime(2026, 10, 16, 23, 30), cost=0.4, total_cost=0.4),
+        # Same chat, after midnight: today's spend, not yesterday's.
+        event("message_send", datetime(2026, 10, 17, 0, 15), cost=0.6, total_cost=1.0),
+        "not json",
+    ]) + "\n")
+    cfg = ProviderConfig(options={"analytics_log": str(log), "daily_budget": 2})
+    snap = AiderAdapter(now=datetime(2026, 10, 17, 12)).collect(cfg)
+
+    assert snap.details["today_spend"] == 0.6
+    assert snap.details["last_session_spend"] == 1.0
+    assert snap.session_used_pct == 30.0
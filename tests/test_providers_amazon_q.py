@@ -0,0 +1,47 @@
+import json
+import sqlite3
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.amazon_q import AmazonQAdapter
+
+
+def test_amazon_q_reads_cli_token_and_agentic_allotment(tmp_path: Path) -> None:
+    db = tmp_path / "data.sqlite3"
+    conn = sqlite3.connect(db)
+    conn.execute("CREATE TABLE auth_kv (key TEXT PRIMARY KEY, value TEXT)")
+    conn.execute(
+        "INSERT INTO auth_kv VALUES (?, ?)",
+        ("codewhisperer:odic:token", json.dumps({"access_token": "aoa-token", "expires_at": "2999-01-01T00:00:00Z"})),
+    )
+    conn.commit()
+    conn.close()
+    seen: list[tuple[str, dict]] = []
+
+    def fetch(url: str, headers: dict) -> object:
+        seen.append((url, headers))
+        return {
+            "usageBreakdownList": [
+                {"resourceType": "TRANSFORM", "currentUsage": 900, "usageLimit": 1000},
+                {"resourceType": "AGENTIC_REQUEST", "currentUsage": 20, "usageLimit": 50, "nextDateReset": 1793491200},
+            ]
+        }
+
+    snap = AmazonQAdapter(q_db=db, sso_cache=tmp_path / "none", fetch=fetch).collect(ProviderConfig())
+
+    assert seen[0][0].endswith("/getUsageLimits?origin=AI_EDITOR&resourceType=AGENTIC_REQUEST")
+    assert seen[0][1] == {"Authorization": "Bearer aoa-token"}
+    assert snap.weekly_used_pct == 40.0
+    assert snap.weekly_reset_at == datetime.fromtimestamp(1793491200)
+    assert snap.details["long_window"] == "monthly"
+
+
+def test_amazon_q_skips_expired_sso_tokens(tmp_path: Path) -> None:
+    cache = tmp_path / "sso"
+    cache.mkdir()
+    (cache / "old.json").write_text(json.dumps({"accessToken": "stale", "expiresAt": "2020-01-01T00:00:00Z"}))
+
+    snap = AmazonQAdapter(q_db=tmp_path / "none.sqlite3", sso_cache=cache, fetch=lambda u, h: {}).collect(ProviderConfig())
+
+    assert snap.diagnostics[0].code == "credentials_missing"
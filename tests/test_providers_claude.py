@@ -1,7 +1,21 @@
+import io
+import json
+from datetime import datetime, timedelta, timezone
 from pathlib import Path
 
+from rich.console import Console
+
+from usagedash.cli import _render_panel
 from usagedash.config import ProviderConfig
+from usagedash.providers.anthropic_api import AnthropicApiAdapter
 from usagedash.providers.claude import ClaudeAdapter
+from usagedash.providers.claude_oauth import ClaudeOAuthUsage
+
+
+def _panel_text(snap) -> str:
+    console = Console(file=io.StringIO(), width=100, color_system=None)
+    console.print(_render_panel(snap))
+    return console.file.getvalue()
 
 
 def test_claude_parser_reads_expected_fields(tmp_path: Path) -> None:
@@ -19,3 +33,193 @@ def test_claude_parser_reads_expected_fields(tmp_path: Path) -> None:
 
     assert snap.session_used_pct == 34.5
     assert snap.weekly_used_pct == 58.0
+
+
+def test_claude_api_mode_keeps_the_weekly_limit_next_to_monthly_spend(tmp_path: Path, monkeypatch) -> None:
+    path = tmp_path / "stats-cache.json"
+    path.write_text(Path("tests/fixtures/claude_stats_sample.json").read_text())
+    empty_projects = tmp_path / "projects"
+    empty_projects.mkdir()
+    monkeypatch.setenv("ANTHROPIC_ADMIN_KEY", "sk-ant-admin")
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert headers["x-api-key"] == "sk-ant-admin"
+        return {"data": [{"starting_at": "2099-01-01T00:00:00Z", "results": [{"amount": "5000"}]}], "has_more": False}
+
+    api = AnthropicApiAdapter(fetch=fetch, now=datetime(2099, 1, 2))
+    oauth = ClaudeOAuthUsage(credentials_path=tmp_path / ".credentials.json")  # not signed in
+    adapter = ClaudeAdapter(stats_path=path, projects_path=empty_projects, api=api, oauth=oauth)
+    snap = adapter.collect(ProviderConfig(parser_mode="api", options={"monthly_budget": 200}))
+
+    assert snap.session_used_pct == 34.5  # only the local stats know the session window
+    assert snap.weekly_used_pct == 58.0  # the weekly limit, not $50 of $200 this month
+    assert snap.details["long_window"] == "weekly"
+    assert snap.details["month_to_date_spend"] == 50.0
+
+    path.unlink()  # no local stats: the spend is all there is, labeled as such
+    snap = adapter.collect(ProviderConfig(parser_mode="api", options={"monthly_budget": 200}))
+    assert snap.weekly_used_pct == 25.0
+    assert snap.details["long_window"] == "monthly"
+
+
+def test_claude_api_mode_uses_oauth_limits_when_signed_in(tmp_path: Path) -> None:
+    path = tmp_path / "stats-cache.json"
+    path.write_text(Path("tests/fixtures/claude_stats_sample.json").read_text())
+    empty_projects = tmp_path / "projects"
+    empty_projects.mkdir()
+    creds = tmp_path / ".credentials.json"
+    expires = (datetime.now() + timedelta(hours=1)).timestamp() * 1000
+    creds.write_text(json.dumps({"claudeAiOauth": {"accessToken": "sk-ant-oat01-x", "expiresAt": expires}}))
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert url == "https://api.anthropic.com/api/oauth/usage"
+        assert headers["Authorization"] == "Bearer sk-ant-oat01-x"
+        return {
+            "five_hour": {"utilization": 12.0, "resets_at": "2099-01-01T15:00:00+00:00"},
+            "seven_day": {"utilization": 71.0, "resets_at": "2099-01-05T09:00:00+00:00"},
+            "seven_day_opus": {"utilization": 40.0, "resets_at": None},
+        }
+
+    def no_admin_calls(url: str, headers: dict[str, str]) -> object:
+        raise AssertionError("the admin API is only a fallback")
+
+    adapter = ClaudeAdapter(
+        stats_path=path,
+        projects_path=empty_projects,
+        api=AnthropicApiAdapter(fetch=no_admin_calls),
+        oauth=ClaudeOAuthUsage(credentials_path=creds, fetch=fetch),
+    )
+    snap = adapter.collect(ProviderConfig(parser_mode="api"))
+
+    assert snap.session_used_pct == 12.0
+    assert snap.weekly_used_pct == 71.0
+    assert snap.weekly_reset_at == datetime(2099, 1, 5, 9, 0, tzinfo=timezone.utc).astimezone().replace(tzinfo=None)
+    assert snap.details["limits_source"] == "oauth"
+    assert snap.details["opus_weekly_used_pct"] == 40.0
+    assert "Opus weekly" in _panel_text(snap)
+
+    creds.write_text(json.dumps({"claudeAiOauth": {"accessToken": "old", "expiresAt": 1000}}))
+    assert ClaudeOAuthUsage(credentials_path=creds, fetch=fetch).parse(ProviderConfig(parser_mode="api")) is None
+
+
+def test_claude_model_split_counts_subagent_turns(tmp_path: Path) -> None:
+    now = datetime.now()
+
+    def turn(i: int, model: str, tokens: int) -> str:
+        return json.dumps({
+            "type": "assistant",
+            "timestamp": (now - timedelta(minutes=30 - i)).isoformat(),
+            "requestId": f"req-{model}-{i}",
+            "message": {"id": f"msg-{model}-{i}", "role": "assistant", "model": model,
+                        "usage": {"input_tokens": tokens, "output_tokens": 0, "cache_read_input_tokens": 1000}},
+        })
+
+    project = tmp_path / "projects" / "-repo"
+    (project / "sess-1" / "subagents").mkdir(parents=True)
+    (project / "sess-1.jsonl").write_text("\n".join([
+        turn(1, "claude-opus-4-1", 6000), turn(2, "claude-sonnet-4-5", 2000),
+    ]))
+    (project / "sess-1" / "subagents" / "agent-a.jsonl").write_text(turn(3, "claude-haiku-4-5", 2000))
+    stats = tmp_path / "stats-cache.json"
+    stats.write_text(json.dumps({"weekly_opus": {"percent_used": 40}}))
+
+    snap = ClaudeAdapter(stats_path=stats, projects_path=tmp_path / "projects").collect(ProviderConfig())
+    dyn = snap.details["dynamic_limits"]
+
+    assert dyn["session_tokens"] == 8000
+    assert dyn["subagent_tokens"] == 2000
+    assert list(dyn["model_split"]) == ["opus", "sonnet", "haiku"]
+    assert dyn["model_split"]["opus"]["pct"] == 60.0
+    assert dyn["model_split"]["haiku"]["subagent_tokens"] == 2000
+    assert dyn["weekly_opus_tokens"] == 6000
+    assert snap.details["opus_weekly_used_pct"] == 40.0
+    assert "Opus weekly" in _panel_text(snap)
+    # The latest main-thread turn: 2000 fresh + 1000 cached tokens of a 200k window.
+    assert snap.details["context"] == {"used_tokens": 3000, "window_tokens": 200_000, "used_pct": 1.5}
+
+
+def test_claude_transcripts_mode_prices_tokens_per_model(tmp_path: Path) -> None:
+    now = datetime.now()
+
+    def turn(i: int, model: str, usage: dict, age: timedelta = timedelta(minutes=5)) -> str:
+        return json.dumps({
+            "type": "assistant",
+            "timestamp": (now - age).isoformat(),
+            "requestId": f"req-{i}",
+            "message": {"id": f"msg-{i}", "role": "assistant", "model": model, "usage": usage},
+        })
+
+    project = tmp_path / "projects" / "-repo"
+    project.mkdir(parents=True)
+    (project / "sess.jsonl").write_text("\n".join([
+        turn(1, "claude-sonnet-4-5", {"input_tokens": 1_000_000, "output_tokens": 100_000,
+                                      "cache_read_input_tokens": 2_000_000}),
+        turn(2, "claude-opus-4-1", {"input_tokens": 10_000, "output_tokens": 0, "cache_creation_input_tokens": 40_000}),
+        turn(3, "claude-sonnet-4-5", {"input_tokens": 1_000_000, "output_tokens": 0}, age=timedelta(days=30)),
+        turn(4, "local-model", {"input_tokens": 5, "output_tokens": 5}),
+    ]))
+
+    cfg = ProviderConfig(parser_mode="transcripts", options={"pricing": {"opus-4-1": {"input": 10.0}}})
+    snap = ClaudeAdapter(stats_path=tmp_path / "missing.json", projects_path=tmp_path / "projects").collect(cfg)
+    costs = snap.details["costs"]
+
+    # sonnet: 3.00 input + 1.50 output + 0.60 cache reads; opus at the override: 0.10 + 0.50 cache writes
+    assert costs["models"]["claude-sonnet-4-5"]["cost_usd"] == 5.1
+    assert costs["models"]["claude-sonnet-4-5"]["cache_read_input_tokens"] == 2_000_000
+    assert costs["models"]["claude-opus-4-1"]["cost_usd"] == 0.6
+    assert costs["week_usd"] == 5.7
+    assert costs["unpriced_models"] == ["local-model"]
+    assert snap.session_used_pct is not None
+
+
+# An assistant line as Claude Code writes it: the 1M-context beta leaves no
+# trace in `model`, only in how large the context gets.
+TRANSCRIPT_LINE = {
+    "parentUuid": "5c1d7e8a-0b4f-4f7e-9a51-2f0c3d9e1b72",
+    "isSidechain": False,
+    "userType": "external",
+    "cwd": "/home/dev/repo",
+    "sessionId": "sess-1",
+    "version": "2.0.14",
+    "gitBranch": "main",
+    "message": {
+        "id": "msg_01HqK3vY8mJ2nX4pT6rW9sZa",
+        "type": "message",
+        "role": "assistant",
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{"type": "text", "text": "Done."}],
+        "stop_reason": "end_turn",
+        "stop_sequence": None,
+        "usage": {
+            "input_tokens": 8,
+            "cache_creation_input_tokens": 4_120,
+            "cache_read_input_tokens": 310_452,
+            "cache_creation": {"ephemeral_5m_input_tokens": 4_120, "ephemeral_1h_input_tokens": 0},
+            "output_tokens": 420,
+            "service_tier": "standard",
+        },
+    },
+    "requestId": "req_011CTz7f4Q9uV2bN3mK8hLpD",
+    "type": "assistant",
+    "uuid": "9e2a4b6c-1d3f-4a5b-8c7d-0e1f2a3b4c5d",
+}
+
+
+def test_claude_context_window_follows_the_transcript(tmp_path: Path) -> None:
+    project = tmp_path / "projects" / "-repo"
+    project.mkdir(parents=True)
+    line = {**TRANSCRIPT_LINE, "timestamp": datetime.now(timezone.utc).isoformat().replace("+00:00", "Z")}
+    (project / "sess-1.jsonl").write_text(json.dumps(line) + "\n")
+    adapter = ClaudeAdapter(stats_path=tmp_path / "missing.json", projects_path=tmp_path / "projects")
+
+    snap = adapter.collect(ProviderConfig(parser_mode="transcripts"))
+    assert snap.details["context"] == {"used_tokens": 315_000, "window_tokens": 1_000_000, "used_pct": 31.5}
+
+    small = json.loads(json.dumps(line))
+    small["message"]["usage"]["cache_read_input_tokens"] = 95_452
+    (project / "sess-1.jsonl").write_text(json.dumps(small) + "\n")
+    snap = adapter.collect(ProviderConfig(parser_mode="transcripts"))
+    assert snap.details["context"]["window_tokens"] == 200_000
+
+    snap = adapter.collect(ProviderConfig(parser_mode="transcripts", options={"context_window": 1_000_000}))
+    assert snap.details["context"]["window_tokens"] == 1_000_000
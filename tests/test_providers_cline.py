@@ -0,0 +1,31 @@
+import json
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.cline import ClineAdapter
+
+
+def _task(root: Path, extension: str, task: str, requests: list[tuple[datetime, float]]) -> None:
+    path = root / extension / "tasks" / task / "ui_messages.json"
+    path.parent.mkdir(parents=True)
+    messages = [{"ts": 0, "type": "say", "say": "text", "text": "hello"}]
+    for at, cost in requests:
+        info = {"tokensIn": 1000, "tokensOut": 200, "cacheReads": 0, "cost": cost}
+        messages.append({"ts": at.timestamp() * 1000, "type": "say", "say": "api_req_started", "text": json.dumps(info)})
+    path.write_text(json.dumps(messages))
+
+
+def test_cline_and_roo_spend_adds_up_per_day_and_week(tmp_path: Path) -> None:
+    now = datetime(2026, 10, 15, 18, 0)
+    _task(tmp_path, "saoudrizwan.claude-dev", "1", [(datetime(2026, 10, 15, 9), 0.75), (datetime(2026, 10, 12, 9), 1.0)])
+    _task(tmp_path, "rooveterinaryinc.roo-cline", "2", [(datetime(2026, 10, 15, 10), 0.25), (datetime(2026, 10, 1), 9.0)])
+
+    cfg = ProviderConfig(enabled=True, options={"daily_budget": 2, "weekly_budget": 10})
+    snap = ClineAdapter(roots=[tmp_path], now=now).collect(cfg)
+
+    assert snap.session_used_pct == 50.0
+    assert snap.weekly_used_pct == 20.0  # the October 1st request is outside the week
+    assert snap.session_reset_at == datetime(2026, 10, 16)
+    assert snap.details["by_extension"] == {"Cline": 1.75, "Roo Code": 0.25}
+    assert snap.details["today_tokens"] == 2400
@@ -0,0 +1,85 @@
+from datetime import datetime
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.gemini import GeminiAdapter, GeminiCloudAdapter
+from usagedash.providers.vertex import VertexAdapter
+
+GENERATE = "aiplatform.googleapis.com/generate_content_requests_per_minute_per_project_per_base_model"
+DAILY = "aiplatform.googleapis.com/online_prediction_requests_per_day"
+
+
+def test_vertex_reports_busiest_minute_and_day_quotas() -> None:
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert headers["Authorization"] == "Bearer ya29.token"
+        if "consumerQuotaMetrics" in url:
+            return {"metrics": [
+                {"metric": GENERATE, "consumerQuotaLimits": [{
+                    "unit": "1/min/{project}/{region}/{base_model}",
+                    "quotaBuckets": [
+                        {"effectiveLimit": "200"},
+                        {"effectiveLimit": "1000", "dimensions": {"base_model": "gemini-pro"}},
+                    ],
+                }]},
+                {"metric": DAILY, "consumerQuotaLimits": [{"unit": "1/d/{project}", "quotaBuckets": [{"effectiveLimit": "10000"}]}]},
+            ]}
+        period = "86400s" if "86400s" in url else "60s"
+        value = "2500" if period == "86400s" else "50"
+        metric = DAILY if period == "86400s" else GENERATE
+        return {"timeSeries": [{"metric": {"labels": {"quota_metric": metric}}, "points": [{"value": {"int64Value": value}}]}]}
+
+    adapter = VertexAdapter(fetch=fetch, token=lambda: "ya29.token", now=datetime(2026, 10, 17, 9, 30, 15))
+    snap = adapter.collect(ProviderConfig(options={"project_id": "proj-1"}))
+
+    assert snap.session_used_pct == 25.0
+    assert snap.weekly_used_pct == 25.0
+    assert snap.session_reset_at == datetime(2026, 10, 17, 9, 31)
+    assert snap.details["long_window"] == "daily"
+    assert "busiest per-minute quota: generate_content_requests_per_minute_per_project_per_base_model" in snap.messages
+
+
+def test_gemini_cloud_mode_tracks_token_minute_and_request_day_quotas() -> None:
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        if "consumerQuotaMetrics" in url:
+            assert "generativelanguage.googleapis.com" in url
+            return {"metrics": [
+                {"metric": "generativelanguage.googleapis.com/generate_content_input_token_count", "consumerQuotaLimits": [
+                    {"unit": "1/min/{project}/{model}", "quotaBuckets": [{"effectiveLimit": "1000000"}]},
+                ]},
+                {"metric": "generativelanguage.googleapis.com/generate_requests_per_model", "consumerQuotaLimits": [
+                    {"unit": "1/min/{project}/{model}", "quotaBuckets": [{"effectiveLimit": "10"}]},
+                    {"unit": "1/d/{project}/{model}", "quotaBuckets": [{"effectiveLimit": "1000"}]},
+                ]},
+            ]}
+        if "86400s" in url:
+            return {"timeSeries": [{"metric": {"labels": {"quota_metric": "generativelanguage.googleapis.com/generate_requests_per_model"}}, "points": [{"value": {"int64Value": "100"}}]}]}
+        return {"timeSeries": [
+            {"metric": {"labels": {"quota_metric": "generativelanguage.googleapis.com/generate_content_input_token_count"}}, "points": [{"value": {"int64Value": "400000"}}]},
+            {"metric": {"labels": {"quota_metric": "generativelanguage.googleapis.com/generate_requests_per_model"}}, "points": [{"value": {"int64Value": "9"}}]},
+        ]}
+
+    adapter = GeminiCloudAdapter(fetch=fetch, token=lambda: "t", now=datetime(2026, 10, 17, 9, 30))
+    snap = adapter.collect(ProviderConfig(parser_mode="cloud", options={"project_id": "p"}))
+
+    # The 90% request-per-minute quota is ignored: only token quotas count per minute.
+    assert snap.session_used_pct == 40.0
+    assert snap.weekly_used_pct == 10.0
+
+
+def test_gemini_cloud_sources_split_ai_studio_and_vertex_rows(monkeypatch) -> None:
+    seen: list[str] = []
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        seen.append(url.split("/services/")[1].split("/")[0] if "/services/" in url else url.split("/projects/")[1].split("/")[0])
+        return {"metrics": []} if "consumerQuotaMetrics" in url else {"timeSeries": []}
+
+    monkeypatch.setattr("usagedash.providers.cloud_quota.fetch_json", fetch)
+    monkeypatch.setattr("usagedash.providers.cloud_quota.adc_access_token", lambda: "t")
+    cfg = ProviderConfig(parser_mode="cloud", options={
+        "project_id": "studio-proj", "vertex_project_id": "vertex-proj", "sources": ["ai-studio", "vertex"],
+    })
+
+    rows = GeminiAdapter().collect_all(cfg)
+
+    assert [r.key for r in rows] == ["gemini:ai-studio", "gemini:vertex"]
+    assert "generativelanguage.googleapis.com" in seen and "aiplatform.googleapis.com" in seen
+    assert "vertex-proj" in seen
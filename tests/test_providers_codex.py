@@ -1,3 +1,7 @@
+import json
+import os
+import time
+from datetime import datetime, timedelta, timezone
 from pathlib import Path
 
 from usagedash.config import ProviderConfig
@@ -18,3 +22,63 @@ def test_codex_parser_reads_percentages(tmp_path: Path) -> None:
 
     assert snap.session_used_pct is not None
     assert snap.weekly_used_pct is not None
+
+
+def test_codex_accounts_report_one_row_each(tmp_path: Path, monkeypatch) -> None:
+    sample = Path("tests/fixtures/codex_history_sample.jsonl").read_text()
+    for name in ("work", "personal"):
+        (tmp_path / name / "sessions").mkdir(parents=True)
+        (tmp_path / name / "history.jsonl").write_text(sample)
+    monkeypatch.setenv("CODEX_HOME", str(tmp_path / "work"))
+
+    cfg = ProviderConfig(options={"accounts": {"work": str(tmp_path / "work"), "personal": str(tmp_path / "personal")}})
+    rows = CodexAdapter().collect_all(cfg)
+
+    assert [(r.key, r.label, r.details["active"]) for r in rows] == [
+        ("codex:work", "codex (work)", True),
+        ("codex:personal", "codex (personal)", False),
+    ]
+    assert all(r.weekly_used_pct is not None for r in rows)
+
+
+def test_codex_flags_session_active_until_transcript_goes_quiet(tmp_path: Path) -> None:
+    history = tmp_path / "history.jsonl"
+    history.write_text(Path("tests/fixtures/codex_history_sample.jsonl").read_text())
+    adapter = CodexAdapter(history_path=history, sessions_path=tmp_path / "sessions")
+
+    snap = adapter.collect(ProviderConfig())
+    assert snap.details["session_active"] is True
+    assert snap.activity == "active"
+
+    hour_ago = time.time() - 3600
+    os.utime(history, (hour_ago, hour_ago))
+    snap = adapter.collect(ProviderConfig(options={"active_minutes": 30}))
+    assert snap.details["session_active"] is False
+    assert snap.activity.startswith("idle since ")
+
+
+def test_codex_reads_rate_limits_from_an_older_session_day(tmp_path: Path) -> None:
+    day = tmp_path / "sessions" / "2025" / "11" / "03"
+    day.mkdir(parents=True)
+    event = {
+        "timestamp": "2025-11-03T10:00:00Z",
+        "type": "event_msg",
+        "payload": {
+            "type": "token_count",
+            "rate_limits": {
+                "primary": {"used_percent": 42.0, "window_minutes": 300, "resets_in_seconds": 3600},
+                "secondary": {"used_percent": 7.5, "window_minutes": 10080, "resets_at": 1762707600},
+            },
+        },
+    }
+    (day / "rollout-2025-11-03T10-00-00-abc.jsonl").write_text(json.dumps(event) + "\n")
+    history = tmp_path / "history.jsonl"
+    history.write_text("")
+
+    snap = CodexAdapter(history_path=history, sessions_path=tmp_path / "sessions").collect(ProviderConfig())
+
+    observed = datetime(2025, 11, 3, 10, 0, tzinfo=timezone.utc).astimezone().replace(tzinfo=None)
+    assert snap.session_used_pct == 42.0
+    assert snap.session_reset_at == observed + timedelta(hours=1)
+    assert snap.weekly_used_pct == 7.5
+    assert snap.weekly_reset_at == datetime.fromtimestamp(1762707600)
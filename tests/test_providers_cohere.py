@@ -0,0 +1,46 @@
+import json
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.cohere import CohereAdapter, record_call
+
+
+def test_cohere_counts_this_months_calls_against_trial_cap(tmp_path: Path) -> None:
+    log = tmp_path / "cohere.jsonl"
+    log.write_text("\n".join([
+        *(json.dumps({"timestamp": f"2026-10-{day:02d}T09:00:00Z"}) for day in range(1, 11)),
+        json.dumps({"timestamp": "2026-09-30T23:59:00Z"}),
+        "not json",
+    ]))
+
+    snap = CohereAdapter(now=datetime(2026, 10, 17, 12)).collect(ProviderConfig(options={"log_path": str(log)}))
+
+    assert snap.weekly_used_pct == 1.0
+    assert snap.weekly_reset_at == datetime(2026, 11, 1)
+    assert snap.details["calls_this_month"] == 10
+
+
+def test_cohere_production_key_has_no_percentage_without_a_limit(tmp_path: Path) -> None:
+    log = tmp_path / "cohere.jsonl"
+    log.write_text(json.dumps({"created": 1792224000}))
+    cfg = ProviderConfig(options={"log_path": str(log), "key_type": "production"})
+
+    snap = CohereAdapter(now=datetime(2026, 10, 17, 12)).collect(cfg)
+
+    assert snap.weekly_used_pct is None
+    assert snap.details["monthly_call_limit"] is None
+
+
+def test_cohere_counts_calls_recorded_by_the_helper(tmp_path: Path) -> None:
+    log = tmp_path / "cohere" / "requests.jsonl"
+    cfg = ProviderConfig(options={"log_path": str(log)})
+    adapter = CohereAdapter(now=datetime(2026, 10, 17, 12))
+    assert adapter.collect(cfg).diagnostics[0].code == "call_log_missing"
+
+    for _ in range(5):
+        record_call(log, billed_units={"input_tokens": 12, "output_tokens": 40}, now=datetime(2026, 10, 16, 9))
+
+    snap = adapter.collect(cfg)
+    assert snap.details["calls_this_month"] == 5
+    assert snap.weekly_used_pct == 0.5
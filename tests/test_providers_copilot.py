@@ -0,0 +1,46 @@
+import json
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.models import StatusKind
+from usagedash.providers.base import ProviderError
+from usagedash.providers.copilot import CopilotAdapter
+
+QUOTA = {
+    "copilot_plan": "individual",
+    "quota_reset_date": "2026-11-01",
+    "quota_snapshots": {
+        "chat": {"unlimited": True},
+        "completions": {"unlimited": True},
+        "premium_interactions": {"entitlement": 300, "remaining": 75, "percent_remaining": 25.0, "unlimited": False},
+    },
+}
+
+
+def test_copilot_reads_token_and_premium_quota(tmp_path: Path) -> None:
+    (tmp_path / "apps.json").write_text(json.dumps({"github.com:Iv1.abc": {"oauth_token": "gho_x"}}))
+    seen: dict[str, str] = {}
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        seen.update(headers, url=url)
+        return QUOTA
+
+    snap = CopilotAdapter(config_dir=tmp_path, fetch=fetch).collect(ProviderConfig())
+
+    assert seen["Authorization"] == "token gho_x"
+    assert snap.weekly_used_pct == 75.0
+    assert snap.session_used_pct is None
+    assert snap.weekly_reset_at is not None and snap.weekly_reset_at.month == 11
+    assert snap.status == StatusKind.OK
+
+
+def test_copilot_reports_fetch_errors(tmp_path: Path) -> None:
+    (tmp_path / "hosts.json").write_text(json.dumps({"github.com": {"oauth_token": "gho_x"}}))
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        raise ProviderError(f"{url}: HTTP 401")
+
+    snap = CopilotAdapter(config_dir=tmp_path, fetch=fetch).collect(ProviderConfig())
+
+    assert snap.status == StatusKind.ERROR
+    assert any("HTTP 401" in m for m in snap.messages)
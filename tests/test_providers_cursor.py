@@ -0,0 +1,34 @@
+import base64
+import json
+import sqlite3
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.cursor import CursorAdapter
+
+
+def _jwt(sub: str) -> str:
+    payload = base64.urlsafe_b64encode(json.dumps({"sub": sub}).encode()).decode().rstrip("=")
+    return f"e30.{payload}.sig"
+
+
+def test_cursor_reads_token_from_state_db_and_fast_requests(tmp_path: Path) -> None:
+    db = tmp_path / "state.vscdb"
+    conn = sqlite3.connect(db)
+    conn.execute("CREATE TABLE ItemTable (key TEXT PRIMARY KEY, value BLOB)")
+    conn.execute("INSERT INTO ItemTable VALUES (?, ?)", ("cursorAuth/accessToken", _jwt("auth0|user_42")))
+    conn.commit()
+    conn.close()
+    urls: list[str] = []
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        urls.append(url)
+        assert headers["Cookie"].startswith("WorkosCursorSessionToken=user_42%3A%3A")
+        return {"gpt-4": {"numRequests": 125, "maxRequestUsage": 500}, "startOfMonth": "2026-10-03T08:00:00.000Z"}
+
+    snap = CursorAdapter(state_db=db, fetch=fetch).collect(ProviderConfig())
+
+    assert urls == ["https://www.cursor.com/api/usage?user=user_42"]
+    assert snap.weekly_used_pct == 25.0
+    assert snap.weekly_reset_at is not None and (snap.weekly_reset_at.month, snap.weekly_reset_at.day) == (11, 3)
+    assert snap.details["long_window"] == "monthly"
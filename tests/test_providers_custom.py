@@ -0,0 +1,95 @@
+import json
+import sys
+from datetime import datetime
+
+from usagedash.config import ProviderConfig
+from usagedash.models import StatusKind
+from usagedash.providers.custom import CustomAdapter
+
+
+def _printer(payload: object) -> list[str]:
+    return [sys.executable, "-c", f"print({json.dumps(json.dumps(payload))})"]
+
+
+def test_command_entries_become_one_row_each() -> None:
+    cfg = ProviderConfig(options={
+        "gateway": {
+            "command": _printer({"session_used_pct": 42, "weekly_reset_at": "2026-10-20T00:00:00", "messages": ["hi"]}),
+            "display_name": "Gateway",
+        },
+        "broken": {"command": [sys.executable, "-c", "import sys; sys.exit('no token')"]},
+        "typed": {"parser_mode": "manual", "manual": {"weekly_used_pct": 7.0}},
+    })
+
+    rows = CustomAdapter().collect_all(cfg)
+
+    assert [r.key for r in rows] == ["custom:gateway", "custom:broken", "custom:typed"]
+    gateway, broken, typed = rows
+    assert gateway.label == "Gateway (gateway)"
+    assert gateway.session_used_pct == 42.0
+    assert gateway.weekly_reset_at == datetime(2026, 10, 20)
+    assert gateway.messages[0] == "hi"
+    assert broken.status == StatusKind.ERROR
+    assert broken.messages[0] == f"{sys.executable} failed: no token"
+    assert typed.weekly_used_pct == 7.0
+
+
+def test_command_output_must_be_a_json_object() -> None:
+    cfg = ProviderConfig(options={"bad": {"command": _printer([1, 2])}})
+
+    snap = CustomAdapter().collect_all(cfg)[0]
+
+    assert snap.diagnostics[0].code == "unexpected_response"
+
+
+def test_file_entries_read_fields_through_json_pointers(tmp_path) -> None:
+    status = tmp_path / "status.json"
+    status.write_text(json.dumps({
+        "limits": [{"used": 12.5, "reset": 1792224000}, {"used": "64", "reset": "2026-10-20T00:00:00"}],
+        "a/b": {"pct": 3},
+    }))
+    cfg = ProviderConfig(options={
+        "tool": {
+            "path": str(status),
+            "pointers": {
+                "session_used_pct": "/limits/0/used",
+                "session_reset_at": "/limits/0/reset",
+                "weekly_used_pct": "/limits/1/used",
+                "weekly_reset_at": "/limits/9/reset",
+            },
+        },
+        "escaped": {"path": str(status), "pointers": {"weekly_used_pct": "/a~1b/pct"}},
+    })
+
+    tool, escaped = CustomAdapter().collect_all(cfg)
+
+    assert tool.session_used_pct == 12.5
+    assert tool.session_reset_at == datetime.fromtimestamp(1792224000)
+    assert tool.weekly_used_pct == 64.0
+    assert tool.weekly_reset_at is None
+    assert escaped.weekly_used_pct == 3.0
+
+
+def test_url_entries_send_bearer_token_and_apply_pointers(monkeypatch) -> None:
+    monkeypatch.setenv("GATEWAY_KEY", "sk-test")
+    seen: list[tuple[str, dict]] = []
+
+    def fetch(url: str, headers: dict) -> object:
+        seen.append((url, headers))
+        return {"budget": {"spend_pct": 55.5, "reset_at": "2026-11-01T00:00:00"}}
+
+    cfg = ProviderConfig(options={
+        "litellm": {
+            "url": "http://gateway.local/key/info",
+            "token_env": "GATEWAY_KEY",
+            "pointers": {"weekly_used_pct": "/budget/spend_pct", "weekly_reset_at": "/budget/reset_at"},
+        },
+        "nokey": {"url": "http://gateway.local/key/info", "token_env": "UNSET_GATEWAY_KEY"},
+    })
+
+    gateway, nokey = CustomAdapter(fetch=fetch).collect_all(cfg)
+
+    assert seen == [("http://gateway.local/key/info", {"Authorization": "Bearer sk-test"})]
+    assert gateway.weekly_used_pct == 55.5
+    assert gateway.weekly_reset_at == datetime(2026, 11, 1)
+    assert nokey.diagnostics[0].code == "credentials_missing"
@@ -0,0 +1,41 @@
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.deepseek import DeepSeekAdapter
+
+
+def test_deepseek_spend_tracks_balance_drops_and_top_ups(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("DEEPSEEK_API_KEY", "ds-key")
+    balances = iter(["50.00", "40.00", "90.00", "85.00"])
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert url == "https://api.deepseek.com/user/balance"
+        return {"is_available": True, "balance_infos": [{"currency": "USD", "total_balance": next(balances)}]}
+
+    adapter = DeepSeekAdapter(fetch=fetch, now=datetime(2026, 10, 17), state_path=tmp_path / "ds.json")
+    cfg = ProviderConfig(options={"monthly_budget": 20})
+
+    assert adapter.collect(cfg).weekly_used_pct == 0.0
+    assert adapter.collect(cfg).weekly_used_pct == 50.0  # spent 10
+    assert adapter.collect(cfg).weekly_used_pct == 50.0  # +50 top-up is not spend
+    snap = adapter.collect(cfg)
+    assert snap.weekly_used_pct == 75.0  # spent 15
+    assert snap.details["balance"] == 85.0
+
+
+def test_deepseek_keeps_state_next_to_the_state_file_and_not_read_only(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("DEEPSEEK_API_KEY", "ds-key")
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        return {"balance_infos": [{"currency": "USD", "total_balance": "50.00"}]}
+
+    adapter = DeepSeekAdapter(fetch=fetch, now=datetime(2026, 10, 17))
+    adapter.state_dir = tmp_path
+    adapter.read_only = True
+    adapter.collect(ProviderConfig())
+    assert not (tmp_path / "deepseek-balance.json").exists()
+
+    adapter.read_only = False
+    adapter.collect(ProviderConfig())
+    assert (tmp_path / "deepseek-balance.json").exists()
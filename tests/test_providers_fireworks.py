@@ -0,0 +1,27 @@
+from pathlib import Path
+
+from usagedash.capabilities import capabilities
+from usagedash.config import ProviderConfig, load_config
+from usagedash.providers.fireworks import FireworksAdapter
+
+
+def test_fireworks_per_minute_requests(monkeypatch) -> None:
+    monkeypatch.setenv("FIREWORKS_API_KEY", "fw-key")
+
+    def headers_fetch(url: str, headers: dict[str, str], body: bytes | None) -> dict[str, str]:
+        assert url == "https://api.fireworks.ai/inference/v1/models" and body is None
+        assert headers["Authorization"] == "Bearer fw-key"
+        return {"x-ratelimit-limit-requests": "600", "x-ratelimit-remaining-requests": "450"}
+
+    snap = FireworksAdapter(headers_fetch=headers_fetch).collect(ProviderConfig(options={"requests_per_minute": 500}))
+
+    assert snap.session_used_pct == 10.0
+    assert snap.weekly_used_pct is None
+    assert snap.details["session_window"] == "minute"
+
+
+def test_fireworks_is_reported_as_experimental(tmp_path: Path) -> None:
+    providers = capabilities(load_config(tmp_path / "config.toml"))["providers"]
+
+    assert providers["fireworks"]["experimental"]
+    assert not providers["claude"]["experimental"]
@@ -0,0 +1,37 @@
+from datetime import datetime, timedelta
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.github_models import GitHubModelsAdapter
+
+
+def test_github_models_probes_once_and_reuses_cached_headers(tmp_path: Path, monkeypatch) -> None:
+    monkeypatch.setenv("GITHUB_TOKEN", "ghp_x")
+    probes: list[tuple[str, bytes | None]] = []
+
+    def fetch(url: str, headers: dict[str, str], body: bytes | None) -> dict[str, str]:
+        probes.append((url, body))
+        assert headers["Authorization"] == "Bearer ghp_x"
+        return {"x-ratelimit-remaining-requests": "38", "x-ratelimit-reset-requests": "2h"}
+
+    cfg = ProviderConfig(enabled=True, options={"tier": "high", "model": "openai/gpt-4.1"})
+    now = datetime(2026, 10, 1, 9, 0)
+    # Probing spends a request, so nothing is sent until it is enabled.
+    off = GitHubModelsAdapter(tmp_path / "cache.json", fetch, now).collect(cfg)
+    assert probes == [] and off.diagnostics[0].code == "probe_disabled"
+
+    cfg.options["probe"] = True
+    snap = GitHubModelsAdapter(tmp_path / "cache.json", fetch, now).collect(cfg)
+
+    assert probes[0][0] == "https://models.github.ai/inference/chat/completions"
+    assert b'"max_tokens": 1' in (probes[0][1] or b"")
+    assert snap.session_used_pct == 24.0  # 12 of the high tier's 50 daily requests
+    assert snap.session_reset_at == datetime(2026, 10, 1, 11, 0)
+    assert snap.details["requests_remaining"] == 38
+
+    later = GitHubModelsAdapter(tmp_path / "cache.json", fetch, now + timedelta(hours=5)).collect(cfg)
+    assert len(probes) == 1
+    assert later.session_used_pct == 24.0
+
+    GitHubModelsAdapter(tmp_path / "cache.json", fetch, now + timedelta(hours=7)).collect(cfg)
+    assert len(probes) == 2
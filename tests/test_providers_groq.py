@@ -0,0 +1,25 @@
+import json
+from datetime import datetime, timezone
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.groq import GroqAdapter
+
+
+def test_groq_tallies_todays_tokens_and_resets_at_midnight_utc(tmp_path: Path) -> None:
+    log = tmp_path / "groq.jsonl"
+    today = datetime(2026, 10, 17, 8, tzinfo=timezone.utc).timestamp()
+    yesterday = datetime(2026, 10, 16, 23, tzinfo=timezone.utc).timestamp()
+    log.write_text("\n".join([
+        json.dumps({"created": int(today), "usage": {"total_tokens": 150_000}}),
+        json.dumps({"timestamp": "2026-10-17T09:00:00Z", "prompt_tokens": 40_000, "completion_tokens": 10_000}),
+        json.dumps({"created": int(yesterday), "usage": {"total_tokens": 999_999}}),
+        "not json",
+    ]))
+    cfg = ProviderConfig(options={"log_path": str(log), "daily_token_limit": 500_000})
+
+    snap = GroqAdapter(now=datetime(2026, 10, 17, 12)).collect(cfg)
+
+    assert snap.session_used_pct == 40.0
+    assert snap.session_reset_at == datetime(2026, 10, 18)
+    assert snap.details["requests_today"] == 2
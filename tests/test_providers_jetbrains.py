@@ -0,0 +1,36 @@
+import json
+import os
+from datetime import datetime
+from html import escape
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.jetbrains import QUOTA_FILE, JetBrainsAdapter
+
+
+def _quota(root: Path, ide: str, current: str, mtime: float) -> None:
+    path = root / ide / QUOTA_FILE
+    path.parent.mkdir(parents=True)
+    quota = {"type": "Available", "current": current, "maximum": "1000000", "until": "2026-12-31T00:00:00"}
+    refill = {"type": "Known", "next": "2026-11-01T00:00:00"}
+    path.write_text(
+        '<application><component name="AIAssistantQuotaManager2">'
+        f'<option name="quotaInfo" value="{escape(json.dumps(quota))}" />'
+        f'<option name="nextRefill" value="{escape(json.dumps(refill))}" />'
+        "</component></application>"
+    )
+    os.utime(path, (mtime, mtime))
+
+
+def test_jetbrains_uses_the_most_recently_written_ide_quota(tmp_path: Path) -> None:
+    _quota(tmp_path, "PyCharm2025.1", "900000", 1_000)
+    _quota(tmp_path, "IntelliJIdea2025.2", "250000.5", 2_000)
+
+    snap = JetBrainsAdapter(root=tmp_path).collect(ProviderConfig())
+
+    assert snap.weekly_used_pct == 25.00005
+    assert snap.weekly_reset_at == datetime(2026, 11, 1)
+    assert snap.details["ide"] == "IntelliJIdea2025.2"
+
+    pinned = ProviderConfig(options={"config_dir": str(tmp_path / "PyCharm2025.1")})
+    assert JetBrainsAdapter(root=tmp_path).collect(pinned).weekly_used_pct == 90.0
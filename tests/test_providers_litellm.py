@@ -0,0 +1,28 @@
+from datetime import datetime
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.litellm import LiteLLMAdapter
+
+
+def test_litellm_reports_one_row_per_key_against_its_budget(monkeypatch) -> None:
+    monkeypatch.setenv("LITELLM_TEAM_KEY", "sk-team")
+    monkeypatch.setenv("LITELLM_CI_KEY", "sk-ci")
+    infos = {
+        "sk-team": {"spend": 30.0, "max_budget": 120.0, "budget_reset_at": "2026-11-01T00:00:00Z", "key_alias": "team"},
+        "sk-ci": {"spend": 4.5, "max_budget": None, "key_alias": None},
+    }
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert url == "http://proxy:4000/key/info"
+        key = headers["Authorization"].removeprefix("Bearer ")
+        return {"key": key, "info": infos[key]}
+
+    cfg = ProviderConfig(options={"api_base": "http://proxy:4000/", "key_envs": ["LITELLM_TEAM_KEY", "LITELLM_CI_KEY"]})
+    team, ci = LiteLLMAdapter(fetch=fetch).collect_all(cfg)
+
+    assert team.account == "team"
+    assert team.weekly_used_pct == 25.0
+    assert team.weekly_reset_at == datetime(2026, 11, 1)
+    assert ci.account == "LITELLM_CI_KEY"
+    assert ci.weekly_used_pct is None
+    assert ci.details["spend"] == 4.5
@@ -0,0 +1,34 @@
+import json
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.models import StatusKind
+from usagedash.providers.ollama import OllamaAdapter
+
+
+def test_ollama_reports_loaded_models_requests_and_tokens(tmp_path: Path) -> None:
+    log = tmp_path / "server.log"
+    log.write_text(
+        '[GIN] 2026/10/17 - 02:00:00 | 200 |  1.2s | 127.0.0.1 | POST     "/api/chat"\n'
+        '[GIN] 2026/10/17 - 09:00:00 | 200 |  1.2s | 127.0.0.1 | POST     "/api/chat"\n'
+        '[GIN] 2026/10/17 - 09:05:00 | 200 |  2.0s | 127.0.0.1 | POST     "/api/generate"\n'
+        '[GIN] 2026/10/17 - 09:06:00 | 200 |   1ms | 127.0.0.1 | GET      "/api/ps"\n'
+    )
+    responses = tmp_path / "responses.jsonl"
+    responses.write_text("\n".join(json.dumps(r) for r in [
+        {"created_at": "2026-10-17T09:00:01", "done": True, "eval_count": 300, "prompt_eval_count": 50},
+        {"created_at": "2026-10-17T09:05:01", "done": True, "eval_count": 200, "prompt_eval_count": 20},
+    ]))
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert url == "http://127.0.0.1:11434/api/ps"
+        return {"models": [{"name": "llama3.2:3b", "size_vram": 2 * 2**30}]}
+
+    cfg = ProviderConfig(options={"server_log": str(log), "response_log": str(responses)})
+    snap = OllamaAdapter(fetch=fetch, now=datetime(2026, 10, 17, 10)).collect(cfg)
+
+    assert snap.status == StatusKind.OK
+    assert snap.details["session_requests"] == 2
+    assert snap.details["session_generated_tokens"] == 500
+    assert snap.messages == ["models loaded: 1 (llama3.2:3b, 2.0 GiB VRAM)", "2 requests", "500 tokens generated"]
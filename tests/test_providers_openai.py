@@ -0,0 +1,28 @@
+from datetime import datetime, timezone
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.openai_billing import OpenAIBillingAdapter
+
+
+def _bucket(day: int, *amounts: float) -> dict:
+    start = int(datetime(2026, 10, day, tzinfo=timezone.utc).timestamp())
+    return {"start_time": start, "results": [{"amount": {"value": a, "currency": "usd"}} for a in amounts]}
+
+
+def test_openai_month_to_date_spend_against_budget(monkeypatch) -> None:
+    monkeypatch.setenv("OPENAI_ADMIN_KEY", "sk-admin")
+    pages = {
+        None: {"data": [_bucket(1, 10.0, 2.5)], "has_more": True, "next_page": "p2"},
+        "p2": {"data": [_bucket(2, 12.5)], "has_more": False},
+    }
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        assert headers["Authorization"] == "Bearer sk-admin"
+        return pages["p2" if "page=p2" in url else None]
+
+    cfg = ProviderConfig(options={"monthly_budget": 100})
+    snap = OpenAIBillingAdapter(fetch=fetch, now=datetime(2026, 10, 2, 12)).collect(cfg)
+
+    assert snap.weekly_used_pct == 25.0
+    assert snap.weekly_reset_at == datetime(2026, 11, 1)
+    assert "2026-10-01: $12.50" in snap.messages
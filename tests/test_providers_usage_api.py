@@ -0,0 +1,59 @@
+from datetime import datetime
+
+from usagedash.config import ProviderConfig
+from usagedash.models import ProviderName
+from usagedash.providers.usage_api import UsageApiAdapter
+
+
+class _ExampleAdapter(UsageApiAdapter):
+    name = ProviderName.CUSTOM
+    title = "Example"
+    default_api_base = "https://api.example.test"
+    default_key_env = "EXAMPLE_API_KEY"
+    default_usage_path = "/v1/usage?start_date={start}&end_date={end}"
+    currency = "eur"
+
+
+def test_usage_api_monthly_tokens_against_cap(monkeypatch) -> None:
+    monkeypatch.setenv("EXAMPLE_API_KEY", "ex-key")
+    urls: list[str] = []
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        urls.append(url)
+        assert headers["Authorization"] == "Bearer ex-key"
+        return {"data": [
+            {"date": "2026-10-01", "input_tokens": 300_000, "output_tokens": 100_000, "cost": 1.2},
+            {"date": "2026-10-02", "total_tokens": 100_000, "cost": 0.3},
+        ]}
+
+    cfg = ProviderConfig(options={"monthly_token_cap": 1_000_000})
+    snap = _ExampleAdapter(fetch=fetch, now=datetime(2026, 10, 2, 9)).collect(cfg)
+
+    assert urls == ["https://api.example.test/v1/usage?start_date=2026-10-01&end_date=2026-10-02"]
+    assert snap.weekly_used_pct == 50.0
+    assert snap.details["month_to_date_tokens"] == 500_000
+    assert "2026-10-01: EUR 1.20" in snap.messages
+
+
+def test_usage_api_without_key_asks_for_it(monkeypatch) -> None:
+    monkeypatch.delenv("EXAMPLE_API_KEY", raising=False)
+
+    snap = _ExampleAdapter(fetch=lambda url, headers: {}).collect(ProviderConfig())
+
+    assert snap.diagnostics[0].code == "credentials_missing"
+
+
+def test_usage_api_reports_credit_balance_next_to_spend(monkeypatch) -> None:
+    monkeypatch.setenv("EXAMPLE_API_KEY", "ex-key")
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        if url == "https://api.example.test/v1/credits":
+            return {"balance": 42.5}
+        return [{"date": "2026-10-01", "cost": 2.5}]
+
+    cfg = ProviderConfig(options={"monthly_budget": 10, "balance_path": "/v1/credits", "currency": "usd"})
+    snap = _ExampleAdapter(fetch=fetch, now=datetime(2026, 10, 2)).collect(cfg)
+
+    assert snap.weekly_used_pct == 25.0
+    assert snap.details["remaining_credits"] == 42.5
+    assert snap.messages[0] == "$42.50 credits remaining"
@@ -0,0 +1,28 @@
+import json
+import sqlite3
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.windsurf import WindsurfAdapter
+
+
+def test_windsurf_reads_cached_plan_credits(tmp_path: Path) -> None:
+    end = datetime(2026, 11, 3, 8, 0)
+    plan = {
+        "planName": "Pro",
+        "endTimestamp": int(end.timestamp() * 1000),
+        "usage": {"messages": 50000, "usedMessages": 12500, "flexCredits": 10000, "usedFlexCredits": 2500},
+    }
+    db = tmp_path / "state.vscdb"
+    conn = sqlite3.connect(db)
+    conn.execute("CREATE TABLE ItemTable (key TEXT PRIMARY KEY, value BLOB)")
+    conn.execute("INSERT INTO ItemTable VALUES (?, ?)", ("windsurf.settings.cachedPlanInfo", json.dumps(plan)))
+    conn.commit()
+    conn.close()
+
+    snap = WindsurfAdapter(state_db=db).collect(ProviderConfig())
+
+    assert snap.weekly_used_pct == 25.0
+    assert snap.weekly_reset_at == end
+    assert snap.messages == ["125 of 500 prompt credits used", "75 add-on credits left"]
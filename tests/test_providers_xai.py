@@ -0,0 +1,31 @@
+from datetime import datetime
+
+from usagedash.config import ProviderConfig
+from usagedash.providers.xai import XaiAdapter
+
+
+def test_xai_reports_spend_and_remaining_credits(monkeypatch) -> None:
+    monkeypatch.setenv("XAI_MANAGEMENT_KEY", "x-key")
+    urls: list[str] = []
+
+    def fetch(url: str, headers: dict[str, str]) -> object:
+        urls.append(url)
+        if url.endswith("/prepaid/balance"):
+            return {"total": {"val": "-2550"}}
+        return {"data": [{"date": "2026-10-01", "cost": 5.0}]}
+
+    cfg = ProviderConfig(options={"team_id": "team-1", "monthly_budget": 20})
+    snap = XaiAdapter(fetch=fetch, now=datetime(2026, 10, 2)).collect(cfg)
+
+    assert urls[0] == "https://management-api.x.ai/v1/billing/teams/team-1/usage?start_date=2026-10-01&end_date=2026-10-02"
+    assert snap.weekly_used_pct == 25.0
+    assert snap.details["remaining_credits"] == 25.5
+    assert snap.messages[0] == "$25.50 credits remaining"
+
+
+def test_xai_without_team_id_asks_for_it(monkeypatch) -> None:
+    monkeypatch.setenv("XAI_MANAGEMENT_KEY", "x-key")
+
+    snap = XaiAdapter(fetch=lambda url, headers: {}).collect(ProviderConfig())
+
+    assert snap.diagnostics[0].code == "credentials_missing"
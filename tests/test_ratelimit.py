@@ -0,0 +1,70 @@
+import json
+import urllib.request
+from pathlib import Path
+
+from usagedash.config import load_config
+from usagedash.ratelimit import acquire
+from usagedash.snapshot import build_snapshot, write_snapshot_files
+
+
+def test_token_bucket_is_shared_through_disk_state(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.rate_limits = {"openai.usage": "2/1m"}
+
+    assert acquire(cfg, "openai.usage", now=0.0)
+    assert acquire(cfg, "openai.usage", now=1.0)
+    assert not acquire(cfg, "openai.usage", now=2.0)
+    # One token refills every 30 seconds.
+    assert acquire(cfg, "openai.usage", now=32.0)
+    # Unconfigured endpoints without a default are unlimited.
+    assert all(acquire(cfg, "other", now=3.0) for _ in range(10))
+
+
+def test_read_only_runs_neither_charge_nor_persist_buckets(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.general.read_only = True
+    cfg.rate_limits = {"openai.usage": "1/1h"}
+
+    assert all(acquire(cfg, "openai.usage", now=float(i)) for i in range(3))
+    assert not (tmp_path / "ratelimit.json").exists()
+
+
+class _Response:
+    def __init__(self, body: dict) -> None:
+        self.body = json.dumps(body).encode()
+
+    def __enter__(self) -> "_Response":
+        return self
+
+    def __exit__(self, *exc: object) -> None:
+        return None
+
+    def read(self) -> bytes:
+        return self.body
+
+
+def test_collect_inside_the_window_makes_no_request_and_keeps_the_last_values(tmp_path: Path, monkeypatch) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    for name, pcfg in cfg.providers.items():
+        pcfg.enabled = name == "litellm"
+    cfg.rate_limits = {"litellm": "1/1h"}
+    monkeypatch.setenv("LITELLM_API_KEY", "sk-test")
+    calls: list[str] = []
+
+    def urlopen(req, timeout: float) -> _Response:
+        calls.append(req.full_url)
+        return _Response({"info": {"spend": 5.0, "max_budget": 20.0}})
+
+    monkeypatch.setattr(urllib.request, "urlopen", urlopen)
+
+    first = build_snapshot(cfg)
+    write_snapshot_files(cfg, first)
+    second = build_snapshot(cfg)
+
+    assert len(calls) == 1
+    row = second.providers[0]
+    assert row.weekly_used_pct == 25.0
+    assert [d.code for d in row.diagnostics][-1] == "rate_limited"
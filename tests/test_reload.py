@@ -0,0 +1,43 @@
+import os
+from pathlib import Path
+
+from usagedash.config import load_config
+from usagedash.reload import ConfigWatcher
+
+
+def test_watcher_applies_edits_and_keeps_old_config_on_errors(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text("[general]\nrefresh_seconds = 60\n")
+    prepared: list[float] = []
+    watcher = ConfigWatcher(load_config(path, create=False), path, lambda cfg: prepared.append(cfg.general.refresh_seconds))
+
+    assert watcher.poll() is watcher.current
+    assert prepared == []
+
+    path.write_text("[general]\nrefresh_seconds = 15\n")
+    os.utime(path, ns=(1, 1))
+    assert watcher.poll().general.refresh_seconds == 15
+    assert prepared == [15]
+
+    path.write_text("[general\nrefresh_seconds = 50\n")
+    os.utime(path, ns=(2, 2))
+    assert watcher.poll().general.refresh_seconds == 15
+    assert watcher.last_error is not None
+
+    path.write_text("[general]\nrefresh_seconds = 3\n")
+    os.utime(path, ns=(2, 2))  # same size and mtime: only an explicit request notices
+    assert watcher.poll().general.refresh_seconds == 15
+    watcher.request()
+    assert watcher.poll().general.refresh_seconds == 3
+    assert watcher.last_error is None
+
+
+def test_alert_without_a_name_keeps_the_old_config(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text("[general]\nrefresh_seconds = 60\n")
+    watcher = ConfigWatcher(load_config(path, create=False), path)
+
+    path.write_text('[general]\nrefresh_seconds = 15\n\n[[alerts]]\nprovider = "claude"\n')
+    os.utime(path, ns=(1, 1))
+    assert watcher.poll().general.refresh_seconds == 60
+    assert watcher.last_error == "ValueError: alert None: missing name"
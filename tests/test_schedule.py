@@ -0,0 +1,42 @@
+from datetime import datetime, timedelta
+
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.schedule import render_cron, render_systemd, schedule_times, upcoming_resets
+
+
+def test_schedule_aligns_to_future_resets() -> None:
+    now = datetime(2026, 2, 16, 12, 0)
+    snap = UsageSnapshot(
+        generated_at=now,
+        providers=[
+            ProviderSnapshot(
+                provider=ProviderName.CODEX,
+                status=StatusKind.OK,
+                session_reset_at=datetime(2026, 2, 16, 15, 30),
+                weekly_reset_at=datetime(2026, 2, 20, 9, 0),
+            ),
+            ProviderSnapshot(
+                provider=ProviderName.CLAUDE,
+                status=StatusKind.OK,
+                session_reset_at=datetime(2026, 2, 16, 11, 0),
+            ),
+        ],
+    )
+
+    resets = upcoming_resets(snap, now=now)
+    assert [(p, w) for p, w, _ in resets] == [("codex", "session"), ("codex", "weekly")]
+
+    times = schedule_times(resets, timedelta(minutes=2))
+    units = render_systemd("batch", "run-agents.sh --fast", times)
+    assert "OnCalendar=2026-02-16 15:32:00\nOnCalendar=2026-02-20 09:02:00\n" in units["batch.timer"]
+    assert "ExecStart=/bin/sh -c 'run-agents.sh --fast'" in units["batch.service"]
+    assert render_cron("job.sh", times[:1]) == "32 15 16 2 * job.sh\n"
+
+
+def test_percent_signs_in_the_command_are_escaped_per_target() -> None:
+    times = [datetime(2026, 2, 16, 15, 32)]
+    command = "backup.sh --tag $(date +%Y-%m-%d)"
+
+    units = render_systemd("backup", command, times)
+    assert "ExecStart=/bin/sh -c 'backup.sh --tag $(date +%%Y-%%m-%%d)'\n" in units["backup.service"]
+    assert render_cron(command, times) == "32 15 16 2 * backup.sh --tag $(date +\\%Y-\\%m-\\%d)\n"
@@ -0,0 +1,47 @@
+from pathlib import Path
+
+from usagedash.config import AlertRule, Config
+from usagedash.pipeline import BUS
+from usagedash.selftest import run_selftest
+
+
+def test_selftest_passes_every_stage_without_touching_state(tmp_path: Path, capsys) -> None:
+    cfg = Config()
+    cfg.general.state_file = str(tmp_path / "state" / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror" / "latest.json")
+    cfg.alerts = [AlertRule(name="weekly", provider="codex", sink="stderr"), AlertRule(name="x", provider="claude", sink="bogus:")]
+
+    results = run_selftest(cfg)
+
+    assert [(r.stage, r.ok) for r in results] == [
+        ("collect", True),
+        ("bus", True),
+        ("state", True),
+        ("mirror", True),
+        ("history", True),
+        ("alerts", True),
+        ("heartbeat", True),
+        ("sink bogus:", False),
+        ("sink stderr", True),
+    ]
+    assert "selftest" in capsys.readouterr().err
+    assert not (tmp_path / "state").exists()
+
+
+def test_selftest_fails_when_a_pipeline_subscriber_breaks(tmp_path: Path, monkeypatch) -> None:
+    cfg = Config()
+    cfg.general.state_file = str(tmp_path / "state" / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror" / "latest.json")
+
+    def broken(cfg: Config, snapshot: object) -> None:
+        raise OSError("disk full")
+
+    unsubscribe = BUS.subscribe("broken", broken)
+    try:
+        results = {r.stage: r for r in run_selftest(cfg, sinks=False)}
+    finally:
+        unsubscribe()
+
+    assert not results["bus"].ok
+    assert "broken" in results["bus"].detail
+    assert results["state"].ok
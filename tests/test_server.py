@@ -0,0 +1,88 @@
+import threading
+import urllib.error
+import urllib.request
+from http.server import ThreadingHTTPServer
+from pathlib import Path
+
+from usagedash.cli import _request_reload
+from usagedash.config import load_config
+from usagedash.pause import pause_state
+from usagedash.protocol import Message, decode, encode
+from usagedash.reload import ConfigWatcher
+from usagedash.server import SESSION_HEADER, _handler, _State, ensure_ipc_token, ipc_token_path
+
+
+def _serve(tmp_path: Path):
+    path = tmp_path / "config.toml"
+    path.write_text(f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n')
+    cfg = load_config(path)
+    state = _State(ensure_ipc_token(cfg))
+    server = ThreadingHTTPServer(("127.0.0.1", 0), _handler(ConfigWatcher(cfg, path), state))
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    return cfg, server, f"http://127.0.0.1:{server.server_address[1]}/ipc"
+
+
+def _post(url: str, body: bytes, headers: dict[str, str] | None = None) -> tuple[int, Message]:
+    req = urllib.request.Request(url, data=body, headers=headers or {}, method="POST")
+    try:
+        with urllib.request.urlopen(req, timeout=5) as resp:
+            return resp.status, decode(resp.read())
+    except urllib.error.HTTPError as exc:
+        return exc.code, decode(exc.read())
+
+
+def test_ipc_token_is_owner_only_and_kept_across_restarts(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+
+    token = ensure_ipc_token(cfg)
+
+    assert ipc_token_path(cfg).stat().st_mode & 0o777 == 0o600
+    assert ensure_ipc_token(cfg) == token
+
+
+def test_ipc_needs_a_session_and_the_token_for_privileged_requests(tmp_path: Path) -> None:
+    cfg, server, url = _serve(tmp_path)
+    try:
+        code, reply = _post(url, encode(Message(type="pause")).encode())
+        assert (code, reply.payload["error"]) == (400, "no session: send hello first")
+
+        code, welcome = _post(url, encode(Message(type="hello", payload={"versions": [1]})).encode())
+        assert code == 200 and welcome.type == "welcome"
+        session = {SESSION_HEADER: welcome.payload["session"]}
+
+        code, reply = _post(url, encode(Message(type="pause")).encode(), session)
+        assert code == 401 and "ipc.token" in reply.payload["error"]
+        code, reply = _post(url, encode(Message(type="pause")).encode(), {**session, "Authorization": "Bearer wrong"})
+        assert code == 401
+        assert pause_state(cfg) is None
+
+        token = ipc_token_path(cfg).read_text().strip()
+        code, reply = _post(url, encode(Message(type="pause")).encode(), {**session, "Authorization": f"Bearer {token}"})
+        assert (code, reply.type) == (200, "ok")
+        assert pause_state(cfg) is not None
+    finally:
+        server.shutdown()
+        server.server_close()
+
+
+def test_ipc_rejects_a_bad_content_length(tmp_path: Path) -> None:
+    _, server, url = _serve(tmp_path)
+    try:
+        code, reply = _post(url, b"{}", {"Content-Length": "lots"})
+        assert (code, reply.payload["error"]) == (400, "invalid Content-Length")
+        code, reply = _post(url, b"not json")
+        assert code == 400 and reply.payload["error"].startswith("malformed message")
+    finally:
+        server.shutdown()
+        server.server_close()
+
+
+def test_reload_command_negotiates_and_authenticates(tmp_path: Path, capsys) -> None:
+    cfg, server, url = _serve(tmp_path)
+    try:
+        assert _request_reload(cfg, url.removesuffix("/ipc")) == 0
+        assert capsys.readouterr().out == "config reloaded\n"
+    finally:
+        server.shutdown()
+        server.server_close()
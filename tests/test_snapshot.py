@@ -1,7 +1,12 @@
+import json
 from pathlib import Path
 
-from usagedash.config import load_config
-from usagedash.snapshot import build_snapshot, write_snapshot_files
+import pytest
+
+from usagedash.alerts import evaluate_alerts
+from usagedash.config import AlertRule, MaintenanceWindow, load_config
+from usagedash.pipeline import collect_or_reuse, run_cycle
+from usagedash.snapshot import build_snapshot, read_snapshot, snapshot_to_json, write_snapshot_files
 
 
 def test_snapshot_write(tmp_path: Path) -> None:
@@ -14,3 +19,189 @@ def test_snapshot_write(tmp_path: Path) -> None:
 
     assert Path(cfg.general.state_file).exists()
     assert Path(cfg.general.windows_state_path).exists()
+
+
+def test_state_file_is_compact_unless_pretty_state(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    snap = build_snapshot(cfg)
+
+    write_snapshot_files(cfg, snap)
+    assert "\n" not in Path(cfg.general.state_file).read_text()
+
+    cfg.general.pretty_state = True
+    write_snapshot_files(cfg, snap)
+    assert Path(cfg.general.windows_state_path).read_text().startswith("{\n")
+
+
+def test_mirror_fields_reduce_the_mirror_but_not_the_state_file(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    cfg.mirror.fields = ["weekly_used_pct", "details.long_window"]
+    snap = build_snapshot(cfg)
+    snap.providers[0].details = {"long_window": "monthly", "projects": ["secret-repo"]}
+    snap.providers[0].account = "work"
+
+    write_snapshot_files(cfg, snap)
+
+    raw = json.loads(Path(cfg.general.windows_state_path).read_text())["providers"][0]
+    assert sorted(raw) == ["details", "provider", "status", "weekly_used_pct"]
+    assert raw["details"] == {"long_window": "monthly"}
+    assert read_snapshot(cfg.general.windows_state_path).providers[0].details == {"long_window": "monthly"}
+    assert read_snapshot(cfg.general.state_file).providers[0].account == "work"
+
+
+def test_compressed_mirror_reads_back_transparently(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    cfg.mirror.compression = "gzip"
+    snap = build_snapshot(cfg)
+
+    write_snapshot_files(cfg, snap)
+
+    assert Path(cfg.general.windows_state_path).read_bytes()[:2] == b"\x1f\x8b"
+    assert [p.key for p in read_snapshot(cfg.general.windows_state_path).providers] == [p.key for p in snap.providers]
+
+
+def test_collect_or_reuse_returns_fresh_state_file(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    cfg.general.reuse_snapshot_seconds = 60
+
+    first = collect_or_reuse(cfg)
+    second = collect_or_reuse(cfg)
+
+    assert second.generated_at == first.generated_at
+
+
+def test_read_only_cycle_writes_nothing(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.general.read_only = True
+    cfg.general.state_file = str(tmp_path / "state" / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror" / "latest.json")
+
+    snap = run_cycle(cfg)
+
+    assert snap.providers
+    assert list(tmp_path.iterdir()) == []
+
+
+def test_cbor_state_round_trips_when_available(tmp_path: Path) -> None:
+    pytest.importorskip("cbor2")
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.state")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    cfg.general.state_format = "cbor"
+    snap = build_snapshot(cfg)
+
+    write_snapshot_files(cfg, snap)
+
+    assert Path(cfg.general.windows_state_path).read_text().startswith("{")
+    restored = read_snapshot(cfg.general.state_file)
+    assert restored.generated_at == snap.generated_at
+    assert [p.provider for p in restored.providers] == [p.provider for p in snap.providers]
+
+
+def test_maintenance_window_skips_collection_and_alerts(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml")
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    cfg.general.windows_state_path = str(tmp_path / "mirror.json")
+    cfg.providers["codex"].manual.weekly_used_pct = 99.0
+    cfg.alerts = [AlertRule(name="codex-weekly", provider="codex")]
+    cfg.maintenance = [MaintenanceWindow(name="always", cron="* * * * *", providers=["codex"])]
+
+    snap = build_snapshot(cfg)
+    codex = next(p for p in snap.providers if p.provider.value == "codex")
+
+    assert codex.details["maintenance"] == "always"
+    assert codex.weekly_used_pct is None
+    assert evaluate_alerts(cfg, snap, {}) == []
+
+
+def test_display_names_and_order_flow_into_snapshot(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text(
+        '[providers.claude]\ndisplay_name = "Claude (work)"\norder = -1\n'
+        '[providers.codex]\norder = 5\n'
+    )
+    cfg = load_config(path)
+
+    snap = build_snapshot(cfg)
+
+    assert [p.provider.value for p in snap.providers] == ["claude", "codex"]
+    assert snap.providers[0].label == "Claude (work)"
+    assert snap.providers[1].label == "codex"
+    assert cfg.providers["claude"].options == {}
+
+
+def test_serialized_order_does_not_depend_on_registration_or_detail_order(tmp_path: Path) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text(
+        '[providers.codex]\nparser_mode = "manual"\n[providers.claude]\nparser_mode = "manual"\n'
+        '[providers.gemini]\nenabled = false\n[providers.groq]\nenabled = true\nparser_mode = "manual"\norder = -1\n'
+    )
+    cfg = load_config(path)
+
+    snap = build_snapshot(cfg)
+    assert [p.provider.value for p in snap.providers][:3] == ["groq", "claude", "codex"]
+
+    snap.providers[0].details = {"zeta": 1, "alpha": {"b": 2, "a": 1}}
+    data = json.loads(snapshot_to_json(snap))
+    assert list(data["providers"][0]["details"]) == ["alpha", "zeta"]
+    assert list(data["providers"][0]["details"]["alpha"]) == ["b", "a"]
+
+
+def test_repeated_messages_count_up_and_log_once_per_hour(tmp_path: Path, capsys) -> None:
+    from usagedash.config import PROVIDER_DEFAULTS
+
+    lines = [f'[general]\nstate_file = "{tmp_path / "latest.json"}"\nwindows_state_path = "{tmp_path / "mirror.json"}"']
+    lines.append('[log]\nsinks = ["stderr"]\nmin_level = "warning"')
+    lines += [f"[providers.{name}]\nenabled = false" for name in PROVIDER_DEFAULTS if name != "groq"]
+    lines.append(f'[providers.groq]\nenabled = true\nlog_path = "{tmp_path / "missing.jsonl"}"')
+    path = tmp_path / "config.toml"
+    path.write_text("\n".join(lines) + "\n")
+    cfg = load_config(path)
+
+    for _ in range(3):
+        snap = run_cycle(cfg)
+
+    missing = snap.providers[0].diagnostics[0]
+    assert missing.code == "missing_source_file"
+    assert missing.count == 3 and missing.first_seen < missing.last_seen
+    assert "(3x, first 0m ago)" in snap.providers[0].rendered_messages()[0]
+    logged = [line for line in capsys.readouterr().err.splitlines() if "provider_message" in line]
+    assert logged == [
+        f"usagedash[warning] provider_message: groq: missing {tmp_path / 'missing.jsonl'}",
+        "usagedash[error] provider_message: groq: no usage metrics detected; configure providers.groq.manual.*",
+    ]
+
+
+def test_a_failing_adapter_becomes_an_error_row(tmp_path: Path, monkeypatch) -> None:
+    from usagedash.models import StatusKind
+    from usagedash.providers import ADAPTERS
+    from usagedash.providers.base import ProviderAdapter
+
+    class Broken(ProviderAdapter):
+        def collect(self, cfg):
+            raise AttributeError("'NoneType' object has no attribute 'get'")
+
+    monkeypatch.setitem(ADAPTERS, "groq", Broken)
+    path = tmp_path / "config.toml"
+    path.write_text(
+        f'[general]\nstate_file = "{tmp_path / "latest.json"}"\n'
+        '[providers.groq]\nenabled = true\n'
+        '[providers.ollama]\nenabled = true\nsession_hours = "five"\n'
+    )
+    cfg = load_config(path)
+
+    rows = {p.provider.value: p for p in build_snapshot(cfg).providers}
+
+    assert rows["groq"].status == StatusKind.ERROR
+    assert rows["groq"].messages == ["unexpected groq response: AttributeError: 'NoneType' object has no attribute 'get'"]
+    assert rows["ollama"].status == StatusKind.ERROR
+    assert rows["ollama"].messages == ["providers.ollama.session_hours must be a number, got 'five'"]
+    assert rows["claude"].provider.value == "claude"
@@ -0,0 +1,54 @@
+import json
+from pathlib import Path
+
+import pytest
+
+from usagedash.models import SNAPSHOT_SCHEMA_VERSION
+from usagedash.snapshot import PROVIDER_FIELD_ALIASES, snapshot_from_dict, snapshot_to_json
+
+# One fixture per shape a released version wrote. Add a new one whenever
+# the serialized form changes; never edit the old ones.
+FIXTURES = sorted(Path("tests/fixtures/snapshots").glob("*.json"))
+
+
+def _still_contains(new: object, old: object) -> bool:
+    """`old` survives in `new`; fields added since (at any depth) are fine."""
+    if isinstance(old, dict):
+        return isinstance(new, dict) and all(k in new and _still_contains(new[k], v) for k, v in old.items())
+    if isinstance(old, list):
+        return isinstance(new, list) and len(new) == len(old) and all(map(_still_contains, new, old))
+    return new == old
+
+
+@pytest.mark.parametrize("path", FIXTURES)
+def test_released_snapshots_still_load_and_round_trip(path: Path) -> None:
+    raw = json.loads(path.read_text())
+
+    snap = snapshot_from_dict(raw)
+    written = json.loads(snapshot_to_json(snap))
+
+    assert written["schema_version"] == SNAPSHOT_SCHEMA_VERSION
+    assert snapshot_from_dict(written) == snap
+    for old, new in zip(raw["providers"], written["providers"]):
+        # A field that disappeared from the output without an alias is a rename
+        # that would silently break tray and export readers.
+        missing = {k for k in old if k not in new and k not in PROVIDER_FIELD_ALIASES}
+        assert not missing, f"fields no longer written: {sorted(missing)}"
+        for key, value in old.items():
+            assert _still_contains(new[PROVIDER_FIELD_ALIASES.get(key, key)], value), key
+
+
+def test_aliased_field_names_load_into_their_current_field(monkeypatch) -> None:
+    raw = json.loads(Path("tests/fixtures/snapshots/v1_initial.json").read_text())
+    raw["providers"][0]["five_hour_used_pct"] = raw["providers"][0].pop("session_used_pct")
+    monkeypatch.setitem(PROVIDER_FIELD_ALIASES, "five_hour_used_pct", "session_used_pct")
+
+    assert snapshot_from_dict(raw).providers[0].session_used_pct == 42.0
+
+
+def test_snapshots_from_a_newer_schema_are_rejected() -> None:
+    # Kept outside fixtures/snapshots: it is not a shape any release here wrote.
+    raw = json.loads(Path("tests/fixtures/snapshot_future_schema.json").read_text())
+
+    with pytest.raises(ValueError, match="newer than this release"):
+        snapshot_from_dict(raw)
@@ -0,0 +1,27 @@
+import shutil
+from pathlib import Path
+
+from usagedash.config import load_config
+from usagedash.sources import SourceWatchdog
+
+
+def test_watchdog_logs_each_transition_once(tmp_path: Path, capsys) -> None:
+    path = tmp_path / "config.toml"
+    path.write_text('[log]\nsinks = ["stderr"]\n')
+    cfg = load_config(path)
+    root = tmp_path / ".claude"
+    root.mkdir()
+    watchdog = SourceWatchdog({"claude": lambda: root})
+
+    watchdog.check(cfg)
+    shutil.rmtree(root)
+    for _ in range(3):
+        watchdog.check(cfg)
+    root.mkdir()
+    watchdog.check(cfg)
+    watchdog.check(cfg)
+
+    assert capsys.readouterr().err.splitlines() == [
+        f"usagedash[warning] source_missing: claude: {root} disappeared; waiting for it to come back",
+        f"usagedash[info] source_restored: claude: {root} is back",
+    ]
@@ -0,0 +1,75 @@
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+from usagedash.api import history_records
+from usagedash.config import load_config
+from usagedash.history import snapshot_at
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.store import MemoryStore, SqliteStore, Store, store_path
+
+
+def _exercise(store: Store) -> None:
+    t0 = datetime(2026, 2, 16, 12, 0)
+    for i in range(3):
+        store.append_snapshot(UsageSnapshot(
+            generated_at=t0 + timedelta(hours=i),
+            providers=[ProviderSnapshot(provider=ProviderName.CODEX, status=StatusKind.OK, weekly_used_pct=10.0 * i)],
+        ))
+    store.append_alert({
+        "fired_at": t0.isoformat(), "rule": "w", "level": "warn", "provider": "codex",
+        "window": "weekly", "value": 81.0, "sink": "stderr", "delivered": True, "error": None,
+    })
+
+    assert store.last_snapshot_at() == t0 + timedelta(hours=2)
+    got = store.snapshots(since=t0 + timedelta(hours=1))
+    assert [s.providers[0].weekly_used_pct for s in got] == [10.0, 20.0]
+    assert store.alerts()[0]["delivered"] is True
+    assert store.prune(t0 + timedelta(hours=1)) == 1
+    assert len(store.snapshots()) == 2
+
+
+def test_memory_store() -> None:
+    _exercise(MemoryStore())
+
+
+def test_sqlite_store(tmp_path: Path) -> None:
+    with SqliteStore(tmp_path / "usagedash.db") as store:
+        _exercise(store)
+
+
+def test_sqlite_store_uses_wal_and_survives_reopen(tmp_path: Path) -> None:
+    path = tmp_path / "history.db"
+    with SqliteStore(path, fsync="always") as store:
+        mode = store._conn.execute("PRAGMA journal_mode").fetchone()[0]
+        store.append_snapshot(UsageSnapshot(generated_at=datetime(2026, 3, 1, 12), providers=[]))
+
+    assert mode == "wal"
+    with SqliteStore(path) as store:
+        assert len(store.snapshots()) == 1
+
+
+def test_snapshot_at_picks_latest_before_instant() -> None:
+    store = MemoryStore()
+    t0 = datetime(2026, 3, 1, 12, 0)
+    for i in range(3):
+        store.append_snapshot(UsageSnapshot(generated_at=t0 + timedelta(hours=i), providers=[]))
+    utc = timezone.utc
+
+    found = snapshot_at(store, (t0 + timedelta(hours=1, minutes=30)).replace(tzinfo=utc))
+
+    assert found is not None and found.generated_at == t0 + timedelta(hours=1)
+    assert snapshot_at(store, (t0 - timedelta(minutes=1)).replace(tzinfo=utc)) is None
+
+
+def test_history_records_flatten_stored_rows_for_notebooks(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    with SqliteStore(store_path(cfg)) as store:
+        _exercise(store)
+
+    records = history_records(cfg, provider="codex")
+
+    assert [r["weekly_used_pct"] for r in records] == [10.0, 20.0]
+    assert records[0]["at"] == datetime(2026, 2, 16, 13, 0)
+    assert records[0]["status"] == "ok"
+    assert history_records(cfg, provider="claude") == []
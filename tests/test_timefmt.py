@@ -0,0 +1,23 @@
+from datetime import datetime
+
+from usagedash import timefmt
+
+
+def test_clock_setting_overrides_locale() -> None:
+    dt = datetime(2026, 2, 16, 21, 5, 9)
+
+    timefmt.configure("C", "24h")
+    assert timefmt.format_reset(dt) == "Mon Feb 16  21:05"
+    assert timefmt.format_clock(dt) == "21:05:09"
+
+    timefmt.configure("C", "12h")
+    assert timefmt.format_reset(dt) == "Mon Feb 16  09:05 PM"
+
+    timefmt.configure("C", "auto")
+    assert timefmt.format_clock(dt) == "21:05:09"
+
+
+def test_format_duration_picks_largest_units() -> None:
+    assert timefmt.format_duration(59) == "0m"
+    assert timefmt.format_duration(3 * 3600 + 120) == "3h 2m"
+    assert timefmt.format_duration(2 * 86400 + 4 * 3600) == "2d 4h"
@@ -0,0 +1,32 @@
+import os
+from datetime import datetime
+from pathlib import Path
+
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.snapshot import snapshot_to_json
+from usagedash.tray.bridge import SummaryCache, about_text
+
+
+def _write(path: Path, weekly: float) -> None:
+    snap = UsageSnapshot(
+        generated_at=datetime(2026, 10, 17, 12),
+        providers=[ProviderSnapshot(ProviderName.CLAUDE, StatusKind.OK, weekly_used_pct=weekly)],
+    )
+    path.write_text(snapshot_to_json(snap))
+
+
+def test_summary_cache_rereads_only_after_the_file_changes(tmp_path: Path) -> None:
+    state = tmp_path / "latest.json"
+    cache = SummaryCache(str(state))
+    assert cache.line() == "UsageDash: snapshot missing"
+
+    _write(state, 40.0)
+    assert cache.line() == "claude:S- W40%"
+    assert cache.line() == "claude:S- W40%"
+    assert cache.reads == 1
+
+    _write(state, 55.0)
+    os.utime(state, ns=(state.stat().st_atime_ns, state.stat().st_mtime_ns + 1_000_000))
+    assert cache.line() == "claude:S- W55%"
+    assert cache.reads == 2
+    assert about_text(0.0, cache).endswith("2 reads")
@@ -0,0 +1,41 @@
+from datetime import datetime, timedelta, timezone
+from pathlib import Path
+
+from usagedash.config import load_config
+from usagedash.models import ProviderName, ProviderSnapshot, StatusKind, UsageSnapshot
+from usagedash.store import SqliteStore, store_path
+from usagedash.tui import ProviderRow, next_only, usage_series
+
+
+def test_usage_series_prefers_session_usage_and_keys_rows_by_account(tmp_path: Path) -> None:
+    cfg = load_config(tmp_path / "config.toml", create=False)
+    cfg.general.state_file = str(tmp_path / "latest.json")
+    now = datetime.now(timezone.utc).replace(tzinfo=None)
+    with SqliteStore(store_path(cfg)) as store:
+        for hours_ago, session in ((30, 90.0), (2, 10.0), (1, 20.0)):
+            store.append_snapshot(UsageSnapshot(
+                generated_at=now - timedelta(hours=hours_ago),
+                providers=[
+                    ProviderSnapshot(provider=ProviderName.CLAUDE, status=StatusKind.OK, session_used_pct=session, weekly_used_pct=50.0),
+                    ProviderSnapshot(provider=ProviderName.OPENAI, status=StatusKind.OK, weekly_used_pct=session / 2, account="work"),
+                ],
+            ))
+
+    series = usage_series(cfg)
+
+    # The 30-hour-old snapshot is outside the sparkline's day.
+    assert series == {"claude": [10.0, 20.0], "openai (work)": [5.0, 10.0]}
+
+
+def test_provider_toggle_cycles_through_rows_then_back_to_all() -> None:
+    keys = ["claude", "codex"]
+    assert next_only(keys, None) == "claude"
+    assert next_only(keys, "claude") == "codex"
+    assert next_only(keys, "codex") is None
+    assert next_only(keys, "gone") is None  # the shown row disappeared
+    assert next_only([], None) is None
+
+
+def test_provider_row_is_titled_with_the_display_name_and_account() -> None:
+    snap = ProviderSnapshot(provider=ProviderName.OPENAI, status=StatusKind.OK, display_name="OpenAI team", account="work")
+    assert ProviderRow(snap.label).card.title == "OpenAI team (work)"